@@ -1,20 +1,17 @@
-use alloc::string::{
-  String,
-  ToString,
-};
-use core::ops::Range;
+use alloc::boxed::Box;
+use alloc::string::String;
 
 use derive_more::Display;
-use pest::error::{
-  Error,
-  ErrorVariant,
-};
-use pest::Span;
+use pest::error::Error;
 
+use crate::ast::SpanRange;
+use crate::diagnostic::Diagnostic;
 use crate::parser::Rule;
 
 pub type AnalyzerResult<T> = Result<T, Error<Rule>>;
+pub(super) type DiagnosticResult<T> = Result<T, Box<Diagnostic>>;
 
+/// Represents a semantic error found during the analysis.
 #[derive(Debug, PartialEq, Eq, Clone, Display)]
 pub enum Err {
   #[display(fmt = "Nullable primary key: a nullable primary is not allowed")]
@@ -113,6 +110,7 @@ pub enum Err {
   MismatchedCompositeForeignKey,
 }
 
+/// Represents the reason why a foreign key is invalid.
 #[derive(Debug, PartialEq, Eq, Clone, Display)]
 pub enum InvalidForeignKeyErr {
   #[display(fmt = "the referenced column is neither a primary key or a unique key")]
@@ -133,13 +131,82 @@ pub enum InvalidForeignKeyErr {
   Many2ManyComposite,
 }
 
-pub(super) fn throw_err<T>(err: Err, span_range: &Range<usize>, input: &str) -> AnalyzerResult<T> {
-  let span = Span::new(input, span_range.start, span_range.end).unwrap();
+impl Err {
+  /// Returns the stable code of the error.
+  ///
+  /// Codes are never reused or renumbered, so they can be safely referenced by tools and docs.
+  pub fn code(&self) -> &'static str {
+    match self {
+      Self::NullablePrimaryKey => "E0001",
+      Self::ArrayPrimaryKey => "E0002",
+      Self::InvalidIndexesSetting => "E0003",
+      Self::DuplicateAttributeKey => "E0004",
+      Self::DuplicatePropertyKey => "E0005",
+      Self::DuplicateProjectSetting => "E0006",
+      Self::DuplicatePrimaryKey => "E0007",
+      Self::DuplicateUniqueKey => "E0008",
+      Self::DuplicateIndexKey => "E0009",
+      Self::DuplicateTableName => "E0010",
+      Self::DuplicateColumnName => "E0011",
+      Self::ConflictRelation => "E0012",
+      Self::DuplicateEnumName => "E0013",
+      Self::DuplicateEnumValue => "E0014",
+      Self::DuplicateTableGroupName => "E0015",
+      Self::DuplicateTableGroupItem => "E0016",
+      Self::DuplicateAlias => "E0017",
+      Self::ConflictNullableSetting => "E0018",
+      Self::ProjectSettingNotFound => "E0019",
+      Self::EmptyIndexesBlock => "E0020",
+      Self::InvalidEnum => "E0021",
+      Self::InvalidDataType => "E0022",
+      Self::InvalidDataTypeArguments { .. } => "E0023",
+      Self::InvalidArgumentValue => "E0024",
+      Self::InvalidDefaultValue { .. } => "E0025",
+      Self::DefaultNullInNonNullable => "E0026",
+      Self::DataTypeExceeded { .. } => "E0027",
+      Self::TableGroupNotFound => "E0028",
+      Self::SchemaNotFound => "E0029",
+      Self::TableNotFound => "E0030",
+      Self::ColumnNotFound => "E0031",
+      Self::EnumNotFound => "E0032",
+      Self::EnumValueNotFound => "E0033",
+      Self::MismatchedForeignKeyType { .. } => "E0034",
+      Self::InvalidForeignKey { err } => err.code(),
+      Self::MismatchedCompositeForeignKey => "E0041",
+    }
+  }
 
-  Err(Error::new_from_span(
-    ErrorVariant::CustomError {
-      message: err.to_string(),
-    },
-    span,
-  ))
+  /// Returns a suggestion on how to fix the error, if any.
+  pub fn help(&self) -> Option<&'static str> {
+    match self {
+      Self::NullablePrimaryKey => Some("remove the 'null' setting from the primary key column"),
+      Self::DuplicateTableName => Some("rename one of the tables or move it into another schema"),
+      Self::DuplicateColumnName => Some("rename one of the columns"),
+      Self::DuplicateEnumName => Some("rename one of the enums or move it into another schema"),
+      Self::DuplicateAlias => Some("aliases must be unique across all tables"),
+      Self::ConflictNullableSetting => Some("keep either 'null' or 'not null'"),
+      Self::ProjectSettingNotFound => Some("add a 'Project' block with a 'database_type' property"),
+      Self::ConflictRelation => Some("remove one of the relations between the columns"),
+      Self::InvalidForeignKey { .. } => Some("add a 'pk' or 'unique' setting to the referenced column(s)"),
+      _ => None,
+    }
+  }
+}
+
+impl InvalidForeignKeyErr {
+  /// Returns the stable code of the error.
+  pub fn code(&self) -> &'static str {
+    match self {
+      Self::NitherUniqueKeyNorPrimaryKey => "E0035",
+      Self::NitherUniqueKeyNorPrimaryKeyComposite => "E0036",
+      Self::One2One => "E0037",
+      Self::One2OneComposite => "E0038",
+      Self::Many2Many => "E0039",
+      Self::Many2ManyComposite => "E0040",
+    }
+  }
+}
+
+pub(super) fn throw_err<T>(err: Err, span_range: &SpanRange) -> DiagnosticResult<T> {
+  Err(Box::new(Diagnostic::semantic(err, span_range)))
 }
@@ -2,36 +2,24 @@ use alloc::collections::BTreeSet;
 
 use super::*;
 
-pub(super) fn check_attr_duplicate_keys(attrs: &Vec<Attribute>, input: &str) -> AnalyzerResult<()> {
+pub(super) fn check_attr_duplicate_keys(attrs: &[Attribute], diags: &mut Vec<Diagnostic>) {
   let mut acc = BTreeSet::new();
-  let dup_keys: Vec<_> = attrs
-    .iter()
-    .filter(|attr| !acc.insert(&attr.key.to_string))
-    .cloned()
-    .collect();
 
-  // TODO: handle multiple errs
-  if let Some(attr) = dup_keys.first() {
-    throw_err(Err::DuplicateAttributeKey, &attr.span_range, input)?;
+  for attr in attrs {
+    if !acc.insert(&attr.key.to_string) {
+      diags.push(Diagnostic::semantic(Err::DuplicateAttributeKey, &attr.span_range));
+    }
   }
-
-  Ok(())
 }
 
-pub(super) fn check_prop_duplicate_keys(attrs: &Vec<Property>, input: &str) -> AnalyzerResult<()> {
+pub(super) fn check_prop_duplicate_keys(props: &[Property], diags: &mut Vec<Diagnostic>) {
   let mut acc = BTreeSet::new();
-  let dup_keys: Vec<_> = attrs
-    .iter()
-    .filter(|attr| !acc.insert(&attr.key.to_string))
-    .cloned()
-    .collect();
 
-  // TODO: handle multiple errs
-  if let Some(prop) = dup_keys.first() {
-    throw_err(Err::DuplicatePropertyKey, &prop.span_range, input)?;
+  for prop in props {
+    if !acc.insert(&prop.key.to_string) {
+      diags.push(Diagnostic::semantic(Err::DuplicatePropertyKey, &prop.span_range));
+    }
   }
-
-  Ok(())
 }
 
 pub(super) fn eq_elements<T: Eq + Ord>(lhs: impl Iterator<Item = T>, rhs: impl Iterator<Item = T>) -> bool {
@@ -9,6 +9,9 @@ use alloc::string::{
 
 use super::*;
 
+/// The label message attached to the first definition of a duplicated item.
+const PREVIOUS_DEFINITION: &str = "previously defined here";
+
 /// Represents tables and enums within a given schema name.
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct IndexedSchemaBlock {
@@ -38,10 +41,13 @@ impl Indexer {
   /// - `DuplicateTableName`
   /// - `DuplicateColumnName`
   /// - `DuplicateAlias`
-  pub(super) fn index_table(&mut self, tables: &Vec<&TableBlock>, input: &str) -> AnalyzerResult<()> {
+  pub(super) fn index_table(&mut self, tables: &[&TableBlock], diags: &mut Vec<Diagnostic>) {
+    let mut table_spans: BTreeMap<_, &SpanRange> = BTreeMap::new();
+    let mut alias_spans: BTreeMap<_, &SpanRange> = BTreeMap::new();
+
     for table in tables {
       if let Some(settings) = &table.settings {
-        check_attr_duplicate_keys(&settings.attributes, input)?;
+        check_attr_duplicate_keys(&settings.attributes, diags);
       }
 
       let TableIdent {
@@ -56,39 +62,52 @@ impl Indexer {
         .map(|s| s.to_string.clone())
         .unwrap_or_else(|| DEFAULT_SCHEMA.to_string());
 
-      if self.contains_table(&schema, &name.to_string) {
-        throw_err(Err::DuplicateTableName, span_range, input)?;
+      if let Some(first_span) = table_spans.get(&(schema.clone(), name.to_string.clone())) {
+        diags
+          .push(Diagnostic::semantic(Err::DuplicateTableName, span_range).with_label(first_span, PREVIOUS_DEFINITION));
+        continue;
       }
+      table_spans.insert((schema.clone(), name.to_string.clone()), span_range);
 
-      let mut indexed_cols = BTreeSet::new();
+      let mut col_spans: BTreeMap<_, &SpanRange> = BTreeMap::new();
       for col in table.cols.iter() {
         if let Some(settings) = &col.settings {
-          check_attr_duplicate_keys(&settings.attributes, input)?;
+          check_attr_duplicate_keys(&settings.attributes, diags);
         }
 
-        if !indexed_cols.insert(col.name.to_string.clone()) {
-          throw_err(Err::DuplicateColumnName, &col.span_range, input)?;
+        match col_spans.get(&col.name.to_string) {
+          Some(first_span) => {
+            diags.push(
+              Diagnostic::semantic(Err::DuplicateColumnName, &col.span_range)
+                .with_label(first_span, PREVIOUS_DEFINITION),
+            );
+          }
+          None => {
+            col_spans.insert(col.name.to_string.clone(), &col.span_range);
+          }
         }
       }
 
       self
         .schema_map
         .entry(schema.clone())
-        .or_insert_with(IndexedSchemaBlock::default)
+        .or_default()
         .table_map
-        .insert(name.to_string.clone(), indexed_cols);
+        .insert(name.to_string.clone(), col_spans.into_keys().collect());
 
       if let Some(alias) = alias {
-        if let Some(_) = self
-          .table_alias_map
-          .insert(alias.to_string.clone(), (schema.clone(), name.to_string.clone()))
-        {
-          throw_err(Err::DuplicateAlias, &alias.span_range, input)?;
-        };
+        if let Some(first_span) = alias_spans.get(&alias.to_string) {
+          diags.push(
+            Diagnostic::semantic(Err::DuplicateAlias, &alias.span_range).with_label(first_span, PREVIOUS_DEFINITION),
+          );
+        } else {
+          alias_spans.insert(alias.to_string.clone(), &alias.span_range);
+          self
+            .table_alias_map
+            .insert(alias.to_string.clone(), (schema.clone(), name.to_string.clone()));
+        }
       }
     }
-
-    Ok(())
   }
 
   /// Collects and validates enum identifiers and their values.
@@ -97,7 +116,9 @@ impl Indexer {
   ///
   /// - `DuplicateEnumName`
   /// - `DuplicateEnumValue`
-  pub(super) fn index_enums(&mut self, enums: &Vec<&EnumBlock>, input: &str) -> AnalyzerResult<()> {
+  pub(super) fn index_enums(&mut self, enums: &[&EnumBlock], diags: &mut Vec<Diagnostic>) {
+    let mut enum_spans: BTreeMap<_, &SpanRange> = BTreeMap::new();
+
     for r#enum in enums.iter() {
       let EnumIdent {
         span_range,
@@ -111,30 +132,39 @@ impl Indexer {
         .map(|s| s.to_string.clone())
         .unwrap_or_else(|| DEFAULT_SCHEMA.into());
 
-      if self.contains_enum(&schema, &name.to_string) {
-        throw_err(Err::DuplicateEnumName, &span_range, input)?;
+      if let Some(first_span) = enum_spans.get(&(schema.clone(), name.to_string.clone())) {
+        diags
+          .push(Diagnostic::semantic(Err::DuplicateEnumName, span_range).with_label(first_span, PREVIOUS_DEFINITION));
+        continue;
       }
+      enum_spans.insert((schema.clone(), name.to_string.clone()), span_range);
 
-      let mut value_sets = BTreeSet::new();
+      let mut value_spans: BTreeMap<_, &SpanRange> = BTreeMap::new();
       for value in r#enum.values.iter() {
         if let Some(settings) = &value.settings {
-          check_attr_duplicate_keys(&settings.attributes, input)?;
+          check_attr_duplicate_keys(&settings.attributes, diags);
         }
 
-        if !value_sets.insert(value.value.to_string.clone()) {
-          throw_err(Err::DuplicateEnumValue, &value.span_range, input)?;
+        match value_spans.get(&value.value.to_string) {
+          Some(first_span) => {
+            diags.push(
+              Diagnostic::semantic(Err::DuplicateEnumValue, &value.span_range)
+                .with_label(first_span, PREVIOUS_DEFINITION),
+            );
+          }
+          None => {
+            value_spans.insert(value.value.to_string.clone(), &value.span_range);
+          }
         }
       }
 
       self
         .schema_map
         .entry(schema)
-        .or_insert_with(IndexedSchemaBlock::default)
+        .or_default()
         .enum_map
-        .insert(name.to_string.clone(), value_sets);
+        .insert(name.to_string.clone(), value_spans.into_keys().collect());
     }
-
-    Ok(())
   }
 
   /// Collects and validates table group identifiers and their items.
@@ -144,10 +174,14 @@ impl Indexer {
   /// - `DuplicateTableGroupName`
   /// - `TableNotFound`
   /// - `DuplicateTableGroupItem`
-  pub(super) fn index_table_groups(&mut self, table_groups: &Vec<&TableGroupBlock>, input: &str) -> AnalyzerResult<()> {
+  pub(super) fn index_table_groups(&mut self, table_groups: &[&TableGroupBlock], diags: &mut Vec<Diagnostic>) {
     for table_group in table_groups {
-      if self.table_group_map.get(&table_group.ident.to_string).is_some() {
-        throw_err(Err::DuplicateTableGroupName, &table_group.ident.span_range, input)?;
+      if self.table_group_map.contains_key(&table_group.ident.to_string) {
+        diags.push(Diagnostic::semantic(
+          Err::DuplicateTableGroupName,
+          &table_group.ident.span_range,
+        ));
+        continue;
       }
 
       let mut indexed_items = BTreeSet::new();
@@ -165,7 +199,8 @@ impl Indexer {
                   .any(|item| item.table_map.contains_key(&group_item.ident_alias.to_string));
 
                 if !has_table {
-                  throw_err(Err::TableNotFound, &group_item.span_range, input)?;
+                  diags.push(Diagnostic::semantic(Err::TableNotFound, &group_item.span_range));
+                  continue;
                 }
 
                 (DEFAULT_SCHEMA.to_string(), group_item.ident_alias.to_string.clone())
@@ -175,7 +210,10 @@ impl Indexer {
         };
 
         if !indexed_items.insert(ident) {
-          throw_err(Err::DuplicateTableGroupItem, &group_item.span_range, input)?;
+          diags.push(Diagnostic::semantic(
+            Err::DuplicateTableGroupItem,
+            &group_item.span_range,
+          ));
         }
       }
 
@@ -183,8 +221,6 @@ impl Indexer {
         .table_group_map
         .insert(table_group.ident.to_string.clone(), indexed_items);
     }
-
-    Ok(())
   }
 
   /// Checks if the specified table identifier exists.
@@ -210,7 +246,7 @@ impl Indexer {
     &self,
     schema: &Option<String>,
     enum_name: &String,
-    values: &Vec<String>,
+    values: &[String],
   ) -> (bool, (bool, Vec<bool>)) {
     let schema = schema.clone().unwrap_or_else(|| DEFAULT_SCHEMA.to_string());
 
@@ -230,13 +266,7 @@ impl Indexer {
   }
 
   /// Checks if the table contains the specified fields.
-  pub fn lookup_table_fields(
-    &self,
-    schema: &Option<Ident>,
-    table: &Ident,
-    fields: &Vec<Ident>,
-    input: &str,
-  ) -> AnalyzerResult<()> {
+  pub fn lookup_table_fields(&self, schema: &Option<Ident>, table: &Ident, fields: &[Ident]) -> DiagnosticResult<()> {
     let schema_span = schema.clone().map(|s| s.span_range).unwrap_or_default();
     let schema = schema
       .clone()
@@ -254,15 +284,15 @@ impl Indexer {
               .collect();
 
             if let Some(first) = unlisted_fields.first() {
-              throw_err(Err::ColumnNotFound, &first.span_range, input)?;
+              throw_err(Err::ColumnNotFound, &first.span_range)?;
             }
 
             Ok(())
           }
-          None => throw_err(Err::TableNotFound, &table.span_range, input),
+          None => throw_err(Err::TableNotFound, &table.span_range),
         }
       }
-      None => throw_err(Err::SchemaNotFound, &schema_span, input),
+      None => throw_err(Err::SchemaNotFound, &schema_span),
     }
   }
 
@@ -300,6 +330,8 @@ impl Indexer {
 pub struct IndexedRef {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
+  /// The name of the ref block, if any. Inline refs are always unnamed.
+  pub name: Option<Ident>,
   pub rel: Relation,
   pub lhs: RefIdent,
   pub rhs: RefIdent,
@@ -322,6 +354,7 @@ impl IndexedRef {
 
         Self {
           span_range,
+          name: None,
           rel,
           lhs,
           rhs,
@@ -331,19 +364,19 @@ impl IndexedRef {
       .collect()
   }
 
-  pub fn validate_ref_type(&self, tables: &Vec<TableBlock>, indexer: &Indexer, input: &str) -> AnalyzerResult<()> {
+  pub fn validate_ref_type(&self, tables: &[TableBlock], indexer: &Indexer) -> DiagnosticResult<()> {
     let lhs_ident = indexer.resolve_ref_alias(&self.lhs);
     let rhs_ident = indexer.resolve_ref_alias(&self.rhs);
 
     let composition_len = lhs_ident.compositions.len().max(rhs_ident.compositions.len());
     if lhs_ident.compositions.len() != rhs_ident.compositions.len() {
-      throw_err(Err::MismatchedCompositeForeignKey, &self.span_range, input)?;
+      throw_err(Err::MismatchedCompositeForeignKey, &self.span_range)?;
     }
 
-    indexer.lookup_table_fields(&lhs_ident.schema, &lhs_ident.table, &lhs_ident.compositions, input)?;
-    indexer.lookup_table_fields(&rhs_ident.schema, &rhs_ident.table, &rhs_ident.compositions, input)?;
+    indexer.lookup_table_fields(&lhs_ident.schema, &lhs_ident.table, &lhs_ident.compositions)?;
+    indexer.lookup_table_fields(&rhs_ident.schema, &rhs_ident.table, &rhs_ident.compositions)?;
 
-    let find_ref_table = |ref_ident: &RefIdent| -> AnalyzerResult<&TableBlock> {
+    let find_ref_table = |ref_ident: &RefIdent| -> DiagnosticResult<&TableBlock> {
       tables
         .iter()
         .find(|table| {
@@ -351,14 +384,14 @@ impl IndexedRef {
             && table.ident.name.to_string == ref_ident.table.to_string
         })
         .map(Ok)
-        .unwrap_or_else(|| throw_err(Err::TableNotFound, &ref_ident.span_range, input))
+        .unwrap_or_else(|| throw_err(Err::TableNotFound, &ref_ident.span_range))
     };
-    let find_ref_col = |col_ident: &Ident, table: &TableBlock| -> AnalyzerResult<TableColumn> {
+    let find_ref_col = |col_ident: &Ident, table: &TableBlock| -> DiagnosticResult<TableColumn> {
       let col = table.cols.iter().find(|col| col.name.to_string == col_ident.to_string);
 
       match col {
         Some(col) => Ok(col.clone()),
-        None => throw_err(Err::ColumnNotFound, &col_ident.span_range, input),
+        None => throw_err(Err::ColumnNotFound, &col_ident.span_range),
       }
     };
     let is_valid_composite = |compositions: &Vec<Ident>, table_indexes: &Option<IndexesBlock>| -> bool {
@@ -401,7 +434,7 @@ impl IndexedRef {
           l_type: l.raw.clone(),
         };
 
-        throw_err(err, &self.span_range, input)?;
+        throw_err(err, &self.span_range)?;
       }
 
       if composition_len == 1 {
@@ -422,43 +455,40 @@ impl IndexedRef {
         };
 
         if let Some((err, span_range)) = err {
-          throw_err(Err::InvalidForeignKey { err }, span_range, input)?;
+          throw_err(Err::InvalidForeignKey { err }, span_range)?;
         }
       }
     }
 
-    match composition_len {
-      2.. => {
-        let is_valid_lhs = is_valid_composite(&self.lhs.compositions, &lhs_table.indexes);
-        let is_valid_rhs = is_valid_composite(&self.rhs.compositions, &rhs_table.indexes);
+    if let 2.. = composition_len {
+      let is_valid_lhs = is_valid_composite(&self.lhs.compositions, &lhs_table.indexes);
+      let is_valid_rhs = is_valid_composite(&self.rhs.compositions, &rhs_table.indexes);
 
-        let err = match self.rel {
-          Relation::One2One if !is_valid_lhs && !is_valid_rhs => {
-            Some((InvalidForeignKeyErr::One2OneComposite, &self.span_range))
-          }
-          Relation::Many2Many if !is_valid_lhs || !is_valid_rhs => {
-            Some((InvalidForeignKeyErr::Many2ManyComposite, &self.span_range))
-          }
-          Relation::One2Many if !is_valid_lhs => {
-            Some((
-              InvalidForeignKeyErr::NitherUniqueKeyNorPrimaryKeyComposite,
-              &self.lhs.span_range,
-            ))
-          }
-          Relation::Many2One if !is_valid_rhs => {
-            Some((
-              InvalidForeignKeyErr::NitherUniqueKeyNorPrimaryKeyComposite,
-              &self.rhs.span_range,
-            ))
-          }
-          _ => None,
-        };
-
-        if let Some((err, span_range)) = err {
-          throw_err(Err::InvalidForeignKey { err }, span_range, input)?;
+      let err = match self.rel {
+        Relation::One2One if !is_valid_lhs && !is_valid_rhs => {
+          Some((InvalidForeignKeyErr::One2OneComposite, &self.span_range))
+        }
+        Relation::Many2Many if !is_valid_lhs || !is_valid_rhs => {
+          Some((InvalidForeignKeyErr::Many2ManyComposite, &self.span_range))
         }
+        Relation::One2Many if !is_valid_lhs => {
+          Some((
+            InvalidForeignKeyErr::NitherUniqueKeyNorPrimaryKeyComposite,
+            &self.lhs.span_range,
+          ))
+        }
+        Relation::Many2One if !is_valid_rhs => {
+          Some((
+            InvalidForeignKeyErr::NitherUniqueKeyNorPrimaryKeyComposite,
+            &self.rhs.span_range,
+          ))
+        }
+        _ => None,
+      };
+
+      if let Some((err, span_range)) = err {
+        throw_err(Err::InvalidForeignKey { err }, span_range)?;
       }
-      _ => (),
     };
 
     Ok(())
@@ -518,6 +548,7 @@ impl From<RefBlock> for IndexedRef {
   fn from(ref_block: RefBlock) -> Self {
     Self {
       span_range: ref_block.span_range,
+      name: ref_block.name,
       rel: ref_block.rel,
       lhs: ref_block.lhs,
       rhs: ref_block.rhs,
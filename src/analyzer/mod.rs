@@ -5,9 +5,10 @@ use core::str::FromStr;
 
 use self::err::*;
 use crate::ast::*;
+use crate::diagnostic::Diagnostic;
 use crate::DEFAULT_SCHEMA;
 
-mod err;
+pub mod err;
 mod helper;
 mod indexer;
 
@@ -42,6 +43,7 @@ pub struct TableRef {
 ///
 /// An `AnalyzerResult<AnalyzedIndexer>`, which is an alias for the result of semantic analysis.
 /// It contains the indexing metadata for the collecting table relations and block names representing the parsed and analyzed DBML.
+/// If the AST has several semantic errors, only the first one is returned. Use `analyze_all` to collect all of them.
 ///
 /// # Examples
 ///
@@ -66,7 +68,42 @@ pub struct TableRef {
 /// // of the parsed and analyzed DBML text.
 /// ```
 pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
-  let input = schema_block.input;
+  let (analyzed_indexer, diags) = analyze_all(schema_block);
+
+  match diags.into_iter().next() {
+    Some(diag) => Err(diag.to_pest_error(schema_block.input)),
+    None => Ok(analyzed_indexer),
+  }
+}
+
+/// Performs semantic checks of the unsanitized AST without stopping at the first error.
+///
+/// Independent tables, columns, enums, table groups and refs keep being analyzed after a failure,
+/// so every semantic error of the schema is reported at once.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST representing the parsed DBML text.
+///
+/// # Returns
+///
+/// A tuple of the indexing metadata (which can be partial if any error is found)
+/// and all diagnostics in the order they are found.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::{parse_dbml_unchecked, analyze_all};
+///
+/// let ast = parse_dbml_unchecked(dbml_text).unwrap();
+/// let (analyzed_indexer, diags) = analyze_all(&ast);
+///
+/// for diag in diags {
+///     eprintln!("{}", diag);
+/// }
+/// ```
+pub fn analyze_all(schema_block: &SchemaBlock) -> (AnalyzedIndexer, Vec<Diagnostic>) {
+  let mut diags = vec![];
   let project = schema_block.project();
   let tables = schema_block.tables();
   let table_groups = schema_block.table_groups();
@@ -75,13 +112,21 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
 
   // check project block
   if project.len() > 1 {
-    throw_err(Err::DuplicateProjectSetting, &schema_block.span_range, input)?;
+    diags.push(Diagnostic::semantic(
+      Err::DuplicateProjectSetting,
+      &schema_block.span_range,
+    ));
   }
   match project.first() {
     Some(project_block) => {
-      check_prop_duplicate_keys(&project_block.properties, input)?;
+      check_prop_duplicate_keys(&project_block.properties, &mut diags);
+    }
+    _ => {
+      diags.push(Diagnostic::semantic(
+        Err::ProjectSettingNotFound,
+        &schema_block.span_range,
+      ))
     }
-    _ => throw_err(Err::ProjectSettingNotFound, &schema_block.span_range, input)?,
   }
 
   // collect tables
@@ -89,9 +134,9 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
   let mut indexed_refs: Vec<_> = refs.into_iter().cloned().map(IndexedRef::from).collect();
 
   // start indexing the schema
-  indexer.index_table(&tables, input)?;
-  indexer.index_enums(&enums, input)?;
-  indexer.index_table_groups(&table_groups, input)?;
+  indexer.index_table(&tables, &mut diags);
+  indexer.index_enums(&enums, &mut diags);
+  indexer.index_table_groups(&table_groups, &mut diags);
 
   // index inside the table itself
   for table in &tables {
@@ -102,13 +147,13 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
       if let Some(settings) = &col.settings {
         if settings.is_pk {
           if !tmp_table_indexer.pk_list.is_empty() {
-            throw_err(Err::DuplicatePrimaryKey, &col.span_range, input)?;
+            diags.push(Diagnostic::semantic(Err::DuplicatePrimaryKey, &col.span_range));
           }
           if settings.nullable == Some(Nullable::Null) {
-            throw_err(Err::NullablePrimaryKey, &col.span_range, input)?;
+            diags.push(Diagnostic::semantic(Err::NullablePrimaryKey, &col.span_range));
           }
           if !col.r#type.arrays.is_empty() {
-            throw_err(Err::ArrayPrimaryKey, &col.span_range, input)?;
+            diags.push(Diagnostic::semantic(Err::ArrayPrimaryKey, &col.span_range));
           }
 
           tmp_table_indexer.pk_list.push(col.name.to_string.clone())
@@ -127,7 +172,7 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
           .collect();
 
         if filtered.len() == 2 {
-          throw_err(Err::ConflictNullableSetting, &settings.span_range, input)?;
+          diags.push(Diagnostic::semantic(Err::ConflictNullableSetting, &settings.span_range));
         }
       }
 
@@ -145,7 +190,8 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
     if let Some(indexes_block) = &table.indexes {
       for def in &indexes_block.defs {
         if def.cols.is_empty() {
-          throw_err(Err::EmptyIndexesBlock, &indexes_block.span_range, input)?;
+          diags.push(Diagnostic::semantic(Err::EmptyIndexesBlock, &indexes_block.span_range));
+          continue;
         }
 
         let idents: Vec<_> = def
@@ -164,27 +210,27 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
         for ident in &def.cols {
           if let IndexesColumnType::String(col_name) = ident {
             if !table.cols.iter().any(|col| col.name.to_string == col_name.to_string) {
-              throw_err(Err::ColumnNotFound, &col_name.span_range, input)?;
+              diags.push(Diagnostic::semantic(Err::ColumnNotFound, &col_name.span_range));
             }
           }
         }
 
         match &def.settings {
           Some(settings) => {
-            check_attr_duplicate_keys(&settings.attributes, input)?;
+            check_attr_duplicate_keys(&settings.attributes, &mut diags);
 
-            if vec![settings.is_pk, settings.is_unique, settings.r#type.is_some()]
+            if [settings.is_pk, settings.is_unique, settings.r#type.is_some()]
               .into_iter()
               .filter(|x| *x)
               .count()
               > 1
             {
-              throw_err(Err::InvalidIndexesSetting, &settings.span_range, input)?;
+              diags.push(Diagnostic::semantic(Err::InvalidIndexesSetting, &settings.span_range));
             }
 
             if settings.is_pk {
               if !tmp_table_indexer.pk_list.is_empty() {
-                throw_err(Err::DuplicatePrimaryKey, &def.span_range, input)?;
+                diags.push(Diagnostic::semantic(Err::DuplicatePrimaryKey, &def.span_range));
               }
 
               tmp_table_indexer.pk_list.extend(ident_strings.clone())
@@ -194,7 +240,7 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
                 .iter()
                 .any(|uniq_item| idents.iter().all(|id| uniq_item.contains(&id.to_string)))
               {
-                throw_err(Err::DuplicateUniqueKey, &def.span_range, input)?;
+                diags.push(Diagnostic::semantic(Err::DuplicateUniqueKey, &def.span_range));
               }
 
               tmp_table_indexer
@@ -208,7 +254,7 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
                 .iter()
                 .any(|(idx_item, idx_type)| idx_item == &ident_strings && idx_type == &settings.r#type)
               {
-                throw_err(Err::DuplicateIndexKey, &def.span_range, input)?;
+                diags.push(Diagnostic::semantic(Err::DuplicateIndexKey, &def.span_range));
               }
 
               tmp_table_indexer
@@ -222,7 +268,7 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
               .iter()
               .any(|(idx_item, _)| idx_item == &ident_strings)
             {
-              throw_err(Err::DuplicateIndexKey, &def.span_range, input)?;
+              diags.push(Diagnostic::semantic(Err::DuplicateIndexKey, &def.span_range));
             }
 
             tmp_table_indexer.indexed_list.push((ident_strings, None))
@@ -233,189 +279,266 @@ pub fn analyze(schema_block: &SchemaBlock) -> AnalyzerResult<AnalyzedIndexer> {
   }
 
   // validate table column types
-  let tables = tables
+  let tables: Vec<_> = tables
     .into_iter()
     .map(|table| {
-      let cols = table.cols
-        .clone()
-        .into_iter()
+      let cols = table
+        .cols
+        .iter()
         .map(|col| {
-          let type_name = col.r#type.type_name;
+          let type_name = resolve_col_type(col, &indexer).unwrap_or_else(|diag| {
+            diags.push(*diag);
 
-          let type_name = match type_name {
-            ColumnTypeName::Undef => {
-              unreachable!("undef field type must not appear");
-            }
-            ColumnTypeName::Raw(raw_type) => {
-              match ColumnTypeName::from_str(&raw_type) {
-                Ok(type_name) => {
-                  if !col.r#type.args.is_empty() {
-                    // TODO: add support for interval
-                    match type_name {
-                      ColumnTypeName::VarChar
-                      | ColumnTypeName::Char
-                      | ColumnTypeName::Time
-                      | ColumnTypeName::Timestamp
-                      | ColumnTypeName::Timetz
-                      | ColumnTypeName::Timestamptz
-                      | ColumnTypeName::Bit
-                      | ColumnTypeName::Varbit => {
-                        if col.r#type.args.len() != 1 {
-                          throw_err(Err::InvalidDataTypeArguments { raw_type, n_arg: 1 }, &col.r#type.span_range, input)?;
-                        }
-                      }
-                      ColumnTypeName::Decimal => {
-                        if col.r#type.args.len() != 2 {
-                          throw_err(Err::InvalidDataTypeArguments { raw_type, n_arg: 2 }, &col.r#type.span_range, input)?;
-                        }
-                      }
-                      _ =>  {
-                        throw_err(Err::InvalidDataTypeArguments { raw_type, n_arg: 0 }, &col.r#type.span_range, input)?
-                      },
-                    };
-
-                    if !col.r#type.args.iter().all(|arg| matches!(arg, Value::Integer(_))) {
-                      throw_err(Err::InvalidArgumentValue, &col.r#type.span_range, input)?;
-                    }
-                  }
-                  
-                  type_name
-                }
-                Err(_) => {
-                  let splited: Vec<_> = raw_type.split('.').collect();
-
-                  let (enum_schema, enum_name) = match splited.len() {
-                    1 => (None, raw_type),
-                    2 => (Some(splited[0].to_string()), splited[1].to_string()),
-                    _ => throw_err(Err::InvalidEnum, &col.r#type.span_range, input)?,
-                  };
-
-                  match &col.settings {
-                    Some(ColumnSettings { attributes, default: Some(default_value), .. }) => {
-                      let default_value_span = attributes.iter()
-                        .find_map(|attr| {
-                          (attr.key.to_string == "default").then(|| attr.value.as_ref().map(|v| &v.span_range))
-                        })
-                        .and_then(|opt_span| opt_span)
-                        .unwrap_or_else(|| unreachable!("default value is missing"));
-
-                      match indexer.lookup_enum_values(&enum_schema, &enum_name, &vec![default_value.to_string()]) {
-                        (false, (_, _)) => throw_err(Err::SchemaNotFound, &col.r#type.span_range, input)?,
-                        (true, (false, _)) => throw_err(Err::EnumNotFound, &col.r#type.span_range, input)?,
-                        (true, (true, f)) if f.iter().any(|f| f == &false) => throw_err(Err::EnumValueNotFound, &default_value_span, input)?,
-                        _ => ColumnTypeName::Enum(enum_name)
-                      }
-                    }
-                    _ => {
-                      ColumnTypeName::Enum(enum_name)
-                    }
-                  }
-                }
-              }
-            }
-            _ => unreachable!("preprocessing data type name is not raw"),
-          };
+            fallback_col_type(col)
+          });
 
-          // TODO: add more validation
-          if let Some(ColumnSettings { attributes, default: Some(default_value), .. }) = &col.settings {
-            let span_range = attributes.iter()
-              .find_map(|attr| {
-                (attr.key.to_string == "default").then(|| attr.value.as_ref().map(|v| &v.span_range))
-              })
-              .and_then(|opt_span| opt_span)
-              .unwrap_or_else(|| unreachable!("default value is missing"));
-
-            // validate default value association with a col type
-            match default_value {
-              Value::Enum(_) => (),
-              Value::String(val) => {
-                let err = Err::InvalidDefaultValue { raw_value: val.clone(), raw_type: col.r#type.raw.clone() };
-
-                // TODO: validate which type can be strings
-
-                // validate fixed and variable length data type
-                match type_name {
-                  ColumnTypeName::Bit
-                  | ColumnTypeName::Char
-                  if matches!(col.r#type.args[0], Value::Integer(len) if val.len() as i64 != len) => {
-                    throw_err(err.clone(), &span_range, input)?;
-                  }
-                  ColumnTypeName::Varbit
-                  | ColumnTypeName::VarChar
-                  if matches!(col.r#type.args[0], Value::Integer(cap) if val.len() as i64 > cap) => {
-                    throw_err(err.clone(), &span_range, input)?;
-                  }
-                  _ => ()
-                };
-              },
-              Value::Integer(val) => {
-                let err = Err::DataTypeExceeded { raw_type: col.r#type.raw.clone() };
-
-                // TODO: validate which type can be numbers
-
-                match type_name {
-                  ColumnTypeName::SmallInt
-                  if (*val > i16::MAX as i64) || (*val < i16::MIN as i64) => {
-                    throw_err(err.clone(), &span_range, input)?;
-                  }
-                  ColumnTypeName::Integer
-                  if (*val > i32::MAX as i64) || (*val < i32::MIN as i64) => {
-                    throw_err(err.clone(), &span_range, input)?;
-                  }
-                  ColumnTypeName::BigInt
-                  if val.overflowing_add(1).1 || val.overflowing_sub(1).1 => {
-                    throw_err(err.clone(), &span_range, input)?;
-                  }
-                  _ => ()
-                };
-              },
-              Value::Decimal(_) => (),
-              Value::Bool(val) => {
-                if ![ColumnTypeName::Bool].contains(&type_name) {
-                  throw_err(Err::InvalidDefaultValue { raw_value: val.to_string(), raw_type: col.r#type.raw.clone() }, &span_range, input)?;
-                }
-              }
-              Value::HexColor(_) => (),
-              Value::Expr(_) => (),
-              Value::Null => {
-                if !col.settings.as_ref().is_some_and(|s| s.nullable == Some(Nullable::Null)) {
-                  throw_err(Err::DefaultNullInNonNullable, &span_range, input)?;
-                }
-              }
-            }
+          if let Err(diag) = validate_col_default(col, &type_name) {
+            diags.push(*diag);
           }
 
-          Ok(TableColumn {
+          TableColumn {
             r#type: ColumnType {
               type_name,
-              ..col.r#type
+              ..col.r#type.clone()
             },
-            ..col
-          })
+            ..col.clone()
+          }
         })
-        .collect::<AnalyzerResult<_>>()?;
+        .collect();
 
-      Ok(TableBlock { cols, ..table.clone() })
+      TableBlock { cols, ..table.clone() }
     })
-    .collect::<AnalyzerResult<_>>()?;
+    .collect();
 
   // validate ref
-  for indexed_ref in &indexed_refs {
+  let mut is_valid_refs = vec![];
+  for (i, indexed_ref) in indexed_refs.iter().enumerate() {
     if let Some(settings) = &indexed_ref.settings {
-      check_attr_duplicate_keys(&settings.attributes, input)?;
+      check_attr_duplicate_keys(&settings.attributes, &mut diags);
     }
 
-    indexed_ref.validate_ref_type(&tables, &indexer, input)?;
+    let is_valid = match indexed_ref.validate_ref_type(&tables, &indexer) {
+      Ok(()) => true,
+      Err(diag) => {
+        diags.push(*diag);
+        false
+      }
+    };
+    is_valid_refs.push(is_valid);
 
-    let count = indexed_refs
+    // a conflict is reported once, by the first valid ref involved in it
+    let is_reported = indexed_refs[..i]
+      .iter()
+      .zip(&is_valid_refs)
+      .any(|(other_indexed_ref, is_valid)| *is_valid && indexed_ref.occupy_same_column(other_indexed_ref, &indexer));
+    if !is_valid || is_reported {
+      continue;
+    }
+
+    let conflicts: Vec<_> = indexed_refs[i + 1..]
       .iter()
       .filter(|other_indexed_ref| indexed_ref.occupy_same_column(other_indexed_ref, &indexer))
-      .count();
+      .collect();
+
+    if !conflicts.is_empty() {
+      let diag = conflicts.iter().fold(
+        Diagnostic::semantic(Err::ConflictRelation, &indexed_ref.span_range),
+        |diag, other| diag.with_label(&other.span_range, "conflicting relation here"),
+      );
+
+      diags.push(diag);
+    }
+  }
+
+  (AnalyzedIndexer { indexed_refs, indexer }, diags)
+}
+
+/// Resolves the raw column type into a validated data type or an enum type.
+fn resolve_col_type(col: &TableColumn, indexer: &Indexer) -> DiagnosticResult<ColumnTypeName> {
+  let type_name = match col.r#type.type_name.clone() {
+    ColumnTypeName::Undef => {
+      unreachable!("undef field type must not appear");
+    }
+    ColumnTypeName::Raw(raw_type) => {
+      match ColumnTypeName::from_str(&raw_type) {
+        Ok(type_name) => {
+          if !col.r#type.args.is_empty() {
+            // TODO: add support for interval
+            match type_name {
+              ColumnTypeName::VarChar
+              | ColumnTypeName::Char
+              | ColumnTypeName::Time
+              | ColumnTypeName::Timestamp
+              | ColumnTypeName::Timetz
+              | ColumnTypeName::Timestamptz
+              | ColumnTypeName::Bit
+              | ColumnTypeName::Varbit => {
+                if col.r#type.args.len() != 1 {
+                  throw_err(
+                    Err::InvalidDataTypeArguments { raw_type, n_arg: 1 },
+                    &col.r#type.span_range,
+                  )?;
+                }
+              }
+              ColumnTypeName::Decimal => {
+                if col.r#type.args.len() != 2 {
+                  throw_err(
+                    Err::InvalidDataTypeArguments { raw_type, n_arg: 2 },
+                    &col.r#type.span_range,
+                  )?;
+                }
+              }
+              _ => {
+                throw_err(
+                  Err::InvalidDataTypeArguments { raw_type, n_arg: 0 },
+                  &col.r#type.span_range,
+                )?
+              }
+            };
+
+            if !col.r#type.args.iter().all(|arg| matches!(arg, Value::Integer(_))) {
+              throw_err(Err::InvalidArgumentValue, &col.r#type.span_range)?;
+            }
+          }
+
+          type_name
+        }
+        Err(_) => {
+          let splited: Vec<_> = raw_type.split('.').collect();
+
+          let (enum_schema, enum_name) = match splited.len() {
+            1 => (None, raw_type),
+            2 => (Some(splited[0].to_string()), splited[1].to_string()),
+            _ => throw_err(Err::InvalidEnum, &col.r#type.span_range)?,
+          };
+
+          match &col.settings {
+            Some(ColumnSettings {
+              attributes,
+              default: Some(default_value),
+              ..
+            }) => {
+              let default_value_span = find_default_value_span(attributes);
+
+              match indexer.lookup_enum_values(&enum_schema, &enum_name, &[default_value.to_string()]) {
+                (false, (_, _)) => throw_err(Err::SchemaNotFound, &col.r#type.span_range)?,
+                (true, (false, _)) => throw_err(Err::EnumNotFound, &col.r#type.span_range)?,
+                (true, (true, f)) if f.iter().any(|f| f == &false) => {
+                  throw_err(Err::EnumValueNotFound, default_value_span)?
+                }
+                _ => ColumnTypeName::Enum(enum_name),
+              }
+            }
+            _ => ColumnTypeName::Enum(enum_name),
+          }
+        }
+      }
+    }
+    _ => unreachable!("preprocessing data type name is not raw"),
+  };
+
+  Ok(type_name)
+}
 
-    if count != 1 {
-      throw_err(Err::ConflictRelation, &indexed_ref.span_range, input)?;
+/// Gets the best-effort data type of a column which fails to be resolved
+/// so that the following checks do not report errors caused by the same column.
+fn fallback_col_type(col: &TableColumn) -> ColumnTypeName {
+  match &col.r#type.type_name {
+    ColumnTypeName::Raw(raw_type) => {
+      ColumnTypeName::from_str(raw_type).unwrap_or_else(|_| ColumnTypeName::Enum(raw_type.clone()))
     }
+    type_name => type_name.clone(),
   }
+}
+
+/// Validates the default value association with the resolved column type.
+fn validate_col_default(col: &TableColumn, type_name: &ColumnTypeName) -> DiagnosticResult<()> {
+  // TODO: add more validation
+  if let Some(ColumnSettings {
+    attributes,
+    default: Some(default_value),
+    ..
+  }) = &col.settings
+  {
+    let span_range = find_default_value_span(attributes);
+
+    // validate default value association with a col type
+    match default_value {
+      Value::Enum(_) => (),
+      Value::String(val) => {
+        let err = Err::InvalidDefaultValue {
+          raw_value: val.clone(),
+          raw_type: col.r#type.raw.clone(),
+        };
+
+        // TODO: validate which type can be strings
+
+        // validate fixed and variable length data type
+        match type_name {
+          ColumnTypeName::Bit | ColumnTypeName::Char if matches!(col.r#type.args.first(), Some(Value::Integer(len)) if val.len() as i64 != *len) =>
+          {
+            throw_err(err, span_range)?;
+          }
+          ColumnTypeName::Varbit | ColumnTypeName::VarChar if matches!(col.r#type.args.first(), Some(Value::Integer(cap)) if val.len() as i64 > *cap) =>
+          {
+            throw_err(err, span_range)?;
+          }
+          _ => (),
+        };
+      }
+      Value::Integer(val) => {
+        let err = Err::DataTypeExceeded {
+          raw_type: col.r#type.raw.clone(),
+        };
+
+        // TODO: validate which type can be numbers
+
+        match type_name {
+          ColumnTypeName::SmallInt if (*val > i16::MAX as i64) || (*val < i16::MIN as i64) => {
+            throw_err(err, span_range)?;
+          }
+          ColumnTypeName::Integer if (*val > i32::MAX as i64) || (*val < i32::MIN as i64) => {
+            throw_err(err, span_range)?;
+          }
+          ColumnTypeName::BigInt if val.overflowing_add(1).1 || val.overflowing_sub(1).1 => {
+            throw_err(err, span_range)?;
+          }
+          _ => (),
+        };
+      }
+      Value::Decimal(_) => (),
+      Value::Bool(val) => {
+        if ![ColumnTypeName::Bool].contains(type_name) {
+          throw_err(
+            Err::InvalidDefaultValue {
+              raw_value: val.to_string(),
+              raw_type: col.r#type.raw.clone(),
+            },
+            span_range,
+          )?;
+        }
+      }
+      Value::HexColor(_) => (),
+      Value::Expr(_) => (),
+      Value::Null => {
+        if !col
+          .settings
+          .as_ref()
+          .is_some_and(|s| s.nullable == Some(Nullable::Null))
+        {
+          throw_err(Err::DefaultNullInNonNullable, span_range)?;
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
 
-  Ok(AnalyzedIndexer { indexed_refs, indexer })
+fn find_default_value_span(attributes: &[Attribute]) -> &SpanRange {
+  attributes
+    .iter()
+    .find_map(|attr| (attr.key.to_string == "default").then(|| attr.value.as_ref().map(|v| &v.span_range)))
+    .and_then(|opt_span| opt_span)
+    .unwrap_or_else(|| unreachable!("default value is missing"))
 }
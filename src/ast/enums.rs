@@ -1,10 +1,13 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 
 use super::*;
 
 /// Represents a top-level block of enum.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct EnumBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -16,6 +19,8 @@ pub struct EnumBlock {
 
 /// Represents an enum value or variant.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct EnumValue {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -25,6 +30,8 @@ pub struct EnumValue {
 
 /// Represents settings of an enum value or variant.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct EnumValueSettings {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -36,6 +43,8 @@ pub struct EnumValueSettings {
 
 /// Represents an enum identifier including schema (optional) and name.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct EnumIdent {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -44,3 +53,42 @@ pub struct EnumIdent {
   /// The name of the enum.
   pub name: Ident,
 }
+
+impl fmt::Display for EnumBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "Enum {} {{", self.ident)?;
+
+    for value in &self.values {
+      writeln!(f, "  {}", value)?;
+    }
+
+    write!(f, "}}")
+  }
+}
+
+impl fmt::Display for EnumIdent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(schema) = &self.schema {
+      write!(f, "{}.", schema)?;
+    }
+
+    write!(f, "{}", self.name)
+  }
+}
+
+impl fmt::Display for EnumValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.value)?;
+
+    match &self.settings {
+      Some(settings) if !settings.attributes.is_empty() => write!(f, " {}", settings),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for EnumValueSettings {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_settings(f, &self.attributes)
+  }
+}
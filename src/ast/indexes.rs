@@ -1,12 +1,17 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 use core::str::FromStr;
 
+use derive_more::Display;
+
 use super::*;
 
 /// Represents an indexes block inside a table block.
 /// Indexes allow users to quickly locate and access the data. Users can define single or multi-column indexes.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct IndexesBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -16,6 +21,8 @@ pub struct IndexesBlock {
 
 /// Represents an indexes definition or each item in an indexes block.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct IndexesDef {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -27,6 +34,8 @@ pub struct IndexesDef {
 
 /// Represents settings of an indexes definition.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct IndexesSettings {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -46,6 +55,8 @@ pub struct IndexesSettings {
 
 /// Represents the type of column for indexing.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum IndexesColumnType {
   /// Represents a column name with the given identifier.
   String(Ident),
@@ -54,15 +65,21 @@ pub enum IndexesColumnType {
 }
 
 /// Represents different types of indexes that can be used.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum IndexesType {
   /// Represents a B-tree index.
+  #[display(fmt = "btree")]
   BTree,
   /// Represents a GIN (Generalized Inverted Index) index.
+  #[display(fmt = "gin")]
   Gin,
   /// Represents a GiST (Generalized Search Tree) index.
+  #[display(fmt = "gist")]
   Gist,
   /// Represents a hash index.
+  #[display(fmt = "hash")]
   Hash,
 }
 
@@ -79,3 +96,44 @@ impl FromStr for IndexesType {
     }
   }
 }
+
+impl fmt::Display for IndexesDef {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.cols.as_slice() {
+      [col] => write!(f, "{}", col)?,
+      cols => {
+        write!(f, "(")?;
+
+        for (i, col) in cols.iter().enumerate() {
+          if i != 0 {
+            write!(f, ", ")?;
+          }
+
+          write!(f, "{}", col)?;
+        }
+
+        write!(f, ")")?;
+      }
+    }
+
+    match &self.settings {
+      Some(settings) if !settings.attributes.is_empty() => write!(f, " {}", settings),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for IndexesColumnType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::String(ident) => write!(f, "{}", ident),
+      Self::Expr(literal) => write!(f, "`{}`", literal.value),
+    }
+  }
+}
+
+impl fmt::Display for IndexesSettings {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_settings(f, &self.attributes)
+  }
+}
@@ -1,4 +1,5 @@
-pub(crate) type SpanRange = Range<usize>;
+/// The byte range of a node in the source text.
+pub type SpanRange = Range<usize>;
 
 mod enums;
 mod indexes;
@@ -1,11 +1,14 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 use core::str::FromStr;
 
 use super::*;
 
 /// Represents different types of databases.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum DatabaseType {
   #[default]
   Undef,
@@ -24,6 +27,11 @@ impl FromStr for DatabaseType {
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     match s {
       "PostgreSQL" => Ok(Self::PostgreSQL),
+      "MySQL" => Ok(Self::MySQL),
+      "MariaDB" => Ok(Self::MariaDB),
+      "MSSQL" => Ok(Self::MSSQL),
+      "SQLite" => Ok(Self::SQLite),
+      "Oracle" => Ok(Self::Oracle),
       _ => Err(format!("'{}' database is not supported", s)),
     }
   }
@@ -31,6 +39,8 @@ impl FromStr for DatabaseType {
 
 /// Represents a project block for grouping various tables.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct ProjectBlock {
   /// Range of the span in the source text.
   pub span_range: SpanRange,
@@ -43,3 +53,19 @@ pub struct ProjectBlock {
   /// The note block associated with the project block.
   pub note: Option<NoteBlock>,
 }
+
+impl fmt::Display for ProjectBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "Project {} {{", self.ident)?;
+
+    for prop in &self.properties {
+      writeln!(f, "  {}", prop)?;
+    }
+
+    if let Some(note) = &self.note {
+      writeln!(f, "  {}", note)?;
+    }
+
+    write!(f, "}}")
+  }
+}
@@ -3,11 +3,16 @@ use alloc::string::{
   ToString,
 };
 use alloc::vec::Vec;
+use core::fmt;
 use core::str::FromStr;
 
+use derive_more::Display;
+
 use super::*;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RefInline {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -16,6 +21,8 @@ pub struct RefInline {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RefBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -26,17 +33,24 @@ pub struct RefBlock {
   pub settings: Option<RefSettings>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum Relation {
   #[default]
+  #[display(fmt = "")]
   Undef,
   /// Represents '-' one-to-one. E.g: `users.id` - `user_infos.user_id`.
+  #[display(fmt = "-")]
   One2One,
   /// Represents '<' one-to-many. E.g: `users.id` < `posts.user_id`.
+  #[display(fmt = "<")]
   One2Many,
   /// Represents '>' many-to-one. E.g: `posts.user_id` > `users.id`.
+  #[display(fmt = ">")]
   Many2One,
   /// Represents '<>' many-to-many. E.g: `authors.id` <> `books.id`.
+  #[display(fmt = "<>")]
   Many2Many,
 }
 
@@ -55,6 +69,8 @@ impl FromStr for Relation {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RefIdent {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -63,12 +79,19 @@ pub struct RefIdent {
   pub compositions: Vec<Ident>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum ReferentialAction {
+  #[display(fmt = "no action")]
   NoAction,
+  #[display(fmt = "cascade")]
   Cascade,
+  #[display(fmt = "restrict")]
   Restrict,
+  #[display(fmt = "set null")]
   SetNull,
+  #[display(fmt = "set default")]
   SetDefault,
 }
 
@@ -87,21 +110,9 @@ impl FromStr for ReferentialAction {
   }
 }
 
-impl ToString for ReferentialAction {
-  fn to_string(&self) -> String {
-    let s = match self {
-      Self::NoAction => "no action",
-      Self::Cascade => "cascade",
-      Self::Restrict => "restrict",
-      Self::SetNull => "set null",
-      Self::SetDefault => "set default",
-    };
-
-    s.to_string()
-  }
-}
-
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RefSettings {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -110,3 +121,59 @@ pub struct RefSettings {
   pub on_delete: Option<ReferentialAction>,
   pub on_update: Option<ReferentialAction>,
 }
+
+impl fmt::Display for RefInline {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "ref: {} {}", self.rel, self.rhs)
+  }
+}
+
+impl fmt::Display for RefBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Ref")?;
+
+    if let Some(name) = &self.name {
+      write!(f, " {}", name)?;
+    }
+
+    write!(f, ": {} {} {}", self.lhs, self.rel, self.rhs)?;
+
+    match &self.settings {
+      Some(settings) if !settings.attributes.is_empty() => write!(f, " {}", settings),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for RefIdent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(schema) = &self.schema {
+      write!(f, "{}.", schema)?;
+    }
+
+    write!(f, "{}.", self.table)?;
+
+    match self.compositions.as_slice() {
+      [col] => write!(f, "{}", col),
+      cols => {
+        write!(f, "(")?;
+
+        for (i, col) in cols.iter().enumerate() {
+          if i != 0 {
+            write!(f, ", ")?;
+          }
+
+          write!(f, "{}", col)?;
+        }
+
+        write!(f, ")")
+      }
+    }
+  }
+}
+
+impl fmt::Display for RefSettings {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_settings(f, &self.attributes)
+  }
+}
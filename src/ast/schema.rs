@@ -1,13 +1,24 @@
+use alloc::string::{
+  String,
+  ToString,
+};
 use alloc::vec::Vec;
+use core::fmt;
 
 use super::*;
 
 /// Represents the entire structure of a parsed DBML file.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct SchemaBlock<'a> {
   /// The span range of the entire parsed DBML structure in the source text.
   pub span_range: SpanRange,
   /// The input source content.
+  ///
+  /// It is not serialized, so a deserialized schema has an empty input while its spans still point
+  /// into the original text.
+  #[cfg_attr(feature = "serde", serde(skip))]
   pub input: &'a str,
   /// A vector of top-level blocks comprising the parsed DBML structure.
   pub blocks: Vec<TopLevelBlock>,
@@ -15,6 +26,8 @@ pub struct SchemaBlock<'a> {
 
 /// An enum representing various top-level blocks in a parsed DBML file.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum TopLevelBlock {
   /// Represents a project block in the DBML file.
   Project(ProjectBlock),
@@ -29,6 +42,26 @@ pub enum TopLevelBlock {
 }
 
 impl<'a> SchemaBlock<'a> {
+  /// Emits the schema back into DBML text.
+  ///
+  /// Identifiers are quoted only if needed and notes are written as triple-quoted strings
+  /// only if they span multiple lines or contain single quotes.
+  /// The output is not guaranteed to preserve the formatting and comments of the original input.
+  pub fn to_dbml(&self) -> String {
+    self.to_string()
+  }
+
+  /// Renders the schema as a Graphviz graph in the DOT language.
+  ///
+  /// See [`crate::generator::dot::generate`] for details.
+  ///
+  /// # Errors
+  ///
+  /// All diagnostics of the schema if any of them is an error.
+  pub fn to_dot(&self) -> Result<String, Vec<crate::Diagnostic>> {
+    crate::generator::dot::generate(self)
+  }
+
   pub fn project(&self) -> Vec<&ProjectBlock> {
     self
       .blocks
@@ -99,3 +132,29 @@ impl<'a> SchemaBlock<'a> {
       .collect()
   }
 }
+
+impl<'a> fmt::Display for SchemaBlock<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, block) in self.blocks.iter().enumerate() {
+      if i != 0 {
+        writeln!(f)?;
+      }
+
+      writeln!(f, "{}", block)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for TopLevelBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Project(block) => write!(f, "{}", block),
+      Self::Table(block) => write!(f, "{}", block),
+      Self::TableGroup(block) => write!(f, "{}", block),
+      Self::Ref(block) => write!(f, "{}", block),
+      Self::Enum(block) => write!(f, "{}", block),
+    }
+  }
+}
@@ -1,11 +1,19 @@
-use alloc::string::String;
+use alloc::string::{
+  String,
+  ToString,
+};
 use alloc::vec::Vec;
+use core::fmt;
 use core::str::FromStr;
 
+use derive_more::Display;
+
 use super::*;
 
 /// Represents a block of table.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -23,6 +31,8 @@ pub struct TableBlock {
 
 /// Represents settings of the table.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableSettings {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -32,6 +42,8 @@ pub struct TableSettings {
 
 /// Represents a single column or field of the table.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableColumn {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -45,6 +57,8 @@ pub struct TableColumn {
 
 /// Represents details of the table column.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct ColumnType {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -59,55 +73,99 @@ pub struct ColumnType {
 }
 
 /// Represents data types of the database.
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum ColumnTypeName {
   /// An initial value (default).
   /// This should not present as a final parsing result.
   #[default]
+  #[display(fmt = "")]
   Undef,
   /// A type waiting to be parsed and validated.
   Raw(String),
   Enum(String),
+  #[display(fmt = "bit")]
   Bit,
+  #[display(fmt = "varbit")]
   Varbit,
+  #[display(fmt = "char")]
   Char,
+  #[display(fmt = "varchar")]
   VarChar,
+  #[display(fmt = "box")]
   Box,
+  #[display(fmt = "cidr")]
   Cidr,
+  #[display(fmt = "circle")]
   Circle,
+  #[display(fmt = "inet")]
   Inet,
+  #[display(fmt = "line")]
   Line,
+  #[display(fmt = "lseg")]
   LineSegment,
+  #[display(fmt = "macaddr")]
   MacAddr,
+  #[display(fmt = "macaddr8")]
   MacAddr8,
+  #[display(fmt = "money")]
   Money,
+  #[display(fmt = "path")]
   Path,
+  #[display(fmt = "pg_lsn")]
   PGLongSequenceNumber,
+  #[display(fmt = "pg_snapshot")]
   PGSnapshot,
+  #[display(fmt = "point")]
   Point,
+  #[display(fmt = "polygon")]
   Polygon,
+  #[display(fmt = "tsquery")]
   TSQuery,
+  #[display(fmt = "tsvector")]
   TSVector,
+  #[display(fmt = "smallserial")]
   SmallSerial,
+  #[display(fmt = "serial")]
   Serial,
+  #[display(fmt = "bigserial")]
   BigSerial,
+  #[display(fmt = "smallint")]
   SmallInt,
+  #[display(fmt = "integer")]
   Integer,
+  #[display(fmt = "bigint")]
   BigInt,
+  #[display(fmt = "real")]
   Real,
+  #[display(fmt = "double precision")]
   DoublePrecision,
+  #[display(fmt = "bool")]
   Bool,
+  #[display(fmt = "bytea")]
   ByteArray,
+  #[display(fmt = "date")]
   Date,
+  #[display(fmt = "text")]
   Text,
+  #[display(fmt = "time")]
   Time,
+  #[display(fmt = "timetz")]
   Timetz,
+  #[display(fmt = "timestamp")]
   Timestamp,
+  #[display(fmt = "timestamptz")]
   Timestamptz,
+  #[display(fmt = "uuid")]
   Uuid,
+  #[display(fmt = "json")]
   Json,
+  #[display(fmt = "jsonb")]
   Jsonb,
+  #[display(fmt = "decimal")]
   Decimal,
+  #[display(fmt = "xml")]
   Xml,
 }
 
@@ -166,6 +224,8 @@ impl FromStr for ColumnTypeName {
 
 /// Represents settings of a column.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct ColumnSettings {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -189,6 +249,8 @@ pub struct ColumnSettings {
 
 /// Represents a table identifier.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableIdent {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -199,3 +261,121 @@ pub struct TableIdent {
   /// The alias for the table.
   pub alias: Option<Ident>,
 }
+
+impl fmt::Display for TableBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Table {}", self.ident)?;
+
+    if let Some(settings) = &self.settings {
+      write!(f, " {}", settings)?;
+    }
+
+    writeln!(f, " {{")?;
+
+    for col in &self.cols {
+      writeln!(f, "  {}", col)?;
+    }
+
+    if let Some(indexes) = &self.indexes {
+      writeln!(f, "\n  Indexes {{")?;
+
+      for def in &indexes.defs {
+        writeln!(f, "    {}", def)?;
+      }
+
+      writeln!(f, "  }}")?;
+    }
+
+    if let Some(note) = &self.note {
+      writeln!(f, "\n  {}", note)?;
+    }
+
+    write!(f, "}}")
+  }
+}
+
+impl fmt::Display for TableIdent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(schema) = &self.schema {
+      write!(f, "{}.", schema)?;
+    }
+
+    write!(f, "{}", self.name)?;
+
+    if let Some(alias) = &self.alias {
+      write!(f, " as {}", alias)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for TableSettings {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_settings(f, &self.attributes)
+  }
+}
+
+impl fmt::Display for TableColumn {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {}", self.name, self.r#type)?;
+
+    match &self.settings {
+      Some(settings) if !settings.attributes.is_empty() || !settings.refs.is_empty() => write!(f, " {}", settings),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for ColumnType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let type_name = match &self.type_name {
+      ColumnTypeName::Undef => self.raw.trim().trim_matches('"').into(),
+      type_name => type_name.to_string(),
+    };
+    let is_quoted = type_name.contains(' ') || !self.arrays.is_empty();
+
+    if is_quoted {
+      write!(f, "\"")?;
+    }
+
+    write!(f, "{}", type_name)?;
+
+    if !self.args.is_empty() {
+      write!(f, "(")?;
+
+      for (i, arg) in self.args.iter().enumerate() {
+        if i != 0 {
+          write!(f, ", ")?;
+        }
+
+        fmt_value(f, arg)?;
+      }
+
+      write!(f, ")")?;
+    }
+
+    for array in &self.arrays {
+      match array {
+        Some(len) => write!(f, "[{}]", len)?,
+        None => write!(f, "[]")?,
+      }
+    }
+
+    if is_quoted {
+      write!(f, "\"")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for ColumnSettings {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let attributes = self.attributes.iter().map(|attr| attr.to_string());
+    let refs = self.refs.iter().map(|r| r.to_string());
+    let items: Vec<_> = attributes.chain(refs).collect();
+
+    fmt_settings(f, &items)
+  }
+}
@@ -1,9 +1,12 @@
 use alloc::vec::Vec;
+use core::fmt;
 
 use super::*;
 
 /// Represents a table group allowing to group the related or associated tables together.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableGroupBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -15,6 +18,8 @@ pub struct TableGroupBlock {
 
 /// Represents an associated table identifier listed inside a table group.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct TableGroupItem {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -23,3 +28,25 @@ pub struct TableGroupItem {
   /// The table name or alias.
   pub ident_alias: Ident,
 }
+
+impl fmt::Display for TableGroupBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "TableGroup {} {{", self.ident)?;
+
+    for item in &self.items {
+      writeln!(f, "  {}", item)?;
+    }
+
+    write!(f, "}}")
+  }
+}
+
+impl fmt::Display for TableGroupItem {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(schema) = &self.schema {
+      write!(f, "{}.", schema)?;
+    }
+
+    write!(f, "{}", self.ident_alias)
+  }
+}
@@ -1,13 +1,15 @@
-use alloc::string::{
-  String,
-  ToString,
-};
+use alloc::string::String;
+use core::fmt;
 use core::str::FromStr;
 
+use derive_more::Display;
+
 use super::SpanRange;
 
 /// Represents a string literal.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Literal {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -19,6 +21,8 @@ pub struct Literal {
 
 /// Represents an identifier.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Ident {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -30,6 +34,8 @@ pub struct Ident {
 
 /// Represents an attribute with a key-value pair. It can have solely key without specified any value.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Attribute {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -41,6 +47,8 @@ pub struct Attribute {
 
 /// Represents a key-value property.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Property {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
@@ -52,13 +60,17 @@ pub struct Property {
 
 /// Represents whether a value is explicitly specified as either null or not null.
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum Nullable {
   NotNull,
   Null,
 }
 
 /// Represents settings and arguments values.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum Value {
   Enum(String),
   String(String),
@@ -67,6 +79,7 @@ pub enum Value {
   Bool(bool),
   HexColor(String),
   Expr(String),
+  #[display(fmt = "null")]
   Null,
 }
 
@@ -83,26 +96,96 @@ impl FromStr for Value {
   }
 }
 
-impl ToString for Value {
-  fn to_string(&self) -> String {
-    match self {
-      Self::Enum(v) => v.clone(),
-      Self::String(v) => v.clone(),
-      Self::Integer(v) => format!("{v}"),
-      Self::Decimal(v) => format!("{v}"),
-      Self::Bool(v) => format!("{v}"),
-      Self::HexColor(v) => v.clone(),
-      Self::Expr(v) => v.clone(),
-      Self::Null => "null".to_string(),
-    }
-  }
-}
-
 /// Represents a note block.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct NoteBlock {
   /// The range of the span in the source text.
   pub span_range: SpanRange,
   /// The literal value associated with the note block. It must be a string literal.
   pub value: Literal,
 }
+
+impl fmt::Display for Ident {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if is_plain_ident(&self.to_string) {
+      write!(f, "{}", self.to_string)
+    } else {
+      write!(f, "\"{}\"", self.to_string)
+    }
+  }
+}
+
+impl fmt::Display for Literal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt_value(f, &self.value)
+  }
+}
+
+impl fmt::Display for Attribute {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.key.to_string)?;
+
+    match &self.value {
+      Some(value) => write!(f, ": {}", value),
+      None => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for Property {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.key.to_string, self.value)
+  }
+}
+
+impl fmt::Display for NoteBlock {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Note: {}", self.value)
+  }
+}
+
+/// Checks if the identifier can be written without double quotes.
+pub(crate) fn is_plain_ident(s: &str) -> bool {
+  let mut chars = s.chars();
+
+  chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Writes a value in its DBML literal form.
+pub(crate) fn fmt_value(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+  match value {
+    Value::String(v) => fmt_string(f, v),
+    Value::Expr(v) => write!(f, "`{}`", v),
+    Value::Decimal(v) if v.fract() == 0.0 => write!(f, "{:.1}", v),
+    value => write!(f, "{}", value),
+  }
+}
+
+/// Writes a string literal using triple quotes only if the content cannot be single-quoted.
+///
+/// DBML has no escape sequences, so a string containing `'''` or ending with a single quote
+/// cannot be written back in a form that parses to the same value.
+pub(crate) fn fmt_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+  if s.contains('\'') || s.contains('\n') {
+    write!(f, "'''{}'''", s)
+  } else {
+    write!(f, "'{}'", s)
+  }
+}
+
+/// Writes a comma-separated list of settings surrounded by square brackets.
+pub(crate) fn fmt_settings<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+  write!(f, "[")?;
+
+  for (i, item) in items.iter().enumerate() {
+    if i != 0 {
+      write!(f, ", ")?;
+    }
+
+    write!(f, "{}", item)?;
+  }
+
+  write!(f, "]")
+}
@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use dbml_rs::ast::*;
+use dbml_rs::{
+  Diagnostic,
+  SourceMap,
+  DEFAULT_SCHEMA,
+};
+
+/// Represents a declaration that can be navigated to.
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
+pub enum Symbol {
+  Table {
+    schema: String,
+    name: String,
+  },
+  Column {
+    schema: String,
+    table: String,
+    name: String,
+  },
+  Enum {
+    schema: String,
+    name: String,
+  },
+}
+
+/// Represents where a symbol is declared.
+#[derive(Debug, Clone)]
+pub struct Declaration {
+  pub symbol: Symbol,
+  /// The span of the declared name.
+  pub name_span: SpanRange,
+}
+
+/// Represents where a symbol is used.
+#[derive(Debug, Clone)]
+pub struct Reference {
+  pub symbol: Symbol,
+  pub span: SpanRange,
+}
+
+/// Represents the parsed and analyzed state of a single document.
+pub struct Analysis<'a> {
+  pub schema_block: SchemaBlock<'a>,
+  pub source_map: SourceMap<'a>,
+  pub diagnostics: Vec<Diagnostic>,
+  pub declarations: Vec<Declaration>,
+  pub references: Vec<Reference>,
+  /// Table aliases mapped to the schema and table name.
+  aliases: BTreeMap<String, (String, String)>,
+}
+
+impl<'a> Analysis<'a> {
+  /// Parses the text with error recovery, runs the semantic checks and indexes all symbols.
+  pub fn new(input: &'a str) -> Self {
+    let (schema_block, mut diagnostics) = dbml_rs::parse_dbml_recoverable(input);
+    let (_, semantic_diagnostics) = dbml_rs::analyze_all(&schema_block);
+    diagnostics.extend(semantic_diagnostics);
+
+    let mut analysis = Self {
+      source_map: SourceMap::new(input),
+      schema_block,
+      diagnostics,
+      declarations: vec![],
+      references: vec![],
+      aliases: BTreeMap::new(),
+    };
+    analysis.index();
+
+    analysis
+  }
+
+  /// Finds the symbol under the byte offset, either from a reference or a declaration.
+  pub fn symbol_at(&self, offset: usize) -> Option<&Symbol> {
+    let contains = |span: &SpanRange| span.start <= offset && offset <= span.end;
+
+    self
+      .references
+      .iter()
+      .find(|reference| contains(&reference.span))
+      .map(|reference| &reference.symbol)
+      .or_else(|| {
+        self
+          .declarations
+          .iter()
+          .find(|decl| contains(&decl.name_span))
+          .map(|decl| &decl.symbol)
+      })
+  }
+
+  /// Finds the declaration of the symbol.
+  pub fn declaration(&self, symbol: &Symbol) -> Option<&Declaration> {
+    self.declarations.iter().find(|decl| &decl.symbol == symbol)
+  }
+
+  /// Finds the table block with the given schema and name.
+  pub fn table(&self, schema: &str, name: &str) -> Option<&TableBlock> {
+    self
+      .schema_block
+      .tables()
+      .into_iter()
+      .find(|table| schema_of(&table.ident.schema) == schema && table.ident.name.to_string == name)
+  }
+
+  /// Finds the enum block with the given schema and name.
+  pub fn r#enum(&self, schema: &str, name: &str) -> Option<&EnumBlock> {
+    self
+      .schema_block
+      .enums()
+      .into_iter()
+      .find(|r#enum| schema_of(&r#enum.ident.schema) == schema && r#enum.ident.name.to_string == name)
+  }
+
+  /// Resolves the optional schema and the table name or alias into the schema and table name.
+  pub fn resolve_table(&self, schema: Option<&str>, table: &str) -> (String, String) {
+    match (schema, self.aliases.get(table)) {
+      (Some(schema), _) => (schema.to_string(), table.to_string()),
+      (None, Some(resolved)) => resolved.clone(),
+      (None, None) => (DEFAULT_SCHEMA.to_string(), table.to_string()),
+    }
+  }
+
+  fn index(&mut self) {
+    let tables: Vec<_> = self.schema_block.tables().into_iter().cloned().collect();
+    let enums: Vec<_> = self.schema_block.enums().into_iter().cloned().collect();
+
+    for table in &tables {
+      if let Some(alias) = &table.ident.alias {
+        self.aliases.insert(
+          alias.to_string.clone(),
+          (schema_of(&table.ident.schema), table.ident.name.to_string.clone()),
+        );
+      }
+    }
+
+    for r#enum in &enums {
+      self.declarations.push(Declaration {
+        symbol: Symbol::Enum {
+          schema: schema_of(&r#enum.ident.schema),
+          name: r#enum.ident.name.to_string.clone(),
+        },
+        name_span: r#enum.ident.name.span_range.clone(),
+      });
+    }
+
+    for table in &tables {
+      let schema = schema_of(&table.ident.schema);
+      let name = table.ident.name.to_string.clone();
+
+      self.declarations.push(Declaration {
+        symbol: Symbol::Table {
+          schema: schema.clone(),
+          name: name.clone(),
+        },
+        name_span: table.ident.name.span_range.clone(),
+      });
+
+      for col in &table.cols {
+        self.declarations.push(Declaration {
+          symbol: Symbol::Column {
+            schema: schema.clone(),
+            table: name.clone(),
+            name: col.name.to_string.clone(),
+          },
+          name_span: col.name.span_range.clone(),
+        });
+
+        if let ColumnTypeName::Raw(type_name) = &col.r#type.type_name {
+          // prefer the enum in the same schema as the table
+          let enum_schema = enums
+            .iter()
+            .filter(|r#enum| &r#enum.ident.name.to_string == type_name)
+            .map(|r#enum| schema_of(&r#enum.ident.schema))
+            .min_by_key(|enum_schema| enum_schema != &schema);
+
+          if let Some(enum_schema) = enum_schema {
+            self.references.push(Reference {
+              symbol: Symbol::Enum {
+                schema: enum_schema,
+                name: type_name.clone(),
+              },
+              span: col.r#type.span_range.clone(),
+            });
+          }
+        }
+
+        let refs = col.settings.iter().flat_map(|settings| settings.refs.iter());
+        for ref_inline in refs {
+          self.index_ref_ident(&ref_inline.rhs);
+        }
+      }
+
+      let index_cols = table
+        .indexes
+        .iter()
+        .flat_map(|indexes| indexes.defs.iter())
+        .flat_map(|def| def.cols.iter());
+      for index_col in index_cols {
+        if let IndexesColumnType::String(ident) = index_col {
+          self.references.push(Reference {
+            symbol: Symbol::Column {
+              schema: schema.clone(),
+              table: name.clone(),
+              name: ident.to_string.clone(),
+            },
+            span: ident.span_range.clone(),
+          });
+        }
+      }
+    }
+
+    for ref_block in self.schema_block.refs().into_iter().cloned().collect::<Vec<_>>() {
+      self.index_ref_ident(&ref_block.lhs);
+      self.index_ref_ident(&ref_block.rhs);
+    }
+
+    for table_group in self
+      .schema_block
+      .table_groups()
+      .into_iter()
+      .cloned()
+      .collect::<Vec<_>>()
+    {
+      for item in &table_group.items {
+        let (schema, name) = self.resolve_table(
+          item.schema.as_ref().map(|s| s.to_string.as_str()),
+          &item.ident_alias.to_string,
+        );
+
+        self.references.push(Reference {
+          symbol: Symbol::Table { schema, name },
+          span: item.ident_alias.span_range.clone(),
+        });
+      }
+    }
+  }
+
+  fn index_ref_ident(&mut self, ref_ident: &RefIdent) {
+    let (schema, table) = self.resolve_table(
+      ref_ident.schema.as_ref().map(|s| s.to_string.as_str()),
+      &ref_ident.table.to_string,
+    );
+
+    self.references.push(Reference {
+      symbol: Symbol::Table {
+        schema: schema.clone(),
+        name: table.clone(),
+      },
+      span: ref_ident.table.span_range.clone(),
+    });
+
+    for col in &ref_ident.compositions {
+      self.references.push(Reference {
+        symbol: Symbol::Column {
+          schema: schema.clone(),
+          table: table.clone(),
+          name: col.to_string.clone(),
+        },
+        span: col.span_range.clone(),
+      });
+    }
+  }
+}
+
+/// Returns the schema name, falling back to the default schema.
+pub fn schema_of(schema: &Option<Ident>) -> String {
+  schema
+    .as_ref()
+    .map(|s| s.to_string.clone())
+    .unwrap_or_else(|| DEFAULT_SCHEMA.to_string())
+}
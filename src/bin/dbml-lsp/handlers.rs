@@ -0,0 +1,327 @@
+use dbml_rs::ast::*;
+use dbml_rs::diagnostic::Severity;
+use dbml_rs::Diagnostic;
+use lsp_types::{
+  CompletionItem,
+  CompletionItemKind,
+  DiagnosticRelatedInformation,
+  DiagnosticSeverity,
+  DocumentSymbol,
+  Hover,
+  HoverContents,
+  Location,
+  MarkupContent,
+  MarkupKind,
+  NumberOrString,
+  Position,
+  Range,
+  SymbolKind,
+  Url,
+};
+
+use crate::analysis::{
+  schema_of,
+  Analysis,
+  Symbol,
+};
+
+/// Converts the span into an LSP range counted in UTF-16 code units.
+pub fn to_range(analysis: &Analysis, span_range: &SpanRange) -> Range {
+  let to_position = |offset: usize| {
+    analysis
+      .source_map
+      .line_col(offset)
+      .map(|line_col| Position::new(line_col.line as u32, line_col.col_utf16 as u32))
+      .unwrap_or_default()
+  };
+
+  Range::new(to_position(span_range.start), to_position(span_range.end))
+}
+
+/// Converts the LSP position into a byte offset.
+pub fn to_offset(analysis: &Analysis, position: Position) -> Option<usize> {
+  analysis
+    .source_map
+    .offset_utf16(position.line as usize, position.character as usize)
+}
+
+/// Converts all diagnostics of the document into LSP diagnostics.
+pub fn diagnostics(analysis: &Analysis, uri: &Url) -> Vec<lsp_types::Diagnostic> {
+  analysis
+    .diagnostics
+    .iter()
+    .map(|diag| to_lsp_diagnostic(analysis, uri, diag))
+    .collect()
+}
+
+fn to_lsp_diagnostic(analysis: &Analysis, uri: &Url, diag: &Diagnostic) -> lsp_types::Diagnostic {
+  let related_information = diag
+    .secondary_spans
+    .iter()
+    .map(|label| {
+      DiagnosticRelatedInformation {
+        location: Location::new(uri.clone(), to_range(analysis, &label.span_range)),
+        message: label.message.clone(),
+      }
+    })
+    .collect::<Vec<_>>();
+
+  let message = match &diag.help {
+    Some(help) => format!("{}\nhelp: {}", diag.message, help),
+    None => diag.message.clone(),
+  };
+
+  lsp_types::Diagnostic {
+    range: to_range(analysis, &diag.primary_span),
+    severity: Some(match diag.severity {
+      Severity::Error => DiagnosticSeverity::ERROR,
+      Severity::Warning => DiagnosticSeverity::WARNING,
+    }),
+    code: Some(NumberOrString::String(diag.code.to_string())),
+    source: Some("dbml".to_string()),
+    message,
+    related_information: (!related_information.is_empty()).then_some(related_information),
+    ..Default::default()
+  }
+}
+
+/// Describes the symbol under the cursor.
+pub fn hover(analysis: &Analysis, offset: usize) -> Option<Hover> {
+  let symbol = analysis.symbol_at(offset)?;
+
+  let value = match symbol {
+    Symbol::Table { schema, name } => {
+      let table = analysis.table(schema, name)?;
+      let mut value = format!("```dbml\nTable {}\n```", table.ident);
+
+      if let Some(note) = &table.note {
+        value += &format!("\n\n{}", note.value.raw);
+      }
+
+      value
+    }
+    Symbol::Column { schema, table, name } => {
+      let col = analysis
+        .table(schema, table)?
+        .cols
+        .iter()
+        .find(|col| &col.name.to_string == name)?;
+      let mut value = format!("```dbml\n{}\n```\n\nColumn of `{}.{}`", col, schema, table);
+
+      if let Some(note) = col.settings.as_ref().and_then(|settings| settings.note.as_ref()) {
+        value += &format!("\n\n{}", note);
+      }
+
+      value
+    }
+    Symbol::Enum { schema, name } => {
+      let r#enum = analysis.r#enum(schema, name)?;
+      let values: Vec<_> = r#enum
+        .values
+        .iter()
+        .map(|value| value.value.to_string.as_str())
+        .collect();
+
+      format!("```dbml\nEnum {}\n```\n\nValues: {}", r#enum.ident, values.join(", "))
+    }
+  };
+
+  Some(Hover {
+    contents: HoverContents::Markup(MarkupContent {
+      kind: MarkupKind::Markdown,
+      value,
+    }),
+    range: None,
+  })
+}
+
+/// Finds the declaration of the symbol under the cursor.
+pub fn definition(analysis: &Analysis, uri: &Url, offset: usize) -> Option<Location> {
+  let symbol = analysis.symbol_at(offset)?;
+  let decl = analysis.declaration(symbol)?;
+
+  Some(Location::new(uri.clone(), to_range(analysis, &decl.name_span)))
+}
+
+/// Finds all references of the symbol under the cursor.
+pub fn references(analysis: &Analysis, uri: &Url, offset: usize, include_declaration: bool) -> Vec<Location> {
+  let Some(symbol) = analysis.symbol_at(offset) else {
+    return vec![];
+  };
+
+  let decl_span = analysis
+    .declaration(symbol)
+    .filter(|_| include_declaration)
+    .map(|decl| &decl.name_span);
+  let ref_spans = analysis
+    .references
+    .iter()
+    .filter(|reference| &reference.symbol == symbol)
+    .map(|reference| &reference.span);
+
+  decl_span
+    .into_iter()
+    .chain(ref_spans)
+    .map(|span_range| Location::new(uri.clone(), to_range(analysis, span_range)))
+    .collect()
+}
+
+/// Lists tables, enums and table groups as a symbol tree.
+#[allow(deprecated)]
+pub fn document_symbols(analysis: &Analysis) -> Vec<DocumentSymbol> {
+  let symbol = |name: String, kind, span: &SpanRange, name_span: &SpanRange, children: Option<Vec<_>>| {
+    DocumentSymbol {
+      name,
+      detail: None,
+      kind,
+      tags: None,
+      deprecated: None,
+      range: to_range(analysis, span),
+      selection_range: to_range(analysis, name_span),
+      children,
+    }
+  };
+
+  let tables = analysis.schema_block.tables().into_iter().map(|table| {
+    let cols = table
+      .cols
+      .iter()
+      .map(|col| {
+        DocumentSymbol {
+          detail: Some(col.r#type.raw.trim().to_string()),
+          ..symbol(
+            col.name.to_string.clone(),
+            SymbolKind::FIELD,
+            &col.span_range,
+            &col.name.span_range,
+            None,
+          )
+        }
+      })
+      .collect();
+
+    symbol(
+      qualified_name(&table.ident.schema, &table.ident.name),
+      SymbolKind::STRUCT,
+      &table.span_range,
+      &table.ident.name.span_range,
+      Some(cols),
+    )
+  });
+
+  let enums = analysis.schema_block.enums().into_iter().map(|r#enum| {
+    let values = r#enum
+      .values
+      .iter()
+      .map(|value| {
+        symbol(
+          value.value.to_string.clone(),
+          SymbolKind::ENUM_MEMBER,
+          &value.span_range,
+          &value.value.span_range,
+          None,
+        )
+      })
+      .collect();
+
+    symbol(
+      qualified_name(&r#enum.ident.schema, &r#enum.ident.name),
+      SymbolKind::ENUM,
+      &r#enum.span_range,
+      &r#enum.ident.name.span_range,
+      Some(values),
+    )
+  });
+
+  let table_groups = analysis.schema_block.table_groups().into_iter().map(|table_group| {
+    symbol(
+      table_group.ident.to_string.clone(),
+      SymbolKind::NAMESPACE,
+      &table_group.span_range,
+      &table_group.ident.span_range,
+      None,
+    )
+  });
+
+  tables.chain(enums).chain(table_groups).collect()
+}
+
+/// Completes table names, or column names after `table.`, inside `ref:` settings.
+pub fn completion(analysis: &Analysis, offset: usize) -> Vec<CompletionItem> {
+  let input = analysis.source_map.input();
+  let line_start = input[..offset].rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+  let line = &input[line_start..offset];
+
+  let Some(ref_start) = line.to_ascii_lowercase().rfind("ref:") else {
+    return vec![];
+  };
+  let target = line[ref_start + 4..].trim_start_matches(|c: char| c.is_whitespace() || "<>-".contains(c));
+
+  if !target.chars().all(|c| c.is_alphanumeric() || "_.\"".contains(c)) {
+    return vec![];
+  }
+
+  match target.rsplit_once('.') {
+    Some((table_path, _)) => {
+      let mut parts = table_path.split('.').map(|part| part.trim_matches('"'));
+      let (schema, table) = match (parts.next(), parts.next()) {
+        (Some(schema), Some(table)) => (Some(schema), table),
+        (Some(table), None) => (None, table),
+        _ => return vec![],
+      };
+      let (schema, table) = analysis.resolve_table(schema, table);
+
+      analysis
+        .table(&schema, &table)
+        .map(|table| {
+          table
+            .cols
+            .iter()
+            .map(|col| {
+              CompletionItem {
+                label: col.name.to_string.clone(),
+                kind: Some(CompletionItemKind::FIELD),
+                detail: Some(col.r#type.raw.trim().to_string()),
+                ..Default::default()
+              }
+            })
+            .collect()
+        })
+        .unwrap_or_default()
+    }
+    None => {
+      let tables = analysis.schema_block.tables().into_iter().map(|table| {
+        CompletionItem {
+          label: qualified_name(&table.ident.schema, &table.ident.name),
+          kind: Some(CompletionItemKind::CLASS),
+          detail: Some(format!("Table {}", table.ident)),
+          ..Default::default()
+        }
+      });
+      let aliases = analysis.schema_block.tables().into_iter().filter_map(|table| {
+        table.ident.alias.as_ref().map(|alias| {
+          CompletionItem {
+            label: alias.to_string.clone(),
+            kind: Some(CompletionItemKind::CLASS),
+            detail: Some(format!(
+              "Alias of {}.{}",
+              schema_of(&table.ident.schema),
+              table.ident.name
+            )),
+            ..Default::default()
+          }
+        })
+      });
+
+      tables.chain(aliases).collect()
+    }
+  }
+}
+
+/// Returns the name prefixed with the schema name if specified.
+fn qualified_name(schema: &Option<Ident>, name: &Ident) -> String {
+  match schema {
+    Some(schema) => format!("{}.{}", schema.to_string, name.to_string),
+    None => name.to_string.clone(),
+  }
+}
@@ -0,0 +1,273 @@
+//! A Language Server Protocol server for DBML communicating over stdio.
+
+mod analysis;
+mod handlers;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use analysis::Analysis;
+use lsp_server::{
+  Connection,
+  ErrorCode,
+  Message,
+  Notification,
+  Request,
+  Response,
+};
+use lsp_types::notification::{
+  DidChangeTextDocument,
+  DidCloseTextDocument,
+  DidOpenTextDocument,
+  LogMessage,
+  Notification as _,
+  PublishDiagnostics,
+};
+use lsp_types::request::{
+  Completion,
+  DocumentSymbolRequest,
+  GotoDefinition,
+  HoverRequest,
+  References,
+  Request as _,
+};
+use lsp_types::{
+  CompletionOptions,
+  CompletionResponse,
+  DidChangeTextDocumentParams,
+  DidCloseTextDocumentParams,
+  DidOpenTextDocumentParams,
+  DocumentSymbolResponse,
+  GotoDefinitionResponse,
+  HoverProviderCapability,
+  LogMessageParams,
+  MessageType,
+  OneOf,
+  PublishDiagnosticsParams,
+  ServerCapabilities,
+  TextDocumentSyncCapability,
+  TextDocumentSyncKind,
+  Url,
+};
+
+type ServerResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+fn main() -> ServerResult<()> {
+  let (connection, io_threads) = Connection::stdio();
+
+  connection.initialize(serde_json::to_value(capabilities())?)?;
+  // the connection must be dropped before joining, otherwise the writer thread never ends
+  Server::default().run(connection)?;
+  io_threads.join()?;
+
+  Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+  ServerCapabilities {
+    text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+    hover_provider: Some(HoverProviderCapability::Simple(true)),
+    definition_provider: Some(OneOf::Left(true)),
+    references_provider: Some(OneOf::Left(true)),
+    document_symbol_provider: Some(OneOf::Left(true)),
+    completion_provider: Some(CompletionOptions {
+      trigger_characters: Some([".", ">", "<", "-"].map(String::from).to_vec()),
+      ..Default::default()
+    }),
+    ..Default::default()
+  }
+}
+
+/// Holds the text of all open documents.
+#[derive(Default)]
+struct Server {
+  documents: HashMap<Url, String>,
+}
+
+impl Server {
+  fn run(&mut self, connection: Connection) -> ServerResult<()> {
+    for msg in &connection.receiver {
+      match msg {
+        Message::Request(req) => {
+          if connection.handle_shutdown(&req)? {
+            return Ok(());
+          }
+
+          connection.sender.send(Message::Response(self.handle_request(req)))?;
+        }
+        Message::Notification(not) => {
+          let method = not.method.clone();
+
+          match self.handle_notification(not) {
+            Ok(Some(uri)) => {
+              connection
+                .sender
+                .send(Message::Notification(self.publish_diagnostics(uri)))?
+            }
+            Ok(None) => (),
+            // notifications have no response, so a malformed one is only logged and skipped
+            Err(err) => {
+              let params = LogMessageParams {
+                typ: MessageType::ERROR,
+                message: format!("invalid params of {}: {}", method, err),
+              };
+
+              connection.sender.send(Message::Notification(Notification::new(
+                LogMessage::METHOD.to_string(),
+                params,
+              )))?
+            }
+          }
+        }
+        Message::Response(_) => (),
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Updates the open documents and returns the URI of the changed document, if any.
+  ///
+  /// # Errors
+  ///
+  /// The deserialization error if the parameters of the notification are malformed.
+  fn handle_notification(&mut self, not: Notification) -> Result<Option<Url>, serde_json::Error> {
+    let uri = match not.method.as_str() {
+      DidOpenTextDocument::METHOD => {
+        let params: DidOpenTextDocumentParams = serde_json::from_value(not.params)?;
+
+        self
+          .documents
+          .insert(params.text_document.uri.clone(), params.text_document.text);
+        params.text_document.uri
+      }
+      DidChangeTextDocument::METHOD => {
+        let params: DidChangeTextDocumentParams = serde_json::from_value(not.params)?;
+
+        // only full document sync is supported, so the last change holds the entire text
+        if let Some(change) = params.content_changes.into_iter().last() {
+          self.documents.insert(params.text_document.uri.clone(), change.text);
+        }
+        params.text_document.uri
+      }
+      DidCloseTextDocument::METHOD => {
+        let params: DidCloseTextDocumentParams = serde_json::from_value(not.params)?;
+
+        self.documents.remove(&params.text_document.uri);
+        params.text_document.uri
+      }
+      _ => return Ok(None),
+    };
+
+    Ok(Some(uri))
+  }
+
+  /// Creates the notification publishing the diagnostics of the document.
+  ///
+  /// Closed documents get an empty list to clear their diagnostics.
+  fn publish_diagnostics(&self, uri: Url) -> Notification {
+    let diagnostics = match self.documents.get(&uri) {
+      Some(text) => handlers::diagnostics(&Analysis::new(text), &uri),
+      None => vec![],
+    };
+
+    let params = PublishDiagnosticsParams {
+      uri,
+      diagnostics,
+      version: None,
+    };
+
+    Notification::new(PublishDiagnostics::METHOD.to_string(), params)
+  }
+
+  fn handle_request(&self, req: Request) -> Response {
+    let id = req.id.clone();
+
+    let result = match req.method.as_str() {
+      HoverRequest::METHOD => {
+        self.on::<HoverRequest>(
+          req,
+          |params| &params.text_document_position_params.text_document.uri,
+          |analysis, params| {
+            handlers::to_offset(analysis, params.text_document_position_params.position)
+              .and_then(|offset| handlers::hover(analysis, offset))
+          },
+        )
+      }
+      GotoDefinition::METHOD => {
+        self.on::<GotoDefinition>(
+          req,
+          |params| &params.text_document_position_params.text_document.uri,
+          |analysis, params| {
+            let position = &params.text_document_position_params;
+
+            handlers::to_offset(analysis, position.position)
+              .and_then(|offset| handlers::definition(analysis, &position.text_document.uri, offset))
+              .map(GotoDefinitionResponse::Scalar)
+          },
+        )
+      }
+      References::METHOD => {
+        self.on::<References>(
+          req,
+          |params| &params.text_document_position.text_document.uri,
+          |analysis, params| {
+            let position = &params.text_document_position;
+
+            handlers::to_offset(analysis, position.position).map(|offset| {
+              handlers::references(
+                analysis,
+                &position.text_document.uri,
+                offset,
+                params.context.include_declaration,
+              )
+            })
+          },
+        )
+      }
+      DocumentSymbolRequest::METHOD => {
+        self.on::<DocumentSymbolRequest>(
+          req,
+          |params| &params.text_document.uri,
+          |analysis, _| Some(DocumentSymbolResponse::Nested(handlers::document_symbols(analysis))),
+        )
+      }
+      Completion::METHOD => {
+        self.on::<Completion>(
+          req,
+          |params| &params.text_document_position.text_document.uri,
+          |analysis, params| {
+            handlers::to_offset(analysis, params.text_document_position.position)
+              .map(|offset| CompletionResponse::Array(handlers::completion(analysis, offset)))
+          },
+        )
+      }
+      _ => Err((ErrorCode::MethodNotFound, format!("unsupported method: {}", req.method))),
+    };
+
+    match result {
+      Ok(value) => Response::new_ok(id, value),
+      Err((code, message)) => Response::new_err(id, code as i32, message),
+    }
+  }
+
+  /// Extracts the request parameters and handles the request against the analyzed document.
+  fn on<R: lsp_types::request::Request>(
+    &self,
+    req: Request,
+    uri: fn(&R::Params) -> &Url,
+    handle: impl FnOnce(&Analysis, &R::Params) -> R::Result,
+  ) -> Result<serde_json::Value, (ErrorCode, String)> {
+    let (_, params) = req
+      .extract::<R::Params>(R::METHOD)
+      .map_err(|err| (ErrorCode::InvalidParams, format!("{:?}", err)))?;
+    let text = self
+      .documents
+      .get(uri(&params))
+      .ok_or_else(|| (ErrorCode::InvalidParams, format!("unknown document: {}", uri(&params))))?;
+
+    let result = handle(&Analysis::new(text), &params);
+
+    serde_json::to_value(result).map_err(|err| (ErrorCode::InternalError, err.to_string()))
+  }
+}
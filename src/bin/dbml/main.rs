@@ -0,0 +1,348 @@
+//! A command-line tool for checking, formatting and converting DBML files.
+
+use std::error::Error;
+use std::fs;
+use std::io::{
+  self,
+  Read,
+  Write,
+};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{
+  Parser,
+  Subcommand,
+  ValueEnum,
+};
+use dbml_rs::ast::{
+  DatabaseType,
+  SchemaBlock,
+};
+use dbml_rs::diagnostic::Severity;
+use dbml_rs::formatter::{
+  self,
+  FormatOptions,
+};
+use dbml_rs::generator::mermaid::MermaidOptions;
+use dbml_rs::{
+  generator,
+  Diagnostic,
+  SourceMap,
+};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// The path standing for stdin or stdout.
+const STDIO: &str = "-";
+
+#[derive(Parser)]
+#[command(name = "dbml", version, about = "Check, format and convert DBML files")]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Reports syntax and semantic errors of the files.
+  Check {
+    /// The files to check, or `-` to read from stdin.
+    files: Vec<PathBuf>,
+  },
+  /// Formats the files in place, or stdin to stdout.
+  Fmt {
+    /// Only reports files that are not formatted, without writing them.
+    #[arg(long)]
+    check: bool,
+    /// The number of spaces for each indentation level.
+    #[arg(long, default_value_t = 2)]
+    indent_width: usize,
+    /// The files to format, or `-` to format stdin to stdout.
+    files: Vec<PathBuf>,
+  },
+  /// Converts the file into another format.
+  Convert {
+    /// The output format.
+    #[arg(long, value_enum)]
+    to: Target,
+    /// The SQL dialect, defaulting to the `database_type` of the project.
+    #[arg(long, value_enum)]
+    dialect: Option<Dialect>,
+    /// The table group to draw in a diagram, defaulting to all tables.
+    #[arg(long)]
+    table_group: Option<String>,
+    /// The file to write to instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// The file to convert, or `-` to read from stdin.
+    file: Option<PathBuf>,
+  },
+  /// Prints the number of tables, columns, refs, enums and table groups.
+  Stats {
+    /// The files to count, or `-` to read from stdin.
+    files: Vec<PathBuf>,
+  },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Target {
+  /// SQL data definition statements.
+  Sql,
+  /// The JSON database model of `@dbml/core`.
+  Json,
+  /// A Mermaid entity relationship diagram.
+  Mermaid,
+  /// A Graphviz graph in the DOT language.
+  Dot,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Dialect {
+  Postgres,
+  #[value(alias = "mariadb")]
+  Mysql,
+  Mssql,
+  Sqlite,
+  Oracle,
+}
+
+impl Dialect {
+  /// Picks the dialect matching the database type of the project.
+  fn of(schema_block: &SchemaBlock) -> CliResult<Self> {
+    let database_type = schema_block
+      .project()
+      .first()
+      .map(|project| &project.database_type)
+      .unwrap_or(&DatabaseType::Undef);
+
+    match database_type {
+      DatabaseType::PostgreSQL | DatabaseType::Undef => Ok(Self::Postgres),
+      DatabaseType::MySQL | DatabaseType::MariaDB => Ok(Self::Mysql),
+      DatabaseType::MSSQL => Ok(Self::Mssql),
+      DatabaseType::SQLite => Ok(Self::Sqlite),
+      DatabaseType::Oracle => Ok(Self::Oracle),
+      database_type => Err(format!("no SQL generator for {:?}, pass --dialect to pick one", database_type).into()),
+    }
+  }
+}
+
+/// Represents an input file or stdin.
+struct Source {
+  /// The path shown in reports.
+  name: String,
+  /// The path to write back to, or `None` for stdin.
+  path: Option<PathBuf>,
+  text: String,
+}
+
+impl Source {
+  fn read(path: Option<PathBuf>) -> CliResult<Self> {
+    match path.filter(|path| path.as_os_str() != STDIO) {
+      Some(path) => {
+        let text = fs::read_to_string(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+
+        Ok(Self {
+          name: path.display().to_string(),
+          path: Some(path),
+          text,
+        })
+      }
+      None => {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+
+        Ok(Self {
+          name: "<stdin>".to_string(),
+          path: None,
+          text,
+        })
+      }
+    }
+  }
+
+  /// Reads all files, or stdin if none is given.
+  fn read_all(paths: Vec<PathBuf>) -> CliResult<Vec<Self>> {
+    match paths.is_empty() {
+      true => Ok(vec![Self::read(None)?]),
+      false => paths.into_iter().map(|path| Self::read(Some(path))).collect(),
+    }
+  }
+
+  /// Prints the diagnostics to stderr and returns whether any of them is an error.
+  fn report(&self, diags: &[Diagnostic]) -> bool {
+    let source_map = SourceMap::new(&self.text);
+
+    for diag in diags {
+      eprintln!("{}", diag.render(&source_map, &self.name));
+    }
+
+    diags.iter().any(|diag| diag.severity == Severity::Error)
+  }
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+
+  let result = match cli.command {
+    Command::Check { files } => check(files),
+    Command::Fmt {
+      check,
+      indent_width,
+      files,
+    } => fmt(files, check, indent_width),
+    Command::Convert {
+      to,
+      dialect,
+      table_group,
+      output,
+      file,
+    } => convert(file, to, dialect, table_group, output),
+    Command::Stats { files } => stats(files),
+  };
+
+  match result {
+    // no problems found
+    Ok(true) => ExitCode::SUCCESS,
+    // problems were found and reported
+    Ok(false) => ExitCode::from(1),
+    // the command could not be run at all
+    Err(err) => {
+      eprintln!("error: {}", err);
+      ExitCode::from(2)
+    }
+  }
+}
+
+fn check(files: Vec<PathBuf>) -> CliResult<bool> {
+  let mut errors = 0;
+
+  for source in Source::read_all(files)? {
+    let (schema_block, mut diags) = dbml_rs::parse_dbml_recoverable(&source.text);
+    diags.extend(dbml_rs::analyze_all(&schema_block).1);
+
+    source.report(&diags);
+    errors += diags.iter().filter(|diag| diag.severity == Severity::Error).count();
+  }
+
+  if errors != 0 {
+    eprintln!("error: found {} error(s)", errors);
+  }
+
+  Ok(errors == 0)
+}
+
+fn fmt(files: Vec<PathBuf>, check: bool, indent_width: usize) -> CliResult<bool> {
+  let options = FormatOptions {
+    indent_width,
+    ..Default::default()
+  };
+  let mut ok = true;
+
+  for source in Source::read_all(files)? {
+    // formatting a partial tree would drop the broken blocks
+    let (schema_block, diags) = dbml_rs::parse_dbml_recoverable(&source.text);
+    if source.report(&diags) {
+      eprintln!("error: cannot format {} because of syntax errors", source.name);
+      ok = false;
+      continue;
+    }
+
+    let formatted = formatter::format(&schema_block, &options);
+
+    match (&source.path, check) {
+      (_, true) => {
+        if formatted != source.text {
+          eprintln!("would reformat {}", source.name);
+          ok = false;
+        }
+      }
+      (Some(path), false) => {
+        if formatted != source.text {
+          fs::write(path, formatted).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        }
+      }
+      (None, false) => io::stdout().write_all(formatted.as_bytes())?,
+    }
+  }
+
+  Ok(ok)
+}
+
+fn convert(
+  file: Option<PathBuf>,
+  to: Target,
+  dialect: Option<Dialect>,
+  table_group: Option<String>,
+  output: Option<PathBuf>,
+) -> CliResult<bool> {
+  let source = Source::read(file)?;
+
+  let (schema_block, diags) = dbml_rs::parse_dbml_recoverable(&source.text);
+  if source.report(&diags) {
+    return Ok(false);
+  }
+
+  let generated = match to {
+    Target::Sql => {
+      match dialect.map_or_else(|| Dialect::of(&schema_block), Ok)? {
+        Dialect::Postgres => generator::postgres::generate(&schema_block),
+        Dialect::Mysql => generator::mysql::generate(&schema_block, &Default::default()),
+        Dialect::Mssql => generator::mssql::generate(&schema_block),
+        Dialect::Sqlite => generator::sqlite::generate(&schema_block, &Default::default()),
+        Dialect::Oracle => generator::oracle::generate(&schema_block, &Default::default()),
+      }
+    }
+    Target::Json => generator::json::generate(&schema_block),
+    Target::Mermaid => generator::mermaid::generate(&schema_block, &MermaidOptions { table_group }),
+    Target::Dot => schema_block.to_dot(),
+  };
+
+  let converted = match generated {
+    Ok(converted) => converted,
+    Err(diags) => {
+      source.report(&diags);
+      return Ok(false);
+    }
+  };
+
+  match output.filter(|path| path.as_os_str() != STDIO) {
+    Some(path) => fs::write(&path, converted).map_err(|err| format!("cannot write {}: {}", path.display(), err))?,
+    None => io::stdout().write_all(converted.as_bytes())?,
+  }
+
+  Ok(true)
+}
+
+fn stats(files: Vec<PathBuf>) -> CliResult<bool> {
+  let mut counts = [0; 5];
+  let mut ok = true;
+
+  for source in Source::read_all(files)? {
+    let (schema_block, diags) = dbml_rs::parse_dbml_recoverable(&source.text);
+    if source.report(&diags) {
+      ok = false;
+    }
+
+    let tables = schema_block.tables();
+    let inline_refs = tables
+      .iter()
+      .flat_map(|table| table.cols.iter())
+      .filter_map(|col| col.settings.as_ref())
+      .map(|settings| settings.refs.len())
+      .sum::<usize>();
+
+    counts[0] += tables.len();
+    counts[1] += tables.iter().map(|table| table.cols.len()).sum::<usize>();
+    counts[2] += schema_block.refs().len() + inline_refs;
+    counts[3] += schema_block.enums().len();
+    counts[4] += schema_block.table_groups().len();
+  }
+
+  let labels = ["tables", "columns", "refs", "enums", "table groups"];
+  for (label, count) in labels.iter().zip(counts) {
+    println!("{:<13} {}", format!("{}:", label), count);
+  }
+
+  Ok(ok)
+}
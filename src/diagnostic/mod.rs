@@ -0,0 +1,185 @@
+mod render;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+
+use derive_more::Display;
+use pest::error::{
+  Error,
+  ErrorVariant,
+  InputLocation,
+};
+use pest::{
+  Position,
+  Span,
+};
+
+use crate::analyzer::err::Err;
+use crate::ast::SpanRange;
+use crate::generator::err::Err as GeneratorErr;
+use crate::parser::Rule;
+
+/// Represents how serious a diagnostic is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Display)]
+pub enum Severity {
+  #[display(fmt = "error")]
+  Error,
+  #[display(fmt = "warning")]
+  Warning,
+}
+
+/// Represents the origin of a diagnostic.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DiagnosticKind {
+  /// A syntax error reported by the parser.
+  Syntax(ErrorVariant<Rule>),
+  /// A semantic error reported by the analyzer.
+  Semantic(Err),
+  /// A limitation of the target database reported by a generator.
+  Generator(GeneratorErr),
+}
+
+/// Represents a secondary span attached to a diagnostic.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Label {
+  /// The range of the span in the source text.
+  pub span_range: SpanRange,
+  /// The message describing the span.
+  pub message: String,
+}
+
+/// Represents a problem found in the source text.
+#[derive(Debug, PartialEq, Eq, Clone, Display)]
+#[display(fmt = "{}[{}]: {}", severity, code, message)]
+pub struct Diagnostic {
+  /// The stable code of the diagnostic (e.g. `E0010`).
+  pub code: &'static str,
+  /// The severity of the diagnostic.
+  pub severity: Severity,
+  /// The origin of the diagnostic, which can be matched on programmatically.
+  pub kind: DiagnosticKind,
+  /// The range of the span where the problem is found.
+  pub primary_span: SpanRange,
+  /// The related spans that help explaining the problem.
+  pub secondary_spans: Vec<Label>,
+  /// The human readable message.
+  pub message: String,
+  /// The optional suggestion on how to fix the problem.
+  pub help: Option<String>,
+}
+
+impl Diagnostic {
+  /// The code shared by all syntax errors.
+  pub const SYNTAX_ERROR_CODE: &'static str = "E0000";
+
+  /// Creates an error diagnostic from the `pest` error reported by the parser.
+  pub fn syntax(error: &Error<Rule>) -> Self {
+    let primary_span = match error.location {
+      InputLocation::Pos(pos) => pos..pos,
+      InputLocation::Span((start, end)) => start..end,
+    };
+
+    Self {
+      code: Self::SYNTAX_ERROR_CODE,
+      severity: Severity::Error,
+      message: error.variant.message().to_string(),
+      help: None,
+      kind: DiagnosticKind::Syntax(error.variant.clone()),
+      primary_span,
+      secondary_spans: vec![],
+    }
+  }
+
+  /// Creates a syntax error diagnostic with the given message at the given span.
+  ///
+  /// This is used for syntax errors found in inputs other than DBML, such as imported SQL.
+  pub(crate) fn custom_syntax(message: impl ToString, span_range: &SpanRange) -> Self {
+    let message = message.to_string();
+
+    Self {
+      code: Self::SYNTAX_ERROR_CODE,
+      severity: Severity::Error,
+      message: message.clone(),
+      help: None,
+      kind: DiagnosticKind::Syntax(ErrorVariant::CustomError { message }),
+      primary_span: span_range.clone(),
+      secondary_spans: vec![],
+    }
+  }
+
+  /// Creates an error diagnostic from the semantic error at the given span.
+  pub fn semantic(err: Err, span_range: &SpanRange) -> Self {
+    Self {
+      code: err.code(),
+      severity: Severity::Error,
+      message: err.to_string(),
+      help: err.help().map(|help| help.to_string()),
+      kind: DiagnosticKind::Semantic(err),
+      primary_span: span_range.clone(),
+      secondary_spans: vec![],
+    }
+  }
+
+  /// Creates an error diagnostic from the generator error at the given span.
+  pub fn generator(err: GeneratorErr, span_range: &SpanRange) -> Self {
+    Self {
+      code: err.code(),
+      severity: Severity::Error,
+      message: err.to_string(),
+      help: Some(err.help().to_string()),
+      kind: DiagnosticKind::Generator(err),
+      primary_span: span_range.clone(),
+      secondary_spans: vec![],
+    }
+  }
+
+  /// Attaches a secondary span with the given message.
+  pub fn with_label(mut self, span_range: &SpanRange, message: impl ToString) -> Self {
+    self.secondary_spans.push(Label {
+      span_range: span_range.clone(),
+      message: message.to_string(),
+    });
+    self
+  }
+
+  /// Returns the semantic error if the diagnostic is reported by the analyzer.
+  pub fn err(&self) -> Option<&Err> {
+    match &self.kind {
+      DiagnosticKind::Semantic(err) => Some(err),
+      DiagnosticKind::Syntax(_) | DiagnosticKind::Generator(_) => None,
+    }
+  }
+
+  /// Returns the generator error if the diagnostic is reported by a generator.
+  pub fn generator_err(&self) -> Option<&GeneratorErr> {
+    match &self.kind {
+      DiagnosticKind::Generator(err) => Some(err),
+      DiagnosticKind::Syntax(_) | DiagnosticKind::Semantic(_) => None,
+    }
+  }
+
+  /// Converts the diagnostic into a `pest` error pointing at the given input.
+  ///
+  /// The message of the error is the same as the one produced before diagnostics were introduced.
+  /// The primary span is clamped to the input, as it may point into another text, such as the SQL
+  /// of an imported schema, or fall back to the start of the input if it is not on char boundaries.
+  pub fn to_pest_error(&self, input: &str) -> Error<Rule> {
+    let end = self.primary_span.end.min(input.len());
+    let start = self.primary_span.start.min(end);
+    let span = Span::new(input, start, end).unwrap_or_else(|| {
+      let start = Position::from_start(input);
+
+      start.span(&start)
+    });
+
+    Error::new_from_span(
+      ErrorVariant::CustomError {
+        message: self.message.clone(),
+      },
+      span,
+    )
+  }
+}
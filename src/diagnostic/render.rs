@@ -0,0 +1,112 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::*;
+use crate::source_map::SourceMap;
+
+impl Diagnostic {
+  /// Renders the diagnostic into a rustc-style report with the offending source lines.
+  ///
+  /// # Arguments
+  ///
+  /// * `source_map` - The source map of the text the diagnostic was reported on.
+  /// * `path` - The file name shown in the report.
+  ///
+  /// # Examples
+  ///
+  /// ```text
+  /// error[E0010]: Duplicate table name
+  ///  --> schema.dbml:9:7
+  ///   |
+  /// 9 | Table users {
+  ///   |       ^^^^^
+  ///   |
+  /// 5 | Table users {
+  ///   |       ----- previously defined here
+  ///   |
+  ///   = help: rename one of the tables or move it into another schema
+  /// ```
+  pub fn render(&self, source_map: &SourceMap, path: &str) -> String {
+    let mut out = String::new();
+
+    let lines = core::iter::once(&self.primary_span)
+      .chain(self.secondary_spans.iter().map(|label| &label.span_range))
+      .filter_map(|span_range| source_map.line_col(span_range.start))
+      .map(|line_col| line_col.line + 1);
+    let gutter = lines.max().unwrap_or(1).to_string().len();
+    let pad = " ".repeat(gutter);
+
+    let _ = writeln!(out, "{}", self);
+
+    if let Some(start) = source_map.line_col(self.primary_span.start) {
+      let _ = writeln!(out, "{}--> {}:{}:{}", pad, path, start.line + 1, start.col + 1);
+      let _ = writeln!(out, "{} |", pad);
+
+      render_span(&mut out, source_map, &self.primary_span, '^', "", gutter);
+
+      for label in &self.secondary_spans {
+        let _ = writeln!(out, "{} |", pad);
+
+        render_span(&mut out, source_map, &label.span_range, '-', &label.message, gutter);
+      }
+    }
+
+    if let Some(help) = &self.help {
+      let _ = writeln!(out, "{} |", pad);
+      let _ = writeln!(out, "{} = help: {}", pad, help);
+    }
+
+    out
+  }
+}
+
+/// Writes the first line covered by the span followed by the underline marker.
+fn render_span(
+  out: &mut String,
+  source_map: &SourceMap,
+  span_range: &SpanRange,
+  marker: char,
+  message: &str,
+  gutter: usize,
+) {
+  let (Some(start), Some(end)) = (
+    source_map.line_col(span_range.start),
+    source_map.line_col(span_range.end),
+  ) else {
+    return;
+  };
+  let text = source_map.line(start.line).unwrap_or_default();
+
+  // multi-line spans are underlined up to the end of the first line
+  let trailing = text.len() - text.trim_end().len();
+  let end_col = match end.line == start.line {
+    true => end.col,
+    false => text.len() - trailing,
+  };
+  let width = text
+    .get(start.col..end_col.max(start.col))
+    .map(|underlined| underlined.trim_end().chars().count())
+    .unwrap_or_default()
+    .max(1);
+  let indent = text
+    .get(..start.col)
+    .map(|prefix| prefix.chars().count())
+    .unwrap_or_default();
+
+  let _ = writeln!(out, "{:>gutter$} | {}", start.line + 1, text, gutter = gutter);
+  let _ = write!(
+    out,
+    "{:gutter$} | {}{}",
+    "",
+    " ".repeat(indent),
+    marker.to_string().repeat(width),
+    gutter = gutter
+  );
+
+  match message.is_empty() {
+    true => out.push('\n'),
+    false => {
+      let _ = writeln!(out, " {}", message);
+    }
+  }
+}
@@ -0,0 +1,516 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use derive_more::Display;
+
+use super::*;
+use crate::source_map::SourceMap;
+
+/// Represents how a change affects the existing data and the clients of a database.
+///
+/// The variants are ordered from the least to the most severe, so the maximum of the findings
+/// tells the verdict of a whole diff.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Display)]
+pub enum Compatibility {
+  /// The change keeps all existing data and queries working.
+  #[display(fmt = "safe")]
+  Safe,
+  /// The change may fail or behave differently depending on the existing data.
+  #[display(fmt = "risky")]
+  Risky,
+  /// The change loses data or breaks existing queries or inserts.
+  #[display(fmt = "breaking")]
+  Breaking,
+}
+
+/// Represents a single difference between two schemas classified by its compatibility.
+#[derive(Debug, PartialEq, Eq, Clone, Display)]
+#[display(fmt = "{}: {}", compatibility, message)]
+pub struct Finding {
+  pub compatibility: Compatibility,
+  /// The human readable description of the difference.
+  pub message: String,
+  /// The range of the span in the new schema where the difference is found, or `None` if the
+  /// object is removed without anything left to point at, such as a dropped table.
+  pub span_range: Option<SpanRange>,
+}
+
+impl Finding {
+  /// Renders the finding prefixed with its location in the new schema, in the
+  /// `path:line:col: compatibility: message` form understood by editors and CI annotations.
+  ///
+  /// # Arguments
+  ///
+  /// * `source_map` - The source map of the new schema.
+  /// * `path` - The file name of the new schema.
+  pub fn render(&self, source_map: &SourceMap, path: &str) -> String {
+    match self
+      .span_range
+      .as_ref()
+      .and_then(|span_range| source_map.line_col(span_range.start))
+    {
+      Some(start) => format!("{}:{}:{}: {}", path, start.line + 1, start.col + 1, self),
+      None => format!("{}: {}", path, self),
+    }
+  }
+}
+
+impl<'a> SchemaDiff<'a> {
+  /// Classifies each difference as safe, risky or breaking.
+  ///
+  /// For example, dropping a table or a column, narrowing a column type, removing an enum value
+  /// used by a column default or making a column `not null` without a default are breaking,
+  /// while adding a unique constraint or a ref is risky since existing rows may violate it.
+  ///
+  /// # Examples
+  ///
+  /// ```rs
+  /// use dbml_rs::diff::{diff, Compatibility};
+  ///
+  /// let findings = diff(&old, &new).classify();
+  /// let is_breaking = findings.iter().any(|finding| finding.compatibility == Compatibility::Breaking);
+  /// ```
+  pub fn classify(&self) -> Vec<Finding> {
+    let mut findings = vec![];
+
+    for table_diff in &self.tables {
+      match table_diff {
+        TableDiff::Added(table) => {
+          findings.push(finding(
+            Compatibility::Safe,
+            format!("table {} is added", QualifiedIdent(table_name(table))),
+            Some(&table.ident.span_range),
+          ))
+        }
+        TableDiff::Removed(table) => {
+          findings.push(finding(
+            Compatibility::Breaking,
+            format!("table {} is dropped", QualifiedIdent(table_name(table))),
+            None,
+          ))
+        }
+        TableDiff::Changed { old, new, changes } => {
+          if table_diff.is_renamed() {
+            findings.push(finding(
+              Compatibility::Breaking,
+              format!(
+                "table {} is renamed to {}",
+                QualifiedIdent(table_name(old)),
+                QualifiedIdent(table_name(new))
+              ),
+              Some(&new.ident.span_range),
+            ));
+          }
+
+          for change in changes {
+            classify_table_change(&mut findings, new, change);
+          }
+        }
+      }
+    }
+
+    for enum_diff in &self.enums {
+      match enum_diff {
+        EnumDiff::Added(r#enum) => {
+          findings.push(finding(
+            Compatibility::Safe,
+            format!("enum {} is added", QualifiedIdent(enum_name(r#enum))),
+            Some(&r#enum.ident.span_range),
+          ))
+        }
+        EnumDiff::Removed(r#enum) => {
+          findings.push(finding(
+            Compatibility::Breaking,
+            format!("enum {} is dropped", QualifiedIdent(enum_name(r#enum))),
+            None,
+          ))
+        }
+        EnumDiff::Changed {
+          new,
+          added_values,
+          removed_values,
+          ..
+        } => {
+          let name = QualifiedIdent(enum_name(new));
+
+          for value in added_values {
+            findings.push(finding(
+              Compatibility::Safe,
+              format!("value {} is added to enum {}", value.value, name),
+              Some(&value.span_range),
+            ));
+          }
+          for value in removed_values {
+            findings.push(classify_removed_value(&self.tables, new, value));
+          }
+        }
+      }
+    }
+
+    for ref_diff in &self.refs {
+      match ref_diff {
+        RefDiff::Added(r#ref) => {
+          findings.push(finding(
+            Compatibility::Risky,
+            format!(
+              "ref {} {} {} is added, which existing rows may violate",
+              r#ref.lhs, r#ref.rel, r#ref.rhs
+            ),
+            Some(&r#ref.span_range),
+          ))
+        }
+        RefDiff::Removed(r#ref) => {
+          findings.push(finding(
+            Compatibility::Safe,
+            format!("ref {} {} {} is removed", r#ref.lhs, r#ref.rel, r#ref.rhs),
+            None,
+          ))
+        }
+        RefDiff::Changed { new, changes, .. } => {
+          for change in changes {
+            let property = match change {
+              RefChange::Relation => "relation",
+              RefChange::OnDelete => "delete action",
+              RefChange::OnUpdate => "update action",
+            };
+
+            findings.push(finding(
+              Compatibility::Risky,
+              format!("{} of ref {} {} {} changes", property, new.lhs, new.rel, new.rhs),
+              Some(&new.span_range),
+            ));
+          }
+        }
+      }
+    }
+
+    findings
+  }
+}
+
+fn classify_table_change(findings: &mut Vec<Finding>, table: &TableBlock, change: &TableChange) {
+  let table_name = QualifiedIdent(table_name(table));
+
+  match change {
+    TableChange::ColumnAdded(col) => {
+      let settings = col.settings.as_ref();
+
+      match is_not_null(col) && default_of(col).is_none() && !settings.is_some_and(|s| s.is_incremental) {
+        true => {
+          findings.push(finding(
+            Compatibility::Breaking,
+            format!("not null column {}.{} is added without a default", table_name, col.name),
+            Some(&col.span_range),
+          ))
+        }
+        false => {
+          findings.push(finding(
+            Compatibility::Safe,
+            format!("column {}.{} is added", table_name, col.name),
+            Some(&col.span_range),
+          ))
+        }
+      }
+    }
+    TableChange::ColumnRemoved(col) => {
+      findings.push(finding(
+        Compatibility::Breaking,
+        format!("column {}.{} is dropped", table_name, col.name),
+        Some(&table.ident.span_range),
+      ))
+    }
+    TableChange::ColumnChanged { old, new, changes } => {
+      for change in changes {
+        let col_name = format!("{}.{}", table_name, new.name);
+        let settings = |col: &TableColumn| col.settings.clone().unwrap_or_default();
+
+        let (compatibility, message) = match change {
+          ColumnChange::Type => {
+            let compatibility = classify_type_change(&old.r#type, &new.r#type);
+            let verb = match compatibility {
+              Compatibility::Safe => "widened",
+              Compatibility::Risky => "converted",
+              Compatibility::Breaking => "narrowed",
+            };
+
+            (
+              compatibility,
+              format!("column {} is {} from {} to {}", col_name, verb, old.r#type, new.r#type),
+            )
+          }
+          ColumnChange::Nullable if !is_not_null(new) => {
+            (Compatibility::Safe, format!("column {} becomes nullable", col_name))
+          }
+          ColumnChange::Nullable if default_of(new).is_none() => {
+            (
+              Compatibility::Breaking,
+              format!("column {} becomes not null without a default", col_name),
+            )
+          }
+          ColumnChange::Nullable => {
+            (
+              Compatibility::Risky,
+              format!(
+                "column {} becomes not null, which existing null values violate",
+                col_name
+              ),
+            )
+          }
+          ColumnChange::Default if default_of(new).is_none() && is_not_null(new) => {
+            (
+              Compatibility::Risky,
+              format!("default of not null column {} is removed", col_name),
+            )
+          }
+          ColumnChange::Default => (Compatibility::Safe, format!("default of column {} changes", col_name)),
+          ColumnChange::PrimaryKey => {
+            match settings(new).is_pk {
+              true => {
+                (
+                  Compatibility::Risky,
+                  format!("column {} is added to the primary key", col_name),
+                )
+              }
+              false => {
+                (
+                  Compatibility::Risky,
+                  format!("column {} is removed from the primary key", col_name),
+                )
+              }
+            }
+          }
+          ColumnChange::Unique => {
+            match settings(new).is_unique {
+              true => {
+                (
+                  Compatibility::Risky,
+                  format!("column {} becomes unique, which existing duplicates violate", col_name),
+                )
+              }
+              false => (Compatibility::Safe, format!("column {} is no longer unique", col_name)),
+            }
+          }
+          ColumnChange::Increment => {
+            match settings(new).is_incremental {
+              true => {
+                (
+                  Compatibility::Safe,
+                  format!("column {} becomes auto-incremented", col_name),
+                )
+              }
+              false => {
+                (
+                  Compatibility::Risky,
+                  format!("column {} is no longer auto-incremented", col_name),
+                )
+              }
+            }
+          }
+          ColumnChange::Note => (Compatibility::Safe, format!("note of column {} changes", col_name)),
+        };
+
+        findings.push(finding(compatibility, message, Some(&new.span_range)));
+      }
+    }
+    TableChange::IndexAdded(def) => {
+      let settings = def.settings.clone().unwrap_or_default();
+
+      match settings.is_pk || settings.is_unique {
+        true => {
+          findings.push(finding(
+            Compatibility::Risky,
+            format!(
+              "unique index {} is added to table {}, which existing duplicates violate",
+              def, table_name
+            ),
+            Some(&def.span_range),
+          ))
+        }
+        false => {
+          findings.push(finding(
+            Compatibility::Safe,
+            format!("index {} is added to table {}", def, table_name),
+            Some(&def.span_range),
+          ))
+        }
+      }
+    }
+    TableChange::IndexRemoved(def) => {
+      let span_range = table
+        .indexes
+        .as_ref()
+        .map_or(&table.ident.span_range, |indexes| &indexes.span_range);
+
+      findings.push(finding(
+        Compatibility::Safe,
+        format!("index {} is dropped from table {}", def, table_name),
+        Some(span_range),
+      ))
+    }
+  }
+}
+
+/// Classifies a removed enum value, which is breaking if a column of the enum still defaults to
+/// it and risky otherwise since existing rows may hold it.
+fn classify_removed_value(table_diffs: &[TableDiff], r#enum: &EnumBlock, value: &EnumValue) -> Finding {
+  let name = enum_name(r#enum);
+  let defaulting_col = table_diffs
+    .iter()
+    .filter_map(|table_diff| {
+      match table_diff {
+        TableDiff::Added(table) | TableDiff::Changed { new: table, .. } => Some(*table),
+        TableDiff::Removed(_) => None,
+      }
+    })
+    .flat_map(|table| table.cols.iter().map(move |col| (table, col)))
+    .find(|(_, col)| {
+      is_of_enum(col, &name)
+        && matches!(
+          default_of(col),
+          Some(Value::Enum(default) | Value::String(default)) if default == &value.value.to_string
+        )
+    });
+
+  match defaulting_col {
+    Some((table, col)) => {
+      finding(
+        Compatibility::Breaking,
+        format!(
+          "value {} is removed from enum {} but column {}.{} defaults to it",
+          value.value,
+          QualifiedIdent(name.clone()),
+          QualifiedIdent(table_name(table)),
+          col.name
+        ),
+        Some(&col.span_range),
+      )
+    }
+    None => {
+      finding(
+        Compatibility::Risky,
+        format!(
+          "value {} is removed from enum {}, which existing rows may hold",
+          value.value,
+          QualifiedIdent(name)
+        ),
+        Some(&r#enum.ident.span_range),
+      )
+    }
+  }
+}
+
+/// Classifies a type change by whether every value of the old type fits the new type.
+///
+/// Changes within the integer, floating point, character and bit string types are safe if they
+/// widen the type and breaking if they narrow it. Other changes are risky since converting the
+/// existing values may fail.
+fn classify_type_change(old: &ColumnType, new: &ColumnType) -> Compatibility {
+  if old.arrays != new.arrays {
+    return Compatibility::Risky;
+  }
+
+  let old_name = builtin_type_name(&old.type_name);
+  let new_name = builtin_type_name(&new.type_name);
+
+  if let (Some(old_rank), Some(new_rank)) = (numeric_rank(&old_name), numeric_rank(&new_name)) {
+    return match (old_rank.0 == new_rank.0, old_rank.1 <= new_rank.1) {
+      (true, true) => Compatibility::Safe,
+      (true, false) => Compatibility::Breaking,
+      (false, _) => Compatibility::Risky,
+    };
+  }
+
+  let is_text = |name: &ColumnTypeName| {
+    matches!(
+      name,
+      ColumnTypeName::Char | ColumnTypeName::VarChar | ColumnTypeName::Text
+    )
+  };
+  let is_bits = |name: &ColumnTypeName| matches!(name, ColumnTypeName::Bit | ColumnTypeName::Varbit);
+  if (is_text(&old_name) && is_text(&new_name)) || (is_bits(&old_name) && is_bits(&new_name)) {
+    return match (max_length(&old_name, &old.args), max_length(&new_name, &new.args)) {
+      (_, None) => Compatibility::Safe,
+      (None, Some(_)) => Compatibility::Breaking,
+      (Some(old_len), Some(new_len)) if old_len <= new_len => Compatibility::Safe,
+      (Some(_), Some(_)) => Compatibility::Breaking,
+    };
+  }
+
+  if old_name == ColumnTypeName::Decimal && new_name == ColumnTypeName::Decimal {
+    return match (precision(&old.args), precision(&new.args)) {
+      (_, None) => Compatibility::Safe,
+      (None, Some(_)) => Compatibility::Breaking,
+      (Some((old_p, old_s)), Some((new_p, new_s))) if new_s >= old_s && new_p - new_s >= old_p - old_s => {
+        Compatibility::Safe
+      }
+      (Some(_), Some(_)) => Compatibility::Breaking,
+    };
+  }
+
+  Compatibility::Risky
+}
+
+/// Resolves a type name left unparsed by the parser into a built-in type if possible.
+fn builtin_type_name(type_name: &ColumnTypeName) -> ColumnTypeName {
+  match type_name {
+    ColumnTypeName::Raw(raw) | ColumnTypeName::Enum(raw) => {
+      ColumnTypeName::from_str(&raw.to_lowercase()).unwrap_or_else(|_| type_name.clone())
+    }
+    type_name => type_name.clone(),
+  }
+}
+
+/// Gets the family and the rank within the family of an integer or floating point type.
+fn numeric_rank(type_name: &ColumnTypeName) -> Option<(u8, u8)> {
+  match type_name {
+    ColumnTypeName::SmallInt | ColumnTypeName::SmallSerial => Some((0, 0)),
+    ColumnTypeName::Integer | ColumnTypeName::Serial => Some((0, 1)),
+    ColumnTypeName::BigInt | ColumnTypeName::BigSerial => Some((0, 2)),
+    ColumnTypeName::Real => Some((1, 0)),
+    ColumnTypeName::DoublePrecision => Some((1, 1)),
+    _ => None,
+  }
+}
+
+/// Gets the maximum length of a character or bit string type, or `None` if it is unbounded.
+fn max_length(type_name: &ColumnTypeName, args: &[Value]) -> Option<i64> {
+  match (type_name, args.first()) {
+    (ColumnTypeName::Text, _) => None,
+    (_, Some(Value::Integer(len))) => Some(*len),
+    (ColumnTypeName::Char | ColumnTypeName::Bit, _) => Some(1),
+    _ => None,
+  }
+}
+
+/// Gets the precision and scale of a decimal type, or `None` if it is unconstrained.
+fn precision(args: &[Value]) -> Option<(i64, i64)> {
+  match args {
+    [Value::Integer(precision)] => Some((*precision, 0)),
+    [Value::Integer(precision), Value::Integer(scale)] => Some((*precision, *scale)),
+    _ => None,
+  }
+}
+
+/// Checks if the type of the column names the enum.
+fn is_of_enum(col: &TableColumn, name: &QualifiedName) -> bool {
+  let raw = match &col.r#type.type_name {
+    ColumnTypeName::Raw(raw) | ColumnTypeName::Enum(raw) => raw,
+    _ => return false,
+  };
+
+  match raw.split_once('.') {
+    Some((schema, type_name)) => {
+      type_name == name.name && name.schema.as_deref().unwrap_or(crate::DEFAULT_SCHEMA) == schema
+    }
+    None => raw == &name.name && name.schema.is_none(),
+  }
+}
+
+fn finding(compatibility: Compatibility, message: String, span_range: Option<&SpanRange>) -> Finding {
+  Finding {
+    compatibility,
+    message,
+    span_range: span_range.cloned(),
+  }
+}
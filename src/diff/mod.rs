@@ -0,0 +1,650 @@
+use alloc::boxed::Box;
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::analyzer::analyze_all;
+use crate::ast::*;
+use crate::generator::QualifiedName;
+
+mod compat;
+
+pub use compat::*;
+
+/// Represents the differences between an old and a new schema.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiff<'a> {
+  /// The tables added, removed or changed, in the order of the new schema followed by the
+  /// removed ones.
+  pub tables: Vec<TableDiff<'a>>,
+  /// The enums added, removed or changed.
+  pub enums: Vec<EnumDiff<'a>>,
+  /// The refs added, removed or changed. Refs are collected from the analyzed schemas, so inline
+  /// refs are included and table aliases are resolved.
+  pub refs: Vec<RefDiff>,
+}
+
+/// Represents a difference of a table.
+#[derive(Debug, Clone)]
+pub enum TableDiff<'a> {
+  Added(&'a TableBlock),
+  Removed(&'a TableBlock),
+  /// A table found in both schemas. It is renamed if a removed table and an added table have
+  /// the same columns.
+  Changed {
+    old: &'a TableBlock,
+    new: &'a TableBlock,
+    changes: Vec<TableChange<'a>>,
+  },
+}
+
+/// Represents a change of the columns or indexes of a table.
+#[derive(Debug, Clone)]
+pub enum TableChange<'a> {
+  ColumnAdded(&'a TableColumn),
+  ColumnRemoved(&'a TableColumn),
+  ColumnChanged {
+    old: &'a TableColumn,
+    new: &'a TableColumn,
+    changes: Vec<ColumnChange>,
+  },
+  IndexAdded(&'a IndexesDef),
+  IndexRemoved(&'a IndexesDef),
+}
+
+/// Represents the property of a column which is changed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColumnChange {
+  /// The type name, arguments or array dimensions.
+  Type,
+  Nullable,
+  Default,
+  PrimaryKey,
+  Unique,
+  Increment,
+  Note,
+}
+
+/// Represents a difference of an enum.
+#[derive(Debug, Clone)]
+pub enum EnumDiff<'a> {
+  Added(&'a EnumBlock),
+  Removed(&'a EnumBlock),
+  Changed {
+    old: &'a EnumBlock,
+    new: &'a EnumBlock,
+    added_values: Vec<&'a EnumValue>,
+    removed_values: Vec<&'a EnumValue>,
+  },
+}
+
+/// Represents a difference of a ref, which is identified by the columns on both sides.
+#[derive(Debug, Clone)]
+pub enum RefDiff {
+  Added(RefBlock),
+  Removed(RefBlock),
+  Changed {
+    old: Box<RefBlock>,
+    new: Box<RefBlock>,
+    changes: Vec<RefChange>,
+  },
+}
+
+/// Represents the property of a ref which is changed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RefChange {
+  Relation,
+  OnDelete,
+  OnUpdate,
+}
+
+impl<'a> SchemaDiff<'a> {
+  /// Checks if both schemas are the same.
+  pub fn is_empty(&self) -> bool {
+    self.tables.is_empty() && self.enums.is_empty() && self.refs.is_empty()
+  }
+}
+
+impl<'a> TableDiff<'a> {
+  /// Checks if the table is found under another name in the new schema.
+  pub fn is_renamed(&self) -> bool {
+    match self {
+      Self::Changed { old, new, .. } => table_name(old) != table_name(new),
+      _ => false,
+    }
+  }
+}
+
+/// Compares two schemas, which are expected to be semantically valid.
+///
+/// Tables and enums are matched by their schema and name, columns by their name, and indexes by
+/// their columns and settings. A removed table and an added table with the same column names
+/// and types are reported as a renamed table.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::diff::diff;
+///
+/// let old = parse_dbml_unchecked("Table users { id int [pk] }").unwrap();
+/// let new = parse_dbml_unchecked("Table users { id bigint [pk] }").unwrap();
+/// println!("{}", diff(&old, &new));
+/// ```
+pub fn diff<'a>(old: &'a SchemaBlock, new: &'a SchemaBlock) -> SchemaDiff<'a> {
+  SchemaDiff {
+    tables: diff_tables(&old.tables(), &new.tables()),
+    enums: diff_enums(&old.enums(), &new.enums()),
+    refs: diff_refs(&analyzed_refs(old), &analyzed_refs(new)),
+  }
+}
+
+fn diff_tables<'a>(old_tables: &[&'a TableBlock], new_tables: &[&'a TableBlock]) -> Vec<TableDiff<'a>> {
+  let mut removed: Vec<_> = old_tables
+    .iter()
+    .filter(|old| !new_tables.iter().any(|new| table_name(new) == table_name(old)))
+    .copied()
+    .collect();
+
+  let mut diffs = vec![];
+  for new in new_tables {
+    let old = match old_tables.iter().find(|old| table_name(old) == table_name(new)) {
+      Some(old) => Some(*old),
+      None => {
+        removed
+          .iter()
+          .position(|old| col_signature(old) == col_signature(new))
+          .map(|i| removed.remove(i))
+      }
+    };
+
+    match old {
+      Some(old) => {
+        let changes = diff_table(old, new);
+
+        if !changes.is_empty() || table_name(old) != table_name(new) {
+          diffs.push(TableDiff::Changed { old, new, changes });
+        }
+      }
+      None => diffs.push(TableDiff::Added(new)),
+    }
+  }
+  diffs.extend(removed.into_iter().map(TableDiff::Removed));
+
+  diffs
+}
+
+fn diff_table<'a>(old: &'a TableBlock, new: &'a TableBlock) -> Vec<TableChange<'a>> {
+  let mut changes = vec![];
+
+  for new_col in &new.cols {
+    match old.cols.iter().find(|col| col.name.to_string == new_col.name.to_string) {
+      Some(old_col) => {
+        let col_changes = diff_col(old_col, new_col);
+
+        if !col_changes.is_empty() {
+          changes.push(TableChange::ColumnChanged {
+            old: old_col,
+            new: new_col,
+            changes: col_changes,
+          });
+        }
+      }
+      None => changes.push(TableChange::ColumnAdded(new_col)),
+    }
+  }
+  changes.extend(
+    old
+      .cols
+      .iter()
+      .filter(|old_col| !new.cols.iter().any(|col| col.name.to_string == old_col.name.to_string))
+      .map(TableChange::ColumnRemoved),
+  );
+
+  let old_indexes = index_defs(old);
+  let new_indexes = index_defs(new);
+  changes.extend(
+    new_indexes
+      .iter()
+      .filter(|def| !old_indexes.iter().any(|old_def| index_key(old_def) == index_key(def)))
+      .map(|def| TableChange::IndexAdded(def)),
+  );
+  changes.extend(
+    old_indexes
+      .iter()
+      .filter(|def| !new_indexes.iter().any(|new_def| index_key(new_def) == index_key(def)))
+      .map(|def| TableChange::IndexRemoved(def)),
+  );
+
+  changes
+}
+
+fn diff_col(old: &TableColumn, new: &TableColumn) -> Vec<ColumnChange> {
+  let old_settings = old.settings.as_ref();
+  let new_settings = new.settings.as_ref();
+  let mut changes = vec![];
+
+  if old.r#type.type_name != new.r#type.type_name
+    || old.r#type.args != new.r#type.args
+    || old.r#type.arrays != new.r#type.arrays
+  {
+    changes.push(ColumnChange::Type);
+  }
+  if is_not_null(old) != is_not_null(new) {
+    changes.push(ColumnChange::Nullable);
+  }
+  if default_of(old) != default_of(new) {
+    changes.push(ColumnChange::Default);
+  }
+  if old_settings.is_some_and(|s| s.is_pk) != new_settings.is_some_and(|s| s.is_pk) {
+    changes.push(ColumnChange::PrimaryKey);
+  }
+  if old_settings.is_some_and(|s| s.is_unique) != new_settings.is_some_and(|s| s.is_unique) {
+    changes.push(ColumnChange::Unique);
+  }
+  if old_settings.is_some_and(|s| s.is_incremental) != new_settings.is_some_and(|s| s.is_incremental) {
+    changes.push(ColumnChange::Increment);
+  }
+  if old_settings.and_then(|s| s.note.as_ref()) != new_settings.and_then(|s| s.note.as_ref()) {
+    changes.push(ColumnChange::Note);
+  }
+
+  changes
+}
+
+fn diff_enums<'a>(old_enums: &[&'a EnumBlock], new_enums: &[&'a EnumBlock]) -> Vec<EnumDiff<'a>> {
+  let mut diffs = vec![];
+
+  for new in new_enums {
+    match old_enums.iter().find(|old| enum_name(old) == enum_name(new)) {
+      Some(old) => {
+        let has_value = |r#enum: &EnumBlock, value: &EnumValue| {
+          r#enum
+            .values
+            .iter()
+            .any(|other| other.value.to_string == value.value.to_string)
+        };
+        let added_values: Vec<_> = new.values.iter().filter(|value| !has_value(old, value)).collect();
+        let removed_values: Vec<_> = old.values.iter().filter(|value| !has_value(new, value)).collect();
+
+        if !added_values.is_empty() || !removed_values.is_empty() {
+          diffs.push(EnumDiff::Changed {
+            old,
+            new,
+            added_values,
+            removed_values,
+          });
+        }
+      }
+      None => diffs.push(EnumDiff::Added(new)),
+    }
+  }
+  diffs.extend(
+    old_enums
+      .iter()
+      .filter(|old| !new_enums.iter().any(|new| enum_name(new) == enum_name(old)))
+      .map(|old| EnumDiff::Removed(old)),
+  );
+
+  diffs
+}
+
+fn diff_refs(old_refs: &[RefBlock], new_refs: &[RefBlock]) -> Vec<RefDiff> {
+  let mut diffs = vec![];
+
+  for new in new_refs {
+    let (new_key, new_rel) = ref_key(new);
+
+    match old_refs.iter().find(|old| ref_key(old).0 == new_key) {
+      Some(old) => {
+        let mut changes = vec![];
+        if ref_key(old).1 != new_rel {
+          changes.push(RefChange::Relation);
+        }
+        if on_delete(old) != on_delete(new) {
+          changes.push(RefChange::OnDelete);
+        }
+        if on_update(old) != on_update(new) {
+          changes.push(RefChange::OnUpdate);
+        }
+
+        if !changes.is_empty() {
+          diffs.push(RefDiff::Changed {
+            old: Box::new(old.clone()),
+            new: Box::new(new.clone()),
+            changes,
+          });
+        }
+      }
+      None => diffs.push(RefDiff::Added(new.clone())),
+    }
+  }
+  diffs.extend(
+    old_refs
+      .iter()
+      .filter(|old| !new_refs.iter().any(|new| ref_key(new).0 == ref_key(old).0))
+      .map(|old| RefDiff::Removed(old.clone())),
+  );
+
+  diffs
+}
+
+/// Collects the refs of the schema, including inline refs, with table aliases resolved.
+fn analyzed_refs(schema_block: &SchemaBlock) -> Vec<RefBlock> {
+  let (analyzed_indexer, _) = analyze_all(schema_block);
+  let indexer = &analyzed_indexer.indexer;
+
+  analyzed_indexer
+    .indexed_refs
+    .into_iter()
+    .map(|indexed_ref| {
+      RefBlock {
+        lhs: indexer.resolve_ref_alias(&indexed_ref.lhs),
+        rhs: indexer.resolve_ref_alias(&indexed_ref.rhs),
+        span_range: indexed_ref.span_range,
+        name: indexed_ref.name,
+        rel: indexed_ref.rel,
+        settings: indexed_ref.settings,
+      }
+    })
+    .collect()
+}
+
+/// Identifies a ref by both of its sides regardless of their order, along with the relation
+/// from the first side to the second.
+fn ref_key(r#ref: &RefBlock) -> ((String, String), Relation) {
+  let lhs = ref_side(&r#ref.lhs);
+  let rhs = ref_side(&r#ref.rhs);
+
+  match lhs <= rhs {
+    true => ((lhs, rhs), r#ref.rel.clone()),
+    false => {
+      let rel = match r#ref.rel {
+        Relation::One2Many => Relation::Many2One,
+        Relation::Many2One => Relation::One2Many,
+        ref rel => rel.clone(),
+      };
+
+      ((rhs, lhs), rel)
+    }
+  }
+}
+
+fn ref_side(ref_ident: &RefIdent) -> String {
+  let table = QualifiedName::new(&ref_ident.schema, &ref_ident.table);
+  let cols: Vec<_> = ref_ident
+    .compositions
+    .iter()
+    .map(|col| col.to_string.as_str())
+    .collect();
+
+  format!("{:?}.{}({})", table.schema, table.name, cols.join(","))
+}
+
+fn on_delete(r#ref: &RefBlock) -> Option<String> {
+  r#ref
+    .settings
+    .as_ref()
+    .and_then(|settings| settings.on_delete.as_ref())
+    .map(ToString::to_string)
+}
+
+fn on_update(r#ref: &RefBlock) -> Option<String> {
+  r#ref
+    .settings
+    .as_ref()
+    .and_then(|settings| settings.on_update.as_ref())
+    .map(ToString::to_string)
+}
+
+fn table_name(table: &TableBlock) -> QualifiedName {
+  QualifiedName::new(&table.ident.schema, &table.ident.name)
+}
+
+fn enum_name(r#enum: &EnumBlock) -> QualifiedName {
+  QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name)
+}
+
+/// Lists the column names and types, which identify a renamed table.
+fn col_signature(table: &TableBlock) -> Vec<(&str, String)> {
+  table
+    .cols
+    .iter()
+    .map(|col| (col.name.to_string.as_str(), col.r#type.to_string()))
+    .collect()
+}
+
+fn index_defs(table: &TableBlock) -> Vec<&IndexesDef> {
+  table.indexes.iter().flat_map(|indexes| indexes.defs.iter()).collect()
+}
+
+/// Identifies an index by its columns and settings, except for its note.
+fn index_key(def: &IndexesDef) -> String {
+  let cols: Vec<_> = def.cols.iter().map(ToString::to_string).collect();
+
+  match &def.settings {
+    Some(settings) => {
+      format!(
+        "({}) {} {} {:?} {:?}",
+        cols.join(", "),
+        settings.is_pk,
+        settings.is_unique,
+        settings.r#type.as_ref().map(ToString::to_string),
+        settings.name
+      )
+    }
+    None => format!("({})", cols.join(", ")),
+  }
+}
+
+fn is_not_null(col: &TableColumn) -> bool {
+  col
+    .settings
+    .as_ref()
+    .is_some_and(|settings| settings.nullable == Some(Nullable::NotNull))
+}
+
+fn default_of(col: &TableColumn) -> Option<&Value> {
+  col.settings.as_ref().and_then(|settings| settings.default.as_ref())
+}
+
+/// Writes the differences as a changelog, with one line per change prefixed by `+` for
+/// additions, `-` for removals and `~` for changes.
+impl<'a> fmt::Display for SchemaDiff<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for table_diff in &self.tables {
+      match table_diff {
+        TableDiff::Added(table) => writeln!(f, "+ Table {}", QualifiedIdent(table_name(table)))?,
+        TableDiff::Removed(table) => writeln!(f, "- Table {}", QualifiedIdent(table_name(table)))?,
+        TableDiff::Changed { old, new, changes } => {
+          match table_diff.is_renamed() {
+            true => {
+              writeln!(
+                f,
+                "~ Table {} renamed to {}",
+                QualifiedIdent(table_name(old)),
+                QualifiedIdent(table_name(new))
+              )?
+            }
+            false => writeln!(f, "~ Table {}", QualifiedIdent(table_name(new)))?,
+          }
+
+          for change in changes {
+            fmt_table_change(f, change)?;
+          }
+        }
+      }
+    }
+
+    for enum_diff in &self.enums {
+      match enum_diff {
+        EnumDiff::Added(r#enum) => writeln!(f, "+ Enum {}", QualifiedIdent(enum_name(r#enum)))?,
+        EnumDiff::Removed(r#enum) => writeln!(f, "- Enum {}", QualifiedIdent(enum_name(r#enum)))?,
+        EnumDiff::Changed {
+          new,
+          added_values,
+          removed_values,
+          ..
+        } => {
+          writeln!(f, "~ Enum {}", QualifiedIdent(enum_name(new)))?;
+
+          for value in added_values {
+            writeln!(f, "  + Value {}", value.value)?;
+          }
+          for value in removed_values {
+            writeln!(f, "  - Value {}", value.value)?;
+          }
+        }
+      }
+    }
+
+    for ref_diff in &self.refs {
+      match ref_diff {
+        RefDiff::Added(r#ref) => writeln!(f, "+ {}", r#ref)?,
+        RefDiff::Removed(r#ref) => writeln!(f, "- {}", r#ref)?,
+        RefDiff::Changed { old, new, changes } => {
+          writeln!(f, "~ Ref: {} {} {}", new.lhs, new.rel, new.rhs)?;
+
+          for change in changes {
+            match change {
+              RefChange::Relation => writeln!(f, "  ~ relation: {} -> {}", old.rel, new.rel)?,
+              RefChange::OnDelete => {
+                writeln!(
+                  f,
+                  "  ~ delete: {} -> {}",
+                  Optional(on_delete(old)),
+                  Optional(on_delete(new))
+                )?
+              }
+              RefChange::OnUpdate => {
+                writeln!(
+                  f,
+                  "  ~ update: {} -> {}",
+                  Optional(on_update(old)),
+                  Optional(on_update(new))
+                )?
+              }
+            }
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+fn fmt_table_change(f: &mut fmt::Formatter<'_>, change: &TableChange) -> fmt::Result {
+  match change {
+    TableChange::ColumnAdded(col) => writeln!(f, "  + Column {}", col),
+    TableChange::ColumnRemoved(col) => writeln!(f, "  - Column {}", col.name),
+    TableChange::IndexAdded(def) => writeln!(f, "  + Index {}", def),
+    TableChange::IndexRemoved(def) => writeln!(f, "  - Index {}", def),
+    TableChange::ColumnChanged { old, new, changes } => {
+      for change in changes {
+        write!(f, "  ~ Column {}: ", new.name)?;
+
+        let old_settings = old.settings.as_ref();
+        let new_settings = new.settings.as_ref();
+        match change {
+          ColumnChange::Type => writeln!(f, "type {} -> {}", old.r#type, new.r#type)?,
+          ColumnChange::Nullable => {
+            let nullability = |col: &TableColumn| if is_not_null(col) { "not null" } else { "null" };
+
+            writeln!(f, "{} -> {}", nullability(old), nullability(new))?
+          }
+          ColumnChange::Default => {
+            writeln!(
+              f,
+              "default {} -> {}",
+              Optional(default_of(old).map(literal)),
+              Optional(default_of(new).map(literal))
+            )?
+          }
+          ColumnChange::PrimaryKey => writeln!(f, "pk {}", added_or_removed(new_settings.is_some_and(|s| s.is_pk)))?,
+          ColumnChange::Unique => {
+            writeln!(
+              f,
+              "unique {}",
+              added_or_removed(new_settings.is_some_and(|s| s.is_unique))
+            )?
+          }
+          ColumnChange::Increment => {
+            writeln!(
+              f,
+              "increment {}",
+              added_or_removed(new_settings.is_some_and(|s| s.is_incremental))
+            )?
+          }
+          ColumnChange::Note => {
+            let note = |settings: Option<&ColumnSettings>| {
+              settings
+                .and_then(|s| s.note.clone())
+                .map(|note| literal(&Value::String(note)))
+            };
+
+            writeln!(
+              f,
+              "note {} -> {}",
+              Optional(note(old_settings)),
+              Optional(note(new_settings))
+            )?
+          }
+        }
+      }
+
+      Ok(())
+    }
+  }
+}
+
+fn added_or_removed(is_added: bool) -> &'static str {
+  match is_added {
+    true => "added",
+    false => "removed",
+  }
+}
+
+fn literal(value: &Value) -> Literal {
+  Literal {
+    span_range: SpanRange::default(),
+    raw: value.to_string(),
+    value: value.clone(),
+  }
+}
+
+/// Writes a qualified name with its identifiers quoted if needed.
+struct QualifiedIdent(QualifiedName);
+
+impl fmt::Display for QualifiedIdent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let ident = |name: &str| {
+      Ident {
+        span_range: SpanRange::default(),
+        raw: name.to_string(),
+        to_string: name.to_string(),
+      }
+    };
+
+    if let Some(schema) = &self.0.schema {
+      write!(f, "{}.", ident(schema))?;
+    }
+
+    write!(f, "{}", ident(&self.0.name))
+  }
+}
+
+/// Writes a value or `none` if it is absent.
+struct Optional<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for Optional<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.0 {
+      Some(value) => write!(f, "{}", value),
+      None => write!(f, "none"),
+    }
+  }
+}
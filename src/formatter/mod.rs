@@ -0,0 +1,461 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::ast::*;
+use crate::parser::parse_comments;
+
+/// Represents how identifiers are quoted in the formatted output.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum QuoteStyle {
+  /// Quotes an identifier only if it contains characters other than alphanumerics and underscores.
+  #[default]
+  Minimal,
+  /// Always quotes identifiers with double quotes.
+  Always,
+}
+
+/// Represents the letter casing of block keywords such as `Table` and `Ref`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum KeywordCase {
+  /// E.g. `Table`, `TableGroup`.
+  #[default]
+  Pascal,
+  /// E.g. `table`, `tablegroup`.
+  Lower,
+  /// E.g. `TABLE`, `TABLEGROUP`.
+  Upper,
+}
+
+/// Represents the options for formatting DBML text.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+  /// The number of spaces for each indentation level.
+  pub indent_width: usize,
+  /// The quoting style of identifiers.
+  pub quote_style: QuoteStyle,
+  /// The letter casing of block keywords.
+  pub keyword_case: KeywordCase,
+}
+
+impl Default for FormatOptions {
+  fn default() -> Self {
+    Self {
+      indent_width: 2,
+      quote_style: QuoteStyle::default(),
+      keyword_case: KeywordCase::default(),
+    }
+  }
+}
+
+/// Formats the schema into an opinionated DBML text.
+///
+/// Column types and settings are aligned into columns, column attributes are ordered canonically
+/// (`pk`, `increment`, `not null`, `unique`, `default`, `note`, `ref`), and comments found in the
+/// source input are kept next to the elements they belong to.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the AST to be formatted.
+/// * `options` - A reference to the formatting options.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::formatter::{format, FormatOptions};
+///
+/// let ast = parse_dbml_unchecked("Table users { id int [not null, pk] }").unwrap();
+/// let formatted = format(&ast, &FormatOptions::default());
+/// ```
+pub fn format(schema_block: &SchemaBlock, options: &FormatOptions) -> String {
+  let mut writer = Writer::new(schema_block.input, options);
+
+  for (i, block) in schema_block.blocks.iter().enumerate() {
+    if i != 0 {
+      writer.blank();
+    }
+
+    match block {
+      TopLevelBlock::Project(block) => writer.project(block),
+      TopLevelBlock::Table(block) => writer.table(block),
+      TopLevelBlock::TableGroup(block) => writer.table_group(block),
+      TopLevelBlock::Ref(block) => writer.r#ref(block),
+      TopLevelBlock::Enum(block) => writer.r#enum(block),
+    }
+  }
+
+  writer.finish(&parse_comments(schema_block.input))
+}
+
+/// Represents a single output line before the comments are attached.
+struct Line {
+  /// The indentation level.
+  level: usize,
+  /// The formatted text which can contain line breaks inside multi-line strings.
+  text: String,
+  /// The source lines that the output line originates from.
+  src_lines: Option<RangeInclusive<usize>>,
+  /// Whether the line closes a block.
+  is_closing: bool,
+}
+
+struct Writer<'a> {
+  input: &'a str,
+  options: &'a FormatOptions,
+  line_starts: Vec<usize>,
+  lines: Vec<Line>,
+}
+
+impl<'a> Writer<'a> {
+  fn new(input: &'a str, options: &'a FormatOptions) -> Self {
+    let line_starts = core::iter::once(0)
+      .chain(input.match_indices('\n').map(|(i, _)| i + 1))
+      .collect();
+
+    Self {
+      input,
+      options,
+      line_starts,
+      lines: vec![],
+    }
+  }
+
+  fn line_of(&self, offset: usize) -> usize {
+    match self.line_starts.binary_search(&offset) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    }
+  }
+
+  fn push(&mut self, level: usize, text: String, src_lines: Option<RangeInclusive<usize>>) {
+    self.lines.push(Line {
+      level,
+      text,
+      src_lines,
+      is_closing: false,
+    })
+  }
+
+  /// Pushes a line originating from the whole span.
+  fn push_span(&mut self, level: usize, text: String, span_range: &SpanRange) {
+    let src_lines = self.line_of(span_range.start)..=self.line_of(span_range.end.saturating_sub(1));
+
+    self.push(level, text, Some(src_lines))
+  }
+
+  /// Pushes the opening line of a block originating from the first line of the span.
+  fn open(&mut self, level: usize, text: String, span_range: &SpanRange) {
+    let line = self.line_of(span_range.start);
+
+    self.push(level, text, Some(line..=line))
+  }
+
+  /// Pushes the closing line of a block originating from the last line of the span.
+  fn close(&mut self, level: usize, span_range: &SpanRange) {
+    let line = self.line_of(span_range.end.saturating_sub(1));
+
+    self.lines.push(Line {
+      level,
+      text: "}".to_string(),
+      src_lines: Some(line..=line),
+      is_closing: true,
+    })
+  }
+
+  fn blank(&mut self) {
+    self.push(0, String::new(), None)
+  }
+
+  fn keyword(&self, keyword: &str) -> String {
+    match self.options.keyword_case {
+      KeywordCase::Pascal => keyword.to_string(),
+      KeywordCase::Lower => keyword.to_lowercase(),
+      KeywordCase::Upper => keyword.to_uppercase(),
+    }
+  }
+
+  fn ident(&self, ident: &Ident) -> String {
+    match self.options.quote_style {
+      QuoteStyle::Minimal => ident.to_string(),
+      QuoteStyle::Always => format!("\"{}\"", ident.to_string),
+    }
+  }
+
+  fn schema_ident(&self, schema: &Option<Ident>, name: &Ident) -> String {
+    match schema {
+      Some(schema) => format!("{}.{}", self.ident(schema), self.ident(name)),
+      None => self.ident(name),
+    }
+  }
+
+  fn ref_ident(&self, ref_ident: &RefIdent) -> String {
+    let table = self.schema_ident(&ref_ident.schema, &ref_ident.table);
+    let cols: Vec<_> = ref_ident.compositions.iter().map(|col| self.ident(col)).collect();
+
+    match cols.as_slice() {
+      [col] => format!("{}.{}", table, col),
+      cols => format!("{}.({})", table, cols.join(", ")),
+    }
+  }
+
+  fn project(&mut self, block: &ProjectBlock) {
+    let header = format!("{} {} {{", self.keyword("Project"), self.ident(&block.ident));
+    self.open(0, header, &block.span_range);
+
+    for prop in &block.properties {
+      self.push_span(1, prop.to_string(), &prop.span_range);
+    }
+
+    if let Some(note) = &block.note {
+      self.push_span(1, format!("{}: {}", self.keyword("Note"), note.value), &note.span_range);
+    }
+
+    self.close(0, &block.span_range);
+  }
+
+  fn table(&mut self, block: &TableBlock) {
+    let mut header = format!(
+      "{} {}",
+      self.keyword("Table"),
+      self.schema_ident(&block.ident.schema, &block.ident.name)
+    );
+
+    if let Some(alias) = &block.ident.alias {
+      header += &format!(" as {}", self.ident(alias));
+    }
+    if let Some(settings) = block.settings.as_ref().filter(|s| !s.attributes.is_empty()) {
+      header += &format!(" {}", settings);
+    }
+
+    self.open(0, header + " {", &block.span_range);
+
+    let cols: Vec<_> = block
+      .cols
+      .iter()
+      .map(|col| (self.ident(&col.name), col.r#type.to_string(), self.col_settings(col)))
+      .collect();
+    let name_width = cols
+      .iter()
+      .map(|(name, _, _)| name.chars().count())
+      .max()
+      .unwrap_or_default();
+    let type_width = cols
+      .iter()
+      .map(|(_, ty, _)| ty.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    for (col, (name, ty, settings)) in block.cols.iter().zip(cols) {
+      let text = match settings {
+        Some(settings) => format!("{:name_width$} {:type_width$} {}", name, ty, settings),
+        None => format!("{:name_width$} {}", name, ty).trim_end().to_string(),
+      };
+
+      self.push_span(1, text, &col.span_range);
+    }
+
+    if let Some(indexes) = &block.indexes {
+      self.blank();
+      self.open(1, format!("{} {{", self.keyword("Indexes")), &indexes.span_range);
+
+      for def in &indexes.defs {
+        self.push_span(2, self.indexes_def(def), &def.span_range);
+      }
+
+      self.close(1, &indexes.span_range);
+    }
+
+    if let Some(note) = &block.note {
+      self.blank();
+      self.push_span(1, format!("{}: {}", self.keyword("Note"), note.value), &note.span_range);
+    }
+
+    self.close(0, &block.span_range);
+  }
+
+  fn col_settings(&self, col: &TableColumn) -> Option<String> {
+    let settings = col.settings.as_ref()?;
+
+    let rank = |attr: &&Attribute| {
+      match attr.key.to_string.as_str() {
+        "pk" | "primary key" => 0,
+        "increment" => 1,
+        "not null" | "null" => 2,
+        "unique" => 3,
+        "default" => 4,
+        "note" => 5,
+        _ => 6,
+      }
+    };
+
+    let mut attributes: Vec<_> = settings.attributes.iter().collect();
+    attributes.sort_by_key(rank);
+
+    let items: Vec<_> = attributes
+      .into_iter()
+      .map(|attr| attr.to_string())
+      .chain(
+        settings
+          .refs
+          .iter()
+          .map(|r| format!("ref: {} {}", r.rel, self.ref_ident(&r.rhs))),
+      )
+      .collect();
+
+    if items.is_empty() {
+      None
+    } else {
+      Some(format!("[{}]", items.join(", ")))
+    }
+  }
+
+  fn indexes_def(&self, def: &IndexesDef) -> String {
+    let cols: Vec<_> = def
+      .cols
+      .iter()
+      .map(|col| {
+        match col {
+          IndexesColumnType::String(ident) => self.ident(ident),
+          col => col.to_string(),
+        }
+      })
+      .collect();
+
+    let cols = match cols.as_slice() {
+      [col] => col.clone(),
+      cols => format!("({})", cols.join(", ")),
+    };
+
+    match def.settings.as_ref().filter(|s| !s.attributes.is_empty()) {
+      Some(settings) => format!("{} {}", cols, settings),
+      None => cols,
+    }
+  }
+
+  fn table_group(&mut self, block: &TableGroupBlock) {
+    let header = format!("{} {} {{", self.keyword("TableGroup"), self.ident(&block.ident));
+    self.open(0, header, &block.span_range);
+
+    for item in &block.items {
+      self.push_span(1, self.schema_ident(&item.schema, &item.ident_alias), &item.span_range);
+    }
+
+    self.close(0, &block.span_range);
+  }
+
+  fn r#ref(&mut self, block: &RefBlock) {
+    let mut text = self.keyword("Ref");
+
+    if let Some(name) = &block.name {
+      text += &format!(" {}", self.ident(name));
+    }
+
+    text += &format!(
+      ": {} {} {}",
+      self.ref_ident(&block.lhs),
+      block.rel,
+      self.ref_ident(&block.rhs)
+    );
+
+    if let Some(settings) = block.settings.as_ref().filter(|s| !s.attributes.is_empty()) {
+      text += &format!(" {}", settings);
+    }
+
+    self.push_span(0, text, &block.span_range);
+  }
+
+  fn r#enum(&mut self, block: &EnumBlock) {
+    let header = format!(
+      "{} {} {{",
+      self.keyword("Enum"),
+      self.schema_ident(&block.ident.schema, &block.ident.name)
+    );
+    self.open(0, header, &block.span_range);
+
+    for value in &block.values {
+      let mut text = self.ident(&value.value);
+
+      if let Some(settings) = value.settings.as_ref().filter(|s| !s.attributes.is_empty()) {
+        text += &format!(" {}", settings);
+      }
+
+      self.push_span(1, text, &value.span_range);
+    }
+
+    self.close(0, &block.span_range);
+  }
+
+  /// Attaches the comments to the output lines and joins them into the final text.
+  ///
+  /// A comment following code on the same source line stays at the end of the output line
+  /// originating from that source line. Otherwise, it is placed above the next output line.
+  fn finish(self, comments: &[SpanRange]) -> String {
+    let mut leading = vec![vec![]; self.lines.len() + 1];
+    let mut trailing = vec![vec![]; self.lines.len()];
+
+    for comment in comments {
+      let text = &self.input[comment.clone()];
+      let line = self.line_of(comment.start);
+      let has_code_before = !self.input[self.line_starts[line]..comment.start].trim().is_empty();
+
+      let trailing_idx = has_code_before
+        .then(|| {
+          self
+            .lines
+            .iter()
+            .rposition(|l| l.src_lines.as_ref().is_some_and(|src| src.contains(&line)))
+        })
+        .flatten();
+
+      match trailing_idx {
+        Some(idx) => trailing[idx].push(text),
+        None => {
+          let idx = self
+            .lines
+            .iter()
+            .position(|l| l.src_lines.as_ref().is_some_and(|src| *src.start() > line))
+            .unwrap_or(self.lines.len());
+
+          leading[idx].push(text)
+        }
+      }
+    }
+
+    let indent = |level: usize| " ".repeat(level * self.options.indent_width);
+    let mut out = String::new();
+
+    for (idx, line) in self.lines.iter().enumerate() {
+      let comment_level = if line.is_closing { line.level + 1 } else { line.level };
+
+      for comment in &leading[idx] {
+        out += &format!("{}{}\n", indent(comment_level), comment);
+      }
+
+      if !line.text.is_empty() {
+        out += &indent(line.level);
+        out += &line.text;
+      }
+      for comment in &trailing[idx] {
+        out += &format!(" {}", comment);
+      }
+
+      out.push('\n');
+    }
+
+    if !leading[self.lines.len()].is_empty() {
+      if !self.lines.is_empty() {
+        out.push('\n');
+      }
+
+      for comment in &leading[self.lines.len()] {
+        out += &format!("{}\n", comment);
+      }
+    }
+
+    out
+  }
+}
@@ -0,0 +1,233 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::*;
+
+/// The header fill of tables without a `headercolor` setting.
+const DEFAULT_HEADER_COLOR: &str = "#E0E0E0";
+
+/// Generates a Graphviz graph in the DOT language from the schema.
+///
+/// Each table becomes a node drawn as an HTML-like table with a header row, filled with the
+/// `headercolor` of the table if set, followed by one row per column. Primary key columns are
+/// underlined. Table groups become `cluster_*` subgraphs holding the nodes of their tables. Each
+/// ref becomes one edge per pair of columns, connecting the ports of the column rows, with
+/// crow's-foot arrows telling the cardinality on each side: a crow for many, a bar for one, and a
+/// circle next to it if the side is optional since the referencing columns can be null.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::dot;
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id int [pk] }").unwrap();
+/// let graph = dot::generate(&ast).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock) -> Result<String, Vec<Diagnostic>> {
+  let (analyzed_indexer, diags) = analyze_all(schema_block);
+
+  if diags.iter().any(|diag| diag.severity == Severity::Error) {
+    return Err(diags);
+  }
+
+  let tables = schema_block.tables();
+  let mut out = String::from("digraph dbml {\n");
+  out += "  graph [rankdir=LR];\n";
+  out += "  node [shape=plain, fontname=\"Helvetica\"];\n";
+  out += "  edge [fontname=\"Helvetica\"];\n";
+
+  // a table listed in several table groups is drawn in the first one
+  let mut grouped_tables: Vec<QualifiedName> = vec![];
+  for table_group in schema_block.table_groups() {
+    let _ = writeln!(
+      out,
+      "\n  subgraph {} {{\n    label={};",
+      id(&format!("cluster_{}", table_group.ident.to_string)),
+      id(&table_group.ident.to_string)
+    );
+
+    for name in group_items(table_group, &analyzed_indexer) {
+      let table = tables
+        .iter()
+        .find(|table| QualifiedName::new(&table.ident.schema, &table.ident.name) == name);
+
+      if let Some(table) = table.filter(|_| !grouped_tables.contains(&name)) {
+        out += &node(table, "    ");
+        grouped_tables.push(name);
+      }
+    }
+
+    out += "  }\n";
+  }
+
+  for table in &tables {
+    if !grouped_tables.contains(&QualifiedName::new(&table.ident.schema, &table.ident.name)) {
+      out.push('\n');
+      out += &node(table, "  ");
+    }
+  }
+
+  let mut edges = String::new();
+  for indexed_ref in &analyzed_indexer.indexed_refs {
+    let lhs = analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.lhs);
+    let rhs = analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.rhs);
+    let one = |is_required| if is_required { "teetee" } else { "teeodot" };
+    let referencing = if indexed_ref.rel == Relation::One2One {
+      "teeodot"
+    } else {
+      "crowodot"
+    };
+
+    let (tail, head) = match Model::referencing_side(&indexed_ref.rel) {
+      Some(Side::Lhs) => (referencing, one(is_required_side(&tables, &lhs))),
+      Some(Side::Rhs) => (one(is_required_side(&tables, &rhs)), referencing),
+      None if indexed_ref.rel == Relation::Many2Many => ("crowodot", "crowodot"),
+      None => continue,
+    };
+
+    for (lhs_col, rhs_col) in lhs.compositions.iter().zip(&rhs.compositions) {
+      let _ = writeln!(
+        edges,
+        "  {}:{} -> {}:{} [dir=both, arrowtail={}, arrowhead={}];",
+        id(&node_name(&lhs.schema, &lhs.table)),
+        id(&lhs_col.to_string),
+        id(&node_name(&rhs.schema, &rhs.table)),
+        id(&rhs_col.to_string),
+        tail,
+        head
+      );
+    }
+  }
+  if !edges.is_empty() {
+    out.push('\n');
+    out += &edges;
+  }
+
+  out += "}\n";
+
+  Ok(out)
+}
+
+/// Writes the node of the table, with a port named after each column.
+fn node(table: &TableBlock, indent: &str) -> String {
+  let pk = primary_key(table);
+  let header_color = table
+    .settings
+    .iter()
+    .flat_map(|settings| settings.attributes.iter())
+    .find(|attr| attr.key.to_string == "headercolor")
+    .and_then(|attr| {
+      match attr.value.as_ref().map(|literal| &literal.value) {
+        Some(Value::HexColor(color)) => Some(color.as_str()),
+        _ => None,
+      }
+    })
+    // colors may come from other sources than the parser, such as the JSON model
+    .filter(|color| rgb(color).is_some())
+    .unwrap_or(DEFAULT_HEADER_COLOR);
+  let font_color = if is_dark(header_color) { "#FFFFFF" } else { "#000000" };
+
+  let mut out = String::new();
+  let _ = writeln!(
+    out,
+    "{}{} [label=<",
+    indent,
+    id(&node_name(&table.ident.schema, &table.ident.name))
+  );
+  let _ = writeln!(
+    out,
+    "{}  <table border=\"0\" cellborder=\"1\" cellspacing=\"0\" cellpadding=\"4\">",
+    indent
+  );
+  let _ = writeln!(
+    out,
+    "{}    <tr><td bgcolor=\"{}\"><font color=\"{}\"><b>{}</b></font></td></tr>",
+    indent,
+    header_color,
+    font_color,
+    html(&node_name(&table.ident.schema, &table.ident.name))
+  );
+
+  for col in &table.cols {
+    let col_name = &col.name.to_string;
+    let name = match pk.contains(&col_name.as_str()) {
+      true => format!("<u>{}</u>", html(col_name)),
+      false => html(col_name),
+    };
+
+    let _ = writeln!(
+      out,
+      "{}    <tr><td port=\"{}\" align=\"left\">{} <i>{}</i></td></tr>",
+      indent,
+      html(col_name),
+      name,
+      html(col.r#type.to_string().trim_matches('"'))
+    );
+  }
+
+  let _ = writeln!(out, "{}  </table>", indent);
+  let _ = writeln!(out, "{}>];", indent);
+
+  out
+}
+
+/// Gets the name of the node of a table, prefixed with the schema name if not the default one.
+fn node_name(schema: &Option<Ident>, name: &Ident) -> String {
+  let name = QualifiedName::new(schema, name);
+
+  match name.schema {
+    Some(schema) => format!("{}.{}", schema, name.name),
+    None => name.name,
+  }
+}
+
+/// Checks if a hex color is dark enough to be written on in white, by its perceived brightness.
+fn is_dark(color: &str) -> bool {
+  rgb(color).is_some_and(|[r, g, b]| r * 299 + g * 587 + b * 114 < 128_000)
+}
+
+/// Parses a `#RGB` or `#RRGGBB` color into its channels.
+fn rgb(color: &str) -> Option<[u32; 3]> {
+  let hex = color.strip_prefix('#')?;
+  let width = match hex.len() {
+    3 => 1,
+    6 => 2,
+    _ => return None,
+  };
+
+  if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None;
+  }
+
+  let mut channels = [0; 3];
+  for (i, channel) in channels.iter_mut().enumerate() {
+    let value = u32::from_str_radix(&hex[i * width..(i + 1) * width], 16).ok()?;
+    *channel = if width == 1 { value * 17 } else { value };
+  }
+
+  Some(channels)
+}
+
+/// Writes a double-quoted DOT identifier.
+fn id(text: &str) -> String {
+  format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Escapes the text for an HTML-like label.
+fn html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
@@ -0,0 +1,58 @@
+use alloc::string::String;
+
+use derive_more::Display;
+
+/// Represents a schema element that the target database of a generator cannot express.
+#[derive(Debug, PartialEq, Eq, Clone, Display)]
+pub enum Err {
+  #[display(
+    fmt = "Unsupported index type: '{}' indexes are not supported by {}",
+    r#type,
+    dialect
+  )]
+  UnsupportedIndexType { r#type: String, dialect: &'static str },
+  #[display(
+    fmt = "Unsupported expression index: expression indexes are not supported by {}",
+    dialect
+  )]
+  UnsupportedExpressionIndex { dialect: &'static str },
+  #[display(fmt = "Unsupported schema: schemas are not supported by {}", dialect)]
+  UnsupportedSchema { dialect: &'static str },
+  #[display(
+    fmt = "Identifier too long: '{}' exceeds the maximum length of {} bytes in {}",
+    ident,
+    max_len,
+    dialect
+  )]
+  IdentifierTooLong {
+    ident: String,
+    max_len: usize,
+    dialect: &'static str,
+  },
+}
+
+impl Err {
+  /// Returns the stable code of the error.
+  ///
+  /// Generator codes start with `G` so that they never collide with the `E` codes of the analyzer.
+  pub fn code(&self) -> &'static str {
+    match self {
+      Self::UnsupportedIndexType { .. } => "G0001",
+      Self::UnsupportedExpressionIndex { .. } => "G0002",
+      Self::UnsupportedSchema { .. } => "G0003",
+      Self::IdentifierTooLong { .. } => "G0004",
+    }
+  }
+
+  /// Returns a suggestion on how to fix the error.
+  pub fn help(&self) -> &'static str {
+    match self {
+      Self::UnsupportedIndexType { .. } => "remove the 'type' setting to use the default index type",
+      Self::UnsupportedExpressionIndex { .. } => "index a computed column holding the expression instead",
+      Self::UnsupportedSchema { .. } => {
+        "move the table into the default schema or prefix its name with the schema name"
+      }
+      Self::IdentifierTooLong { .. } => "shorten the name to fit the limit of the database",
+    }
+  }
+}
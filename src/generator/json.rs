@@ -0,0 +1,319 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+
+use serde_json::{
+  json,
+  Value as Json,
+};
+
+use super::*;
+
+/// Represents the tables and other elements of a schema in the JSON model.
+struct SchemaModel {
+  name: String,
+  tables: Vec<Json>,
+  enums: Vec<Json>,
+  table_groups: Vec<Json>,
+  refs: Vec<Json>,
+}
+
+/// Generates the JSON database model exchanged by `@dbml/core`, as exported by its
+/// `ModelExporter`.
+///
+/// The schema is semantically checked first. Elements are grouped by schema, with the default
+/// schema always listed first. Inline refs are exported as refs of their own, and table aliases
+/// are resolved in refs and table groups.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::json;
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id int [pk] }").unwrap();
+/// let json = json::generate(&ast).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock) -> Result<String, Vec<Diagnostic>> {
+  generate_value(schema_block).map(|json| format!("{:#}\n", json))
+}
+
+/// Generates the JSON database model as a value rather than a string.
+///
+/// See [`generate`] for details.
+pub fn generate_value(schema_block: &SchemaBlock) -> Result<Json, Vec<Diagnostic>> {
+  let (analyzed_indexer, diags) = analyze_all(schema_block);
+
+  if diags.iter().any(|diag| diag.severity == Severity::Error) {
+    return Err(diags);
+  }
+
+  let indexer = &analyzed_indexer.indexer;
+  let mut schemas = vec![SchemaModel::new(DEFAULT_SCHEMA)];
+
+  for table in schema_block.tables() {
+    SchemaModel::find_or_push(&mut schemas, &table.ident.schema)
+      .tables
+      .push(table_model(table));
+  }
+  for r#enum in schema_block.enums() {
+    SchemaModel::find_or_push(&mut schemas, &r#enum.ident.schema)
+      .enums
+      .push(enum_model(r#enum));
+  }
+  // table groups and refs are not qualified with a schema name in DBML
+  for table_group in schema_block.table_groups() {
+    let tables: Vec<_> = table_group
+      .items
+      .iter()
+      .map(|item| {
+        let (schema, table) = match (&item.schema, indexer.resolve_alias(&item.ident_alias.to_string)) {
+          (None, Some((schema, table))) => (Some(schema.as_str()), table.as_str()),
+          (schema, _) => {
+            (
+              schema.as_ref().map(|schema| schema.to_string.as_str()),
+              item.ident_alias.to_string.as_str(),
+            )
+          }
+        };
+
+        json!({ "tableName": table, "schemaName": schema_name(schema) })
+      })
+      .collect();
+
+    schemas[0].table_groups.push(json!({
+      "name": table_group.ident.to_string,
+      "tables": tables,
+    }));
+  }
+  for indexed_ref in &analyzed_indexer.indexed_refs {
+    let relations = match indexed_ref.rel {
+      Relation::One2Many => ("1", "*"),
+      Relation::Many2One => ("*", "1"),
+      Relation::One2One => ("1", "1"),
+      Relation::Many2Many => ("*", "*"),
+      Relation::Undef => continue,
+    };
+    let settings = indexed_ref.settings.as_ref();
+
+    schemas[0].refs.push(json!({
+      "name": indexed_ref.name.as_ref().map(|name| &name.to_string),
+      "endpoints": [
+        endpoint(&indexer.resolve_ref_alias(&indexed_ref.lhs), relations.0),
+        endpoint(&indexer.resolve_ref_alias(&indexed_ref.rhs), relations.1),
+      ],
+      "onDelete": settings.and_then(|settings| settings.on_delete.as_ref()).map(ToString::to_string),
+      "onUpdate": settings.and_then(|settings| settings.on_update.as_ref()).map(ToString::to_string),
+    }));
+  }
+
+  let project = schema_block.project().into_iter().next();
+  let database_type = project.and_then(|project| {
+    match project.database_type {
+      DatabaseType::Undef => None,
+      ref database_type => Some(format!("{:?}", database_type)),
+    }
+  });
+
+  Ok(json!({
+    "name": project.map(|project| &project.ident.to_string),
+    "databaseType": database_type,
+    "note": project.and_then(|project| project.note.as_ref()).map(note),
+    "schemas": schemas.into_iter().map(SchemaModel::into_json).collect::<Vec<_>>(),
+  }))
+}
+
+impl SchemaModel {
+  fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      tables: vec![],
+      enums: vec![],
+      table_groups: vec![],
+      refs: vec![],
+    }
+  }
+
+  fn find_or_push<'a>(schemas: &'a mut Vec<Self>, schema: &Option<Ident>) -> &'a mut Self {
+    let name = schema
+      .as_ref()
+      .map_or(DEFAULT_SCHEMA, |schema| schema.to_string.as_str());
+
+    match schemas.iter().position(|schema| schema.name == name) {
+      Some(i) => &mut schemas[i],
+      None => {
+        schemas.push(Self::new(name));
+        schemas.last_mut().unwrap()
+      }
+    }
+  }
+
+  fn into_json(self) -> Json {
+    json!({
+      "name": self.name,
+      "note": null,
+      "alias": null,
+      "tables": self.tables,
+      "enums": self.enums,
+      "tableGroups": self.table_groups,
+      "refs": self.refs,
+    })
+  }
+}
+
+fn table_model(table: &TableBlock) -> Json {
+  let header_color = table
+    .settings
+    .iter()
+    .flat_map(|settings| settings.attributes.iter())
+    .find(|attr| attr.key.to_string == "headercolor")
+    .and_then(|attr| attr.value.as_ref())
+    .map(|literal| literal.value.to_string());
+
+  json!({
+    "name": table.ident.name.to_string,
+    "alias": table.ident.alias.as_ref().map(|alias| &alias.to_string),
+    "note": table.note.as_ref().map(note),
+    "headerColor": header_color,
+    "fields": table.cols.iter().map(field).collect::<Vec<_>>(),
+    "indexes": table
+      .indexes
+      .iter()
+      .flat_map(|indexes| indexes.defs.iter())
+      .map(index)
+      .collect::<Vec<_>>(),
+  })
+}
+
+fn field(col: &TableColumn) -> Json {
+  let settings = col.settings.as_ref();
+
+  json!({
+    "name": col.name.to_string,
+    "type": field_type(&col.r#type),
+    "pk": settings.is_some_and(|settings| settings.is_pk),
+    "unique": settings.is_some_and(|settings| settings.is_unique),
+    "increment": settings.is_some_and(|settings| settings.is_incremental),
+    "not_null": settings.is_some_and(|settings| settings.nullable == Some(Nullable::NotNull)),
+    "note": settings.and_then(|settings| settings.note.clone()),
+    "dbdefault": settings.and_then(|settings| settings.default.as_ref()).map(dbdefault),
+  })
+}
+
+/// Splits the schema name of an enum type from the type name, which keeps the arguments and
+/// array dimensions as written.
+fn field_type(col_type: &ColumnType) -> Json {
+  let type_name = col_type.to_string().trim_matches('"').to_string();
+  let schema_name = match &col_type.type_name {
+    ColumnTypeName::Raw(name) | ColumnTypeName::Enum(name) => name.split_once('.').map(|(schema, _)| schema),
+    _ => None,
+  };
+  let type_name = match schema_name {
+    Some(schema) => type_name[schema.len() + 1..].to_string(),
+    None => type_name,
+  };
+  let args = match col_type.args.is_empty() {
+    true => None,
+    false => {
+      Some(
+        col_type
+          .args
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<_>>()
+          .join(","),
+      )
+    }
+  };
+
+  json!({
+    "schemaName": schema_name,
+    "type_name": type_name,
+    "args": args,
+  })
+}
+
+fn dbdefault(value: &Value) -> Json {
+  match value {
+    Value::Integer(int) => json!({ "type": "number", "value": int }),
+    Value::Decimal(decimal) => json!({ "type": "number", "value": decimal }),
+    Value::Bool(bool) => json!({ "type": "boolean", "value": bool.to_string() }),
+    Value::Null => json!({ "type": "boolean", "value": "null" }),
+    Value::Expr(expr) => json!({ "type": "expression", "value": expr }),
+    Value::Enum(s) | Value::String(s) | Value::HexColor(s) => json!({ "type": "string", "value": s }),
+  }
+}
+
+fn index(def: &IndexesDef) -> Json {
+  let settings = def.settings.as_ref();
+  let columns: Vec<_> = def
+    .cols
+    .iter()
+    .map(|col| {
+      match col {
+        IndexesColumnType::String(ident) => json!({ "type": "column", "value": ident.to_string }),
+        IndexesColumnType::Expr(literal) => json!({ "type": "expression", "value": literal.value.to_string() }),
+      }
+    })
+    .collect();
+
+  json!({
+    "columns": columns,
+    "name": settings.and_then(|settings| settings.name.clone()),
+    "type": settings.and_then(|settings| settings.r#type.as_ref()).map(ToString::to_string),
+    "unique": settings.is_some_and(|settings| settings.is_unique),
+    "pk": settings.is_some_and(|settings| settings.is_pk),
+    "note": settings.and_then(|settings| settings.note.clone()),
+  })
+}
+
+fn enum_model(r#enum: &EnumBlock) -> Json {
+  let values: Vec<_> = r#enum
+    .values
+    .iter()
+    .map(|value| {
+      json!({
+        "name": value.value.to_string,
+        "note": value.settings.as_ref().and_then(|settings| settings.note.clone()),
+      })
+    })
+    .collect();
+
+  json!({
+    "name": r#enum.ident.name.to_string,
+    "note": null,
+    "values": values,
+  })
+}
+
+fn endpoint(ref_ident: &RefIdent, relation: &str) -> Json {
+  json!({
+    "schemaName": schema_name(ref_ident.schema.as_ref().map(|schema| schema.to_string.as_str())),
+    "tableName": ref_ident.table.to_string,
+    "fieldNames": ref_ident
+      .compositions
+      .iter()
+      .map(|col| &col.to_string)
+      .collect::<Vec<_>>(),
+    "relation": relation,
+  })
+}
+
+/// Leaves out the default schema name, as `@dbml/core` does for unqualified names.
+fn schema_name(schema: Option<&str>) -> Option<&str> {
+  schema.filter(|schema| schema != &DEFAULT_SCHEMA)
+}
+
+fn note(note: &NoteBlock) -> String {
+  note.value.value.to_string()
+}
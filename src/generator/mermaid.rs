@@ -0,0 +1,228 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::*;
+
+/// Represents the options for generating Mermaid diagrams.
+#[derive(Debug, Clone, Default)]
+pub struct MermaidOptions {
+  /// The name of the table group whose tables are rendered, or `None` to render all tables.
+  pub table_group: Option<String>,
+}
+
+/// Generates a Mermaid `erDiagram` from the schema.
+///
+/// Each table becomes an entity listing its columns with their types, marked `PK` for primary
+/// key columns, `FK` for referencing columns and `UK` for columns unique on their own, either by
+/// their settings or by an index. Notes of the columns become attribute comments. Each ref becomes a
+/// relationship, where the side referenced by a foreign key is exactly one if the referencing
+/// columns are not null and zero or one otherwise. Many-to-many refs are drawn as they are, without
+/// a junction table.
+///
+/// Characters not allowed by Mermaid in attribute types and names, such as spaces and commas, are
+/// replaced with underscores. Entity names are quoted if needed.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+/// * `options` - A reference to the generating options.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error, or a `TableGroupNotFound` error if
+/// the table group of the options does not exist.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::mermaid::{generate, MermaidOptions};
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id int [pk] }").unwrap();
+/// let diagram = generate(&ast, &MermaidOptions::default()).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock, options: &MermaidOptions) -> Result<String, Vec<Diagnostic>> {
+  let (analyzed_indexer, diags) = analyze_all(schema_block);
+
+  if diags.iter().any(|diag| diag.severity == Severity::Error) {
+    return Err(diags);
+  }
+
+  let group_tables = match &options.table_group {
+    Some(name) => Some(table_group_tables(schema_block, &analyzed_indexer, name)?),
+    None => None,
+  };
+  let is_rendered = |name: &QualifiedName| {
+    match &group_tables {
+      Some(group_tables) => group_tables.contains(name),
+      None => true,
+    }
+  };
+
+  let tables: Vec<_> = schema_block
+    .tables()
+    .into_iter()
+    .filter(|table| is_rendered(&QualifiedName::new(&table.ident.schema, &table.ident.name)))
+    .collect();
+  let refs: Vec<_> = analyzed_indexer
+    .indexed_refs
+    .iter()
+    .map(|indexed_ref| {
+      (
+        indexed_ref,
+        analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.lhs),
+        analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.rhs),
+      )
+    })
+    .filter(|(_, lhs, rhs)| {
+      is_rendered(&QualifiedName::new(&lhs.schema, &lhs.table))
+        && is_rendered(&QualifiedName::new(&rhs.schema, &rhs.table))
+    })
+    .collect();
+
+  // the referencing side of each foreign key
+  let foreign_keys: Vec<_> = refs
+    .iter()
+    .filter_map(|(indexed_ref, lhs, rhs)| Model::referencing_side(&indexed_ref.rel).map(|side| side.of(lhs, rhs).0))
+    .collect();
+
+  let mut out = String::from("erDiagram\n");
+
+  for table in &tables {
+    let table_name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+    let pk = primary_key(table);
+
+    let _ = writeln!(out, "  {} {{", entity(&table_name));
+    for col in &table.cols {
+      let col_name = &col.name.to_string;
+      let mut keys = vec![];
+
+      if pk.contains(&col_name.as_str()) {
+        keys.push("PK");
+      }
+      if foreign_keys.iter().any(|foreign_key| {
+        QualifiedName::new(&foreign_key.schema, &foreign_key.table) == table_name
+          && foreign_key
+            .compositions
+            .iter()
+            .any(|ident| &ident.to_string == col_name)
+      }) {
+        keys.push("FK");
+      }
+      if is_unique(table, col) {
+        keys.push("UK");
+      }
+
+      let _ = write!(out, "    {} {}", attribute_type(&col.r#type), sanitize(col_name));
+      if !keys.is_empty() {
+        let _ = write!(out, " {}", keys.join(", "));
+      }
+      if let Some(note) = col.settings.as_ref().and_then(|settings| settings.note.as_ref()) {
+        let _ = write!(out, " {}", comment(note));
+      }
+      out.push('\n');
+    }
+    out += "  }\n";
+  }
+
+  for (indexed_ref, lhs, rhs) in &refs {
+    let is_required = |ref_ident| is_required_side(&tables, ref_ident);
+
+    let referencing_side = Model::referencing_side(&indexed_ref.rel);
+    let is_one = indexed_ref.rel == Relation::One2One;
+
+    let markers = match referencing_side {
+      Some(Side::Lhs) => {
+        (
+          if is_one { "|o" } else { "}o" },
+          if is_required(lhs) { "||" } else { "o|" },
+        )
+      }
+      Some(Side::Rhs) => {
+        (
+          if is_required(rhs) { "||" } else { "|o" },
+          if is_one { "o|" } else { "o{" },
+        )
+      }
+      None if indexed_ref.rel == Relation::Many2Many => ("}o", "o{"),
+      None => continue,
+    };
+    let label = match &indexed_ref.name {
+      Some(name) => name.to_string.clone(),
+      None => {
+        referencing_side
+          .map_or(lhs, |side| side.of(lhs, rhs).0)
+          .compositions
+          .iter()
+          .map(|ident| ident.to_string.as_str())
+          .collect::<Vec<_>>()
+          .join(", ")
+      }
+    };
+
+    let _ = writeln!(
+      out,
+      "  {} {}--{} {} : {}",
+      entity(&QualifiedName::new(&lhs.schema, &lhs.table)),
+      markers.0,
+      markers.1,
+      entity(&QualifiedName::new(&rhs.schema, &rhs.table)),
+      comment(&label)
+    );
+  }
+
+  Ok(out)
+}
+
+/// Checks if the column is unique on its own, either by its settings or by a single-column index.
+fn is_unique(table: &TableBlock, col: &TableColumn) -> bool {
+  col.settings.as_ref().is_some_and(|settings| settings.is_unique)
+    || indexes(table).any(|def| {
+      def.settings.as_ref().is_some_and(|settings| settings.is_unique)
+        && match def.cols.as_slice() {
+          [IndexesColumnType::String(ident)] => ident.to_string == col.name.to_string,
+          _ => false,
+        }
+    })
+}
+
+/// Writes the entity name, quoted if it has characters other than letters, digits, hyphens and
+/// underscores.
+fn entity(name: &QualifiedName) -> String {
+  let text = match &name.schema {
+    Some(schema) => format!("{}.{}", schema, name.name),
+    None => name.name.clone(),
+  };
+
+  match text.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+    true => text,
+    false => format!("\"{}\"", text.replace('"', "'")),
+  }
+}
+
+/// Writes the column type without the quotes required by DBML.
+fn attribute_type(r#type: &ColumnType) -> String {
+  sanitize(r#type.to_string().trim_matches('"').replace(", ", ",").as_str())
+}
+
+/// Replaces the characters not allowed in attribute types and names with underscores.
+fn sanitize(text: &str) -> String {
+  text
+    .chars()
+    .map(|c| {
+      match c.is_alphanumeric() || matches!(c, '-' | '_' | '(' | ')' | '[' | ']') {
+        true => c,
+        false => '_',
+      }
+    })
+    .collect()
+}
+
+/// Writes the text as a double-quoted string, which cannot contain double quotes or line breaks.
+fn comment(text: &str) -> String {
+  format!("\"{}\"", text.replace('"', "'").replace('\n', " "))
+}
@@ -0,0 +1,515 @@
+use alloc::collections::BTreeSet;
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::str::FromStr;
+
+use crate::analyzer::*;
+use crate::ast::*;
+use crate::diagnostic::{
+  Diagnostic,
+  Severity,
+};
+use crate::DEFAULT_SCHEMA;
+
+pub mod dot;
+pub mod err;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod mermaid;
+pub mod mssql;
+pub mod mysql;
+pub mod oracle;
+pub mod postgres;
+pub mod sqlite;
+
+/// Represents a table or enum name qualified with its schema name.
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
+pub(crate) struct QualifiedName {
+  /// The schema name, or `None` for the default schema.
+  pub schema: Option<String>,
+  pub name: String,
+}
+
+impl QualifiedName {
+  pub fn new(schema: &Option<Ident>, name: &Ident) -> Self {
+    Self {
+      schema: schema
+        .as_ref()
+        .map(|schema| schema.to_string.clone())
+        .filter(|schema| schema != DEFAULT_SCHEMA),
+      name: name.to_string.clone(),
+    }
+  }
+}
+
+/// Represents a column type resolved against the enums of the schema.
+#[derive(Debug, Clone)]
+pub(crate) enum ColumnKind<'a> {
+  /// A built-in data type.
+  Builtin(ColumnTypeName),
+  /// A type naming an enum of the schema.
+  Enum(&'a EnumBlock),
+  /// Any other type, which is passed through as written.
+  Custom(&'a str),
+}
+
+/// Represents a foreign key normalized from a ref, pointing from the referencing columns to the
+/// referenced columns.
+#[derive(Debug, Clone)]
+pub(crate) struct ForeignKey {
+  pub name: Option<String>,
+  pub from: QualifiedName,
+  pub from_cols: Vec<String>,
+  pub to: QualifiedName,
+  pub to_cols: Vec<String>,
+  pub on_delete: Option<ReferentialAction>,
+  pub on_update: Option<ReferentialAction>,
+  /// The span of the ref the foreign key is normalized from.
+  pub span_range: SpanRange,
+}
+
+/// Represents a side of a ref.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+  Lhs,
+  Rhs,
+}
+
+impl Side {
+  /// Orders both sides of a ref as the side this one stands for and the other side.
+  pub fn of<'r>(self, lhs: &'r RefIdent, rhs: &'r RefIdent) -> (&'r RefIdent, &'r RefIdent) {
+    match self {
+      Self::Lhs => (lhs, rhs),
+      Self::Rhs => (rhs, lhs),
+    }
+  }
+}
+
+/// Represents a table joining both sides of a many-to-many ref.
+#[derive(Debug, Clone)]
+pub(crate) struct JunctionTable<'a> {
+  pub name: QualifiedName,
+  /// The columns paired with the referenced columns they copy the type from.
+  pub cols: Vec<(String, &'a TableColumn)>,
+  /// The span of the many-to-many ref.
+  pub span_range: SpanRange,
+}
+
+/// Represents the semantically checked schema prepared for generating data definitions.
+#[derive(Debug, Clone)]
+pub(crate) struct Model<'a> {
+  pub tables: Vec<&'a TableBlock>,
+  pub enums: Vec<&'a EnumBlock>,
+  pub junction_tables: Vec<JunctionTable<'a>>,
+  pub foreign_keys: Vec<ForeignKey>,
+}
+
+impl<'a> Model<'a> {
+  /// Analyzes the schema and normalizes its refs into foreign keys.
+  ///
+  /// Many-to-many refs are turned into junction tables with a foreign key to each side.
+  ///
+  /// # Errors
+  ///
+  /// All diagnostics of the schema if any of them is an error.
+  pub fn new(schema_block: &'a SchemaBlock) -> Result<Self, Vec<Diagnostic>> {
+    let (analyzed_indexer, diags) = analyze_all(schema_block);
+
+    if diags.iter().any(|diag| diag.severity == Severity::Error) {
+      return Err(diags);
+    }
+
+    let mut model = Self {
+      tables: schema_block.tables(),
+      enums: schema_block.enums(),
+      junction_tables: vec![],
+      foreign_keys: vec![],
+    };
+
+    for indexed_ref in &analyzed_indexer.indexed_refs {
+      let lhs = analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.lhs);
+      let rhs = analyzed_indexer.indexer.resolve_ref_alias(&indexed_ref.rhs);
+      let settings = indexed_ref.settings.as_ref();
+      let on_delete = settings.and_then(|settings| settings.on_delete.clone());
+      let on_update = settings.and_then(|settings| settings.on_update.clone());
+      let foreign_key = |from: &RefIdent, to: &RefIdent| {
+        ForeignKey {
+          name: indexed_ref.name.as_ref().map(|name| name.to_string.clone()),
+          from: QualifiedName::new(&from.schema, &from.table),
+          from_cols: cols_of(from),
+          to: QualifiedName::new(&to.schema, &to.table),
+          to_cols: cols_of(to),
+          on_delete: on_delete.clone(),
+          on_update: on_update.clone(),
+          span_range: indexed_ref.span_range.clone(),
+        }
+      };
+
+      match Self::referencing_side(&indexed_ref.rel) {
+        Some(side) => {
+          let (from, to) = side.of(&lhs, &rhs);
+          model.foreign_keys.push(foreign_key(from, to))
+        }
+        None if indexed_ref.rel == Relation::Many2Many => {
+          model.push_junction_table(&lhs, &rhs, &on_delete, &on_update, &indexed_ref.span_range)
+        }
+        None => (),
+      }
+    }
+
+    Ok(model)
+  }
+
+  /// Finds the side of a ref holding the foreign key, or `None` if the relation is many-to-many or
+  /// undefined.
+  ///
+  /// As in `@dbml/core`, the foreign key of a one-to-one ref is on its right-hand side.
+  pub fn referencing_side(rel: &Relation) -> Option<Side> {
+    match rel {
+      Relation::Many2One => Some(Side::Lhs),
+      Relation::One2Many | Relation::One2One => Some(Side::Rhs),
+      Relation::Many2Many | Relation::Undef => None,
+    }
+  }
+
+  fn push_junction_table(
+    &mut self,
+    lhs: &RefIdent,
+    rhs: &RefIdent,
+    on_delete: &Option<ReferentialAction>,
+    on_update: &Option<ReferentialAction>,
+    span_range: &SpanRange,
+  ) {
+    let lhs_name = QualifiedName::new(&lhs.schema, &lhs.table);
+    let rhs_name = QualifiedName::new(&rhs.schema, &rhs.table);
+    let name = QualifiedName {
+      schema: lhs_name.schema.clone(),
+      name: format!("{}_{}", lhs_name.name, rhs_name.name),
+    };
+
+    let mut cols = vec![];
+    for (table_name, ref_ident) in [(&lhs_name, lhs), (&rhs_name, rhs)] {
+      let from_cols: Vec<_> = ref_ident
+        .compositions
+        .iter()
+        .filter_map(|col| self.column(table_name, &col.to_string))
+        .map(|col| {
+          let mut col_name = format!("{}_{}", table_name.name, col.name.to_string);
+          // both sides of a self-referencing ref would get the same column names
+          if cols.iter().any(|(name, _)| name == &col_name) {
+            col_name += "_2";
+          }
+
+          (col_name, col)
+        })
+        .collect();
+
+      self.foreign_keys.push(ForeignKey {
+        name: None,
+        from: name.clone(),
+        from_cols: from_cols.iter().map(|(col_name, _)| col_name.clone()).collect(),
+        to: table_name.clone(),
+        to_cols: cols_of(ref_ident),
+        on_delete: on_delete.clone(),
+        on_update: on_update.clone(),
+        span_range: span_range.clone(),
+      });
+      cols.extend(from_cols);
+    }
+
+    self.junction_tables.push(JunctionTable {
+      name,
+      cols,
+      span_range: span_range.clone(),
+    });
+  }
+
+  /// Collects the non-default schema names of all tables and enums.
+  pub fn schemas(&self) -> BTreeSet<&str> {
+    let table_schemas = self.tables.iter().map(|table| &table.ident.schema);
+    let enum_schemas = self.enums.iter().map(|r#enum| &r#enum.ident.schema);
+
+    table_schemas
+      .chain(enum_schemas)
+      .filter_map(|schema| schema.as_ref())
+      .map(|schema| schema.to_string.as_str())
+      .filter(|schema| schema != &DEFAULT_SCHEMA)
+      .collect()
+  }
+
+  /// Finds the column of the table with the given name.
+  pub fn column(&self, table: &QualifiedName, name: &str) -> Option<&'a TableColumn> {
+    self
+      .tables
+      .iter()
+      .find(|table_block| &QualifiedName::new(&table_block.ident.schema, &table_block.ident.name) == table)
+      .and_then(|table_block| table_block.cols.iter().find(|col| col.name.to_string == name))
+  }
+
+  /// Resolves the column type into a built-in type, an enum of the schema or a custom type.
+  pub fn column_kind(&self, col: &'a TableColumn) -> ColumnKind<'a> {
+    let raw = match &col.r#type.type_name {
+      ColumnTypeName::Raw(raw) | ColumnTypeName::Enum(raw) => raw,
+      type_name => return ColumnKind::Builtin(type_name.clone()),
+    };
+
+    if let Ok(type_name) = ColumnTypeName::from_str(&raw.to_lowercase()) {
+      return ColumnKind::Builtin(type_name);
+    }
+
+    let (schema, name) = match raw.split_once('.') {
+      Some((schema, name)) => (schema, name),
+      None => (DEFAULT_SCHEMA, raw.as_str()),
+    };
+
+    self
+      .enums
+      .iter()
+      .find(|r#enum| {
+        r#enum.ident.name.to_string == name
+          && r#enum
+            .ident
+            .schema
+            .as_ref()
+            .map_or(DEFAULT_SCHEMA, |s| s.to_string.as_str())
+            == schema
+      })
+      .map(|r#enum| ColumnKind::Enum(r#enum))
+      .unwrap_or(ColumnKind::Custom(raw))
+  }
+
+  /// Checks if the column is auto-incremented by its PostgreSQL serial type.
+  pub fn is_serial(&self, col: &'a TableColumn) -> bool {
+    matches!(
+      self.column_kind(col),
+      ColumnKind::Builtin(ColumnTypeName::SmallSerial | ColumnTypeName::Serial | ColumnTypeName::BigSerial)
+    )
+  }
+}
+
+/// Gets the primary key column names of the table, either from column settings or an index,
+/// without duplicates.
+pub(crate) fn primary_key(table: &TableBlock) -> Vec<&str> {
+  let col_pk = table
+    .cols
+    .iter()
+    .filter(|col| col.settings.as_ref().is_some_and(|settings| settings.is_pk))
+    .map(|col| col.name.to_string.as_str());
+  let index_pk = table
+    .indexes
+    .iter()
+    .flat_map(|indexes| indexes.defs.iter())
+    .filter(|def| def.settings.as_ref().is_some_and(|settings| settings.is_pk))
+    .flat_map(|def| def.cols.iter())
+    .filter_map(|col| {
+      match col {
+        IndexesColumnType::String(ident) => Some(ident.to_string.as_str()),
+        IndexesColumnType::Expr(_) => None,
+      }
+    });
+
+  let mut pk = vec![];
+  for name in col_pk.chain(index_pk) {
+    if !pk.contains(&name) {
+      pk.push(name);
+    }
+  }
+
+  pk
+}
+
+/// Gets the name of the column declaring the whole primary key by its own `pk` setting, which
+/// can then be written inline. Any other primary key must be written as a table constraint.
+pub(crate) fn inline_primary_key(table: &TableBlock) -> Option<&str> {
+  match primary_key(table).as_slice() {
+    [name] => {
+      table
+        .cols
+        .iter()
+        .find(|col| &col.name.to_string == name && col.settings.as_ref().is_some_and(|settings| settings.is_pk))
+        .map(|col| col.name.to_string.as_str())
+    }
+    _ => None,
+  }
+}
+
+/// Gets the index definitions of the table other than the primary key.
+pub(crate) fn indexes(table: &TableBlock) -> impl Iterator<Item = &IndexesDef> {
+  table
+    .indexes
+    .iter()
+    .flat_map(|indexes| indexes.defs.iter())
+    .filter(|def| !def.settings.as_ref().is_some_and(|settings| settings.is_pk))
+}
+
+/// Finds the tables listed in the table group with the given name, with table aliases resolved.
+///
+/// # Errors
+///
+/// A `TableGroupNotFound` error if the schema has no table group with the given name. Since the
+/// name does not come from the source text, the error points at the name of the project.
+pub(crate) fn table_group_tables(
+  schema_block: &SchemaBlock,
+  analyzed_indexer: &AnalyzedIndexer,
+  name: &str,
+) -> Result<Vec<QualifiedName>, Vec<Diagnostic>> {
+  let Some(table_group) = schema_block
+    .table_groups()
+    .into_iter()
+    .find(|table_group| table_group.ident.to_string == name)
+  else {
+    let span_range = schema_block
+      .project()
+      .first()
+      .map(|project| project.ident.span_range.clone())
+      .unwrap_or_default();
+
+    return Err(vec![Diagnostic::semantic(
+      crate::analyzer::err::Err::TableGroupNotFound,
+      &span_range,
+    )]);
+  };
+
+  Ok(group_items(table_group, analyzed_indexer))
+}
+
+/// Lists the tables of the table group, with table aliases resolved.
+pub(crate) fn group_items(table_group: &TableGroupBlock, analyzed_indexer: &AnalyzedIndexer) -> Vec<QualifiedName> {
+  table_group
+    .items
+    .iter()
+    .map(|item| {
+      match (
+        &item.schema,
+        analyzed_indexer.indexer.resolve_alias(&item.ident_alias.to_string),
+      ) {
+        (None, Some((schema, table))) => {
+          QualifiedName {
+            schema: Some(schema.clone()).filter(|schema| schema != DEFAULT_SCHEMA),
+            name: table.clone(),
+          }
+        }
+        (schema, _) => QualifiedName::new(schema, &item.ident_alias),
+      }
+    })
+    .collect()
+}
+
+/// Checks if the column cannot be null, either explicitly or by being part of the primary key.
+pub(crate) fn is_non_nullable(table: &TableBlock, col: &TableColumn) -> bool {
+  col
+    .settings
+    .as_ref()
+    .is_some_and(|settings| settings.nullable == Some(Nullable::NotNull))
+    || primary_key(table).contains(&col.name.to_string.as_str())
+}
+
+/// Checks if none of the columns on the side of a ref can be null, making the other side required.
+pub(crate) fn is_required_side(tables: &[&TableBlock], ref_ident: &RefIdent) -> bool {
+  let name = QualifiedName::new(&ref_ident.schema, &ref_ident.table);
+  let table = tables
+    .iter()
+    .find(|table| QualifiedName::new(&table.ident.schema, &table.ident.name) == name);
+
+  table.is_some_and(|table| {
+    ref_ident.compositions.iter().all(|ident| {
+      table
+        .cols
+        .iter()
+        .find(|col| col.name.to_string == ident.to_string)
+        .is_some_and(|col| is_non_nullable(table, col))
+    })
+  })
+}
+
+/// Finds the span of the setting with the given key, falling back to the span of all settings.
+pub(crate) fn setting_span<'a>(attributes: &'a [Attribute], key: &str, fallback: &'a SpanRange) -> &'a SpanRange {
+  attributes
+    .iter()
+    .find(|attr| attr.key.to_string == key)
+    .map_or(fallback, |attr| &attr.span_range)
+}
+
+/// Gets the text of a note.
+pub(crate) fn note_text(note: &NoteBlock) -> String {
+  match &note.value.value {
+    Value::String(text) => text.clone(),
+    value => value.to_string(),
+  }
+}
+
+/// Surrounds the text with the quote character, doubling the quote characters inside.
+pub(crate) fn quote(text: &str, open: char, close: char) -> String {
+  let mut out = String::with_capacity(text.len() + 2);
+
+  out.push(open);
+  for c in text.chars() {
+    if c == close {
+      out.push(close);
+    }
+    out.push(c);
+  }
+  out.push(close);
+
+  out
+}
+
+/// Writes an `ALTER TABLE ... ADD FOREIGN KEY` statement, quoting identifiers with `ident`.
+pub(crate) fn alter_table_foreign_key(foreign_key: &ForeignKey, ident: fn(&str) -> String) -> String {
+  format!(
+    "ALTER TABLE {} ADD {};\n",
+    qualified_ident(&foreign_key.from, ident),
+    foreign_key_constraint(foreign_key, ident)
+  )
+}
+
+/// Writes a `FOREIGN KEY ... REFERENCES` table constraint, quoting identifiers with `ident`.
+pub(crate) fn foreign_key_constraint(foreign_key: &ForeignKey, ident: fn(&str) -> String) -> String {
+  let mut out = String::new();
+
+  if let Some(name) = &foreign_key.name {
+    let _ = write!(out, "CONSTRAINT {} ", ident(name));
+  }
+
+  let _ = write!(
+    out,
+    "FOREIGN KEY ({}) REFERENCES {} ({})",
+    idents(&foreign_key.from_cols, ident),
+    qualified_ident(&foreign_key.to, ident),
+    idents(&foreign_key.to_cols, ident)
+  );
+
+  if let Some(action) = &foreign_key.on_delete {
+    let _ = write!(out, " ON DELETE {}", action.to_string().to_uppercase());
+  }
+  if let Some(action) = &foreign_key.on_update {
+    let _ = write!(out, " ON UPDATE {}", action.to_string().to_uppercase());
+  }
+
+  out
+}
+
+/// Joins the quoted identifiers with commas.
+pub(crate) fn idents<S: AsRef<str>>(names: impl IntoIterator<Item = S>, ident: fn(&str) -> String) -> String {
+  names
+    .into_iter()
+    .map(|name| ident(name.as_ref()))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Quotes the name prefixed with the schema name if specified.
+pub(crate) fn qualified_ident(name: &QualifiedName, ident: fn(&str) -> String) -> String {
+  match &name.schema {
+    Some(schema) => format!("{}.{}", ident(schema), ident(&name.name)),
+    None => ident(&name.name),
+  }
+}
+
+fn cols_of(ref_ident: &RefIdent) -> Vec<String> {
+  ref_ident.compositions.iter().map(|col| col.to_string.clone()).collect()
+}
@@ -0,0 +1,340 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::err::Err;
+use super::*;
+
+/// The dialect name shown in diagnostics.
+const DIALECT: &str = "SQL Server";
+
+/// The schema of objects created without a schema name.
+const DEFAULT_MSSQL_SCHEMA: &str = "dbo";
+
+/// Generates Microsoft SQL Server (T-SQL) data definition statements from the schema.
+///
+/// Identifiers are quoted with brackets and objects are qualified with the schema of their block.
+/// Enums are emulated with `CHECK (... IN (...))` constraints on `nvarchar` columns, and notes
+/// are attached with `sp_addextendedproperty` as `MS_Description` properties.
+/// Foreign keys are added with `ALTER TABLE` once all tables exist, followed by `CREATE INDEX`.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error, or an error for each index that
+/// SQL Server cannot express: `hash`, `gin` and `gist` indexes and expression indexes.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::mssql;
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'MSSQL' }\nTable users { id int [pk] }").unwrap();
+/// let sql = mssql::generate(&ast).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock) -> Result<String, Vec<Diagnostic>> {
+  let model = Model::new(schema_block)?;
+  let mut diags = vec![];
+  let mut sections = vec![];
+
+  // `CREATE SCHEMA` must be the only statement of its batch
+  sections.push(
+    model
+      .schemas()
+      .into_iter()
+      .map(|schema| format!("CREATE SCHEMA {};\nGO\n", ident(schema)))
+      .collect(),
+  );
+  sections.extend(model.tables.iter().map(|table| create_table(&model, table)));
+  sections.extend(
+    model
+      .junction_tables
+      .iter()
+      .map(|junction_table| create_junction_table(&model, junction_table)),
+  );
+  sections.push(
+    model
+      .foreign_keys
+      .iter()
+      .map(|foreign_key| {
+        // SQL Server has no `RESTRICT`, which behaves the same as `NO ACTION` anyway
+        let restrict_to_no_action = |action: &Option<ReferentialAction>| {
+          match action {
+            Some(ReferentialAction::Restrict) => Some(ReferentialAction::NoAction),
+            action => action.clone(),
+          }
+        };
+        let foreign_key = ForeignKey {
+          on_delete: restrict_to_no_action(&foreign_key.on_delete),
+          on_update: restrict_to_no_action(&foreign_key.on_update),
+          ..foreign_key.clone()
+        };
+
+        alter_table_foreign_key(&foreign_key, ident)
+      })
+      .collect(),
+  );
+  sections.push(
+    model
+      .tables
+      .iter()
+      .flat_map(|table| create_indexes(table, &mut diags))
+      .collect(),
+  );
+  sections.push(model.tables.iter().map(|table| descriptions(table)).collect());
+
+  if !diags.is_empty() {
+    return Err(diags);
+  }
+
+  let sections: Vec<String> = sections.into_iter().filter(|section| !section.is_empty()).collect();
+
+  Ok(sections.join("\n"))
+}
+
+fn create_table(model: &Model, table: &TableBlock) -> String {
+  let pk = primary_key(table);
+  let inline_pk = inline_primary_key(table);
+  let mut defs: Vec<_> = table
+    .cols
+    .iter()
+    .map(|col| {
+      let col_name = ident(&col.name.to_string);
+      let mut def = format!("{} {}", col_name, col_type(model, col));
+
+      let settings = col.settings.as_ref();
+      if settings.is_some_and(|settings| settings.is_incremental) || model.is_serial(col) {
+        def += " IDENTITY(1, 1)";
+      }
+
+      if let Some(settings) = settings {
+        if inline_pk == Some(col.name.to_string.as_str()) {
+          def += " PRIMARY KEY";
+        }
+        if settings.is_unique {
+          def += " UNIQUE";
+        }
+        if settings.nullable == Some(Nullable::NotNull) {
+          def += " NOT NULL";
+        }
+        if let Some(default) = &settings.default {
+          let _ = write!(def, " DEFAULT {}", value(default));
+        }
+      }
+
+      if let ColumnKind::Enum(r#enum) = model.column_kind(col) {
+        let values: Vec<_> = r#enum
+          .values
+          .iter()
+          .map(|value| string(&value.value.to_string))
+          .collect();
+
+        let _ = write!(def, " CHECK ({} IN ({}))", col_name, values.join(", "));
+      }
+
+      def
+    })
+    .collect();
+
+  if !pk.is_empty() && inline_pk.is_none() {
+    defs.push(format!("PRIMARY KEY ({})", idents(pk, ident)));
+  }
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident),
+    defs.join(",\n  ")
+  )
+}
+
+fn create_junction_table(model: &Model, junction_table: &JunctionTable) -> String {
+  let mut defs: Vec<_> = junction_table
+    .cols
+    .iter()
+    .map(|(name, col)| format!("{} {}", ident(name), col_type(model, col)))
+    .collect();
+  defs.push(format!(
+    "PRIMARY KEY ({})",
+    idents(junction_table.cols.iter().map(|(name, _)| name), ident)
+  ));
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&junction_table.name, ident),
+    defs.join(",\n  ")
+  )
+}
+
+fn create_indexes(table: &TableBlock, diags: &mut Vec<Diagnostic>) -> Vec<String> {
+  let table_name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+
+  indexes(table)
+    .enumerate()
+    .filter_map(|(i, def)| {
+      let settings = def.settings.as_ref();
+
+      if let Some(settings) = settings {
+        if let Some(r#type @ (IndexesType::Hash | IndexesType::Gin | IndexesType::Gist)) = &settings.r#type {
+          let err = Err::UnsupportedIndexType {
+            r#type: r#type.to_string(),
+            dialect: DIALECT,
+          };
+
+          diags.push(Diagnostic::generator(
+            err,
+            setting_span(&settings.attributes, "type", &settings.span_range),
+          ));
+        }
+      }
+
+      let mut cols = vec![];
+      for col in &def.cols {
+        match col {
+          IndexesColumnType::String(col) => cols.push(col.to_string.as_str()),
+          IndexesColumnType::Expr(expr) => {
+            diags.push(Diagnostic::generator(
+              Err::UnsupportedExpressionIndex { dialect: DIALECT },
+              &expr.span_range,
+            ));
+          }
+        }
+      }
+      if cols.len() != def.cols.len() {
+        return None;
+      }
+
+      // index names are mandatory in SQL Server
+      let name = settings
+        .and_then(|settings| settings.name.clone())
+        .unwrap_or_else(|| format!("{}_index_{}", table_name.name, i));
+      let unique = match settings.is_some_and(|settings| settings.is_unique) {
+        true => "UNIQUE ",
+        false => "",
+      };
+
+      Some(format!(
+        "CREATE {}INDEX {} ON {} ({});\n",
+        unique,
+        ident(&name),
+        qualified_ident(&table_name, ident),
+        idents(cols, ident)
+      ))
+    })
+    .collect()
+}
+
+/// Attaches the notes of the table and its columns as `MS_Description` extended properties.
+fn descriptions(table: &TableBlock) -> String {
+  let schema = QualifiedName::new(&table.ident.schema, &table.ident.name)
+    .schema
+    .unwrap_or_else(|| DEFAULT_MSSQL_SCHEMA.to_string());
+  let level1 = format!(
+    "@level0type = N'SCHEMA', @level0name = {}, @level1type = N'TABLE', @level1name = {}",
+    string(&schema),
+    string(&table.ident.name.to_string)
+  );
+  let mut out = String::new();
+
+  if let Some(note) = &table.note {
+    let _ = writeln!(
+      out,
+      "EXEC sp_addextendedproperty @name = N'MS_Description', @value = {}, {};",
+      string(&note_text(note)),
+      level1
+    );
+  }
+
+  for col in &table.cols {
+    if let Some(note) = col.settings.as_ref().and_then(|settings| settings.note.as_ref()) {
+      let _ = writeln!(
+        out,
+        "EXEC sp_addextendedproperty @name = N'MS_Description', @value = {}, {}, @level2type = N'COLUMN', @level2name = {};",
+        string(note),
+        level1,
+        string(&col.name.to_string)
+      );
+    }
+  }
+
+  out
+}
+
+/// Maps the column type to a SQL Server type.
+fn col_type(model: &Model, col: &TableColumn) -> String {
+  // arrays are stored as JSON text
+  if !col.r#type.arrays.is_empty() {
+    return "nvarchar(max)".to_string();
+  }
+
+  let args: Vec<_> = col.r#type.args.iter().map(ToString::to_string).collect();
+  let with_args = |type_name: &str| {
+    match args.is_empty() {
+      true => type_name.to_string(),
+      false => format!("{}({})", type_name, args.join(", ")),
+    }
+  };
+
+  match model.column_kind(col) {
+    ColumnKind::Builtin(type_name) => {
+      match type_name {
+        ColumnTypeName::Char => with_args("nchar"),
+        ColumnTypeName::VarChar if args.is_empty() => "nvarchar(255)".to_string(),
+        ColumnTypeName::VarChar => with_args("nvarchar"),
+        ColumnTypeName::Bit | ColumnTypeName::Varbit | ColumnTypeName::ByteArray => "varbinary(max)".to_string(),
+        ColumnTypeName::SmallInt | ColumnTypeName::SmallSerial => "smallint".to_string(),
+        ColumnTypeName::Integer | ColumnTypeName::Serial => "int".to_string(),
+        ColumnTypeName::BigInt | ColumnTypeName::BigSerial => "bigint".to_string(),
+        ColumnTypeName::Real => "real".to_string(),
+        ColumnTypeName::DoublePrecision => "float".to_string(),
+        ColumnTypeName::Decimal => with_args("decimal"),
+        ColumnTypeName::Money => "money".to_string(),
+        ColumnTypeName::Bool => "bit".to_string(),
+        ColumnTypeName::Date => "date".to_string(),
+        ColumnTypeName::Time | ColumnTypeName::Timetz => with_args("time"),
+        ColumnTypeName::Timestamp => with_args("datetime2"),
+        ColumnTypeName::Timestamptz => with_args("datetimeoffset"),
+        ColumnTypeName::Uuid => "uniqueidentifier".to_string(),
+        ColumnTypeName::Xml => "xml".to_string(),
+        ColumnTypeName::Inet | ColumnTypeName::Cidr => "nvarchar(43)".to_string(),
+        ColumnTypeName::MacAddr | ColumnTypeName::MacAddr8 => "nvarchar(23)".to_string(),
+        ColumnTypeName::Point
+        | ColumnTypeName::Polygon
+        | ColumnTypeName::Line
+        | ColumnTypeName::LineSegment
+        | ColumnTypeName::Path
+        | ColumnTypeName::Box
+        | ColumnTypeName::Circle => "geometry".to_string(),
+        _ => "nvarchar(max)".to_string(),
+      }
+    }
+    ColumnKind::Enum(_) => "nvarchar(255)".to_string(),
+    ColumnKind::Custom(raw) => with_args(raw),
+  }
+}
+
+fn value(value: &Value) -> String {
+  match value {
+    Value::String(s) | Value::Enum(s) | Value::HexColor(s) => string(s),
+    Value::Expr(expr) => expr.clone(),
+    Value::Bool(true) => "1".to_string(),
+    Value::Bool(false) => "0".to_string(),
+    Value::Null => "NULL".to_string(),
+    value => value.to_string(),
+  }
+}
+
+/// Quotes a Unicode string literal.
+fn string(text: &str) -> String {
+  format!("N{}", quote(text, '\'', '\''))
+}
+
+fn ident(name: &str) -> String {
+  quote(name, '[', ']')
+}
@@ -0,0 +1,343 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::err::Err;
+use super::*;
+
+/// The dialect name shown in diagnostics.
+const DIALECT: &str = "MySQL";
+
+/// Represents the options for generating MySQL data definitions.
+#[derive(Debug, Clone)]
+pub struct MySqlOptions {
+  /// The storage engine of all tables.
+  pub engine: String,
+  /// The default character set of all tables.
+  pub charset: String,
+  /// The default collation of all tables, if any.
+  pub collation: Option<String>,
+}
+
+impl Default for MySqlOptions {
+  fn default() -> Self {
+    Self {
+      engine: "InnoDB".to_string(),
+      charset: "utf8mb4".to_string(),
+      collation: None,
+    }
+  }
+}
+
+/// Generates MySQL (and MariaDB) data definition statements from the schema.
+///
+/// Enum columns become inline `ENUM(...)` types and notes become `COMMENT` clauses. Expression
+/// defaults other than `CURRENT_TIMESTAMP` are parenthesized.
+/// Foreign keys are added with `ALTER TABLE` once all tables exist, followed by `CREATE INDEX`.
+/// PostgreSQL-only types are mapped to the closest MySQL type, and array types are stored as `json`.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+/// * `options` - A reference to the generating options.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error, or an `UnsupportedIndexType` error for
+/// each `gin` or `gist` index.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::mysql::{generate, MySqlOptions};
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'MySQL' }\nTable users { id int [pk] }").unwrap();
+/// let sql = generate(&ast, &MySqlOptions::default()).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock, options: &MySqlOptions) -> Result<String, Vec<Diagnostic>> {
+  let model = Model::new(schema_block)?;
+  let mut diags = vec![];
+  let mut sections = vec![];
+
+  sections.push(
+    model
+      .schemas()
+      .into_iter()
+      .map(|schema| format!("CREATE SCHEMA IF NOT EXISTS {};\n", ident(schema)))
+      .collect(),
+  );
+  sections.extend(model.tables.iter().map(|table| create_table(&model, table, options)));
+  sections.extend(
+    model
+      .junction_tables
+      .iter()
+      .map(|junction_table| create_junction_table(&model, junction_table, options)),
+  );
+  sections.push(
+    model
+      .foreign_keys
+      .iter()
+      .map(|foreign_key| alter_table_foreign_key(foreign_key, ident))
+      .collect(),
+  );
+  sections.push(
+    model
+      .tables
+      .iter()
+      .flat_map(|table| create_indexes(table, &mut diags))
+      .collect(),
+  );
+
+  if !diags.is_empty() {
+    return Err(diags);
+  }
+
+  let sections: Vec<String> = sections.into_iter().filter(|section| !section.is_empty()).collect();
+
+  Ok(sections.join("\n"))
+}
+
+fn create_table(model: &Model, table: &TableBlock, options: &MySqlOptions) -> String {
+  let pk = primary_key(table);
+  let inline_pk = inline_primary_key(table);
+  let mut defs: Vec<_> = table
+    .cols
+    .iter()
+    .map(|col| {
+      let mut def = format!("{} {}", ident(&col.name.to_string), col_type(model, col));
+
+      if let Some(settings) = &col.settings {
+        if settings.nullable == Some(Nullable::NotNull) {
+          def += " NOT NULL";
+        }
+        if let Some(default) = &settings.default {
+          let _ = write!(def, " DEFAULT {}", value(default));
+        }
+        if settings.is_incremental || model.is_serial(col) {
+          def += " AUTO_INCREMENT";
+        }
+        if inline_pk == Some(col.name.to_string.as_str()) {
+          def += " PRIMARY KEY";
+        }
+        if settings.is_unique {
+          def += " UNIQUE";
+        }
+        if let Some(note) = &settings.note {
+          let _ = write!(def, " COMMENT {}", string(note));
+        }
+      } else if model.is_serial(col) {
+        def += " AUTO_INCREMENT";
+      }
+
+      def
+    })
+    .collect();
+
+  if !pk.is_empty() && inline_pk.is_none() {
+    defs.push(format!("PRIMARY KEY ({})", idents(pk, ident)));
+  }
+
+  let mut out = format!(
+    "CREATE TABLE {} (\n  {}\n) {}",
+    qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident),
+    defs.join(",\n  "),
+    table_options(options)
+  );
+  if let Some(note) = &table.note {
+    let _ = write!(out, " COMMENT={}", string(&note_text(note)));
+  }
+
+  out + ";\n"
+}
+
+fn create_junction_table(model: &Model, junction_table: &JunctionTable, options: &MySqlOptions) -> String {
+  let mut defs: Vec<_> = junction_table
+    .cols
+    .iter()
+    .map(|(name, col)| format!("{} {}", ident(name), col_type(model, col)))
+    .collect();
+  defs.push(format!(
+    "PRIMARY KEY ({})",
+    idents(junction_table.cols.iter().map(|(name, _)| name), ident)
+  ));
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n) {};\n",
+    qualified_ident(&junction_table.name, ident),
+    defs.join(",\n  "),
+    table_options(options)
+  )
+}
+
+fn table_options(options: &MySqlOptions) -> String {
+  let mut out = format!("ENGINE={} DEFAULT CHARSET={}", options.engine, options.charset);
+
+  if let Some(collation) = &options.collation {
+    let _ = write!(out, " COLLATE={}", collation);
+  }
+
+  out
+}
+
+fn create_indexes(table: &TableBlock, diags: &mut Vec<Diagnostic>) -> Vec<String> {
+  let table_name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+
+  indexes(table)
+    .enumerate()
+    .map(|(i, def)| {
+      let settings = def.settings.as_ref();
+      let mut out = String::from("CREATE ");
+
+      if settings.is_some_and(|settings| settings.is_unique) {
+        out += "UNIQUE ";
+      }
+
+      // index names are mandatory in MySQL
+      let name = settings
+        .and_then(|settings| settings.name.clone())
+        .unwrap_or_else(|| format!("{}_index_{}", table_name.name, i));
+      let cols: Vec<_> = def
+        .cols
+        .iter()
+        .map(|col| {
+          match col {
+            IndexesColumnType::String(col) => ident(&col.to_string),
+            IndexesColumnType::Expr(expr) => format!("({})", expr.value),
+          }
+        })
+        .collect();
+      let _ = write!(
+        out,
+        "INDEX {} ON {} ({})",
+        ident(&name),
+        qualified_ident(&table_name, ident),
+        cols.join(", ")
+      );
+
+      if let Some(settings) = settings {
+        match &settings.r#type {
+          Some(r#type @ (IndexesType::BTree | IndexesType::Hash)) => {
+            let _ = write!(out, " USING {}", r#type.to_string().to_uppercase());
+          }
+          Some(r#type) => {
+            let err = Err::UnsupportedIndexType {
+              r#type: r#type.to_string(),
+              dialect: DIALECT,
+            };
+
+            diags.push(Diagnostic::generator(
+              err,
+              setting_span(&settings.attributes, "type", &settings.span_range),
+            ));
+          }
+          None => (),
+        }
+      }
+
+      out + ";\n"
+    })
+    .collect()
+}
+
+/// Maps the column type to a MySQL type.
+fn col_type(model: &Model, col: &TableColumn) -> String {
+  // MySQL has no array types
+  if !col.r#type.arrays.is_empty() {
+    return "json".to_string();
+  }
+
+  let args: Vec<_> = col.r#type.args.iter().map(ToString::to_string).collect();
+  let with_args = |type_name: &str| {
+    match args.is_empty() {
+      true => type_name.to_string(),
+      false => format!("{}({})", type_name, args.join(", ")),
+    }
+  };
+
+  match model.column_kind(col) {
+    ColumnKind::Builtin(type_name) => {
+      match type_name {
+        ColumnTypeName::Bit | ColumnTypeName::Varbit => with_args("bit"),
+        ColumnTypeName::Char => with_args("char"),
+        ColumnTypeName::VarChar if args.is_empty() => "varchar(255)".to_string(),
+        ColumnTypeName::VarChar => with_args("varchar"),
+        ColumnTypeName::SmallInt | ColumnTypeName::SmallSerial => "smallint".to_string(),
+        ColumnTypeName::Integer | ColumnTypeName::Serial => "int".to_string(),
+        ColumnTypeName::BigInt | ColumnTypeName::BigSerial => "bigint".to_string(),
+        ColumnTypeName::Real => "float".to_string(),
+        ColumnTypeName::DoublePrecision => "double".to_string(),
+        ColumnTypeName::Decimal => with_args("decimal"),
+        ColumnTypeName::Money => "decimal(19, 4)".to_string(),
+        ColumnTypeName::Bool => "boolean".to_string(),
+        ColumnTypeName::ByteArray => "blob".to_string(),
+        ColumnTypeName::Date => "date".to_string(),
+        ColumnTypeName::Time | ColumnTypeName::Timetz => with_args("time"),
+        ColumnTypeName::Timestamp => with_args("datetime"),
+        ColumnTypeName::Timestamptz => with_args("timestamp"),
+        ColumnTypeName::Uuid => "char(36)".to_string(),
+        ColumnTypeName::Json | ColumnTypeName::Jsonb => "json".to_string(),
+        ColumnTypeName::Inet | ColumnTypeName::Cidr => "varchar(43)".to_string(),
+        ColumnTypeName::MacAddr | ColumnTypeName::MacAddr8 => "varchar(23)".to_string(),
+        ColumnTypeName::Point => "point".to_string(),
+        ColumnTypeName::Polygon => "polygon".to_string(),
+        ColumnTypeName::Line | ColumnTypeName::LineSegment | ColumnTypeName::Path => "linestring".to_string(),
+        ColumnTypeName::Box | ColumnTypeName::Circle => "geometry".to_string(),
+        _ => "text".to_string(),
+      }
+    }
+    ColumnKind::Enum(r#enum) => {
+      let values: Vec<_> = r#enum
+        .values
+        .iter()
+        .map(|value| string(&value.value.to_string))
+        .collect();
+
+      format!("enum({})", values.join(", "))
+    }
+    ColumnKind::Custom(raw) => with_args(raw),
+  }
+}
+
+fn value(value: &Value) -> String {
+  match value {
+    Value::String(s) | Value::Enum(s) | Value::HexColor(s) => string(s),
+    Value::Expr(expr) if is_current_timestamp(expr) => expr.clone(),
+    // other expressions must be parenthesized in `DEFAULT`, as of MySQL 8.0.13
+    Value::Expr(expr) => format!("({})", expr),
+    Value::Bool(b) => b.to_string().to_uppercase(),
+    Value::Null => "NULL".to_string(),
+    value => value.to_string(),
+  }
+}
+
+/// Checks if the expression is `CURRENT_TIMESTAMP`, with an optional precision, which is the only
+/// expression allowed unparenthesized in `DEFAULT`.
+fn is_current_timestamp(expr: &str) -> bool {
+  let expr = expr.trim();
+
+  match expr.get(.."CURRENT_TIMESTAMP".len()) {
+    Some(keyword) if keyword.eq_ignore_ascii_case("CURRENT_TIMESTAMP") => {
+      let rest = expr["CURRENT_TIMESTAMP".len()..].trim_start();
+
+      rest.is_empty()
+        || rest
+          .strip_prefix('(')
+          .and_then(|rest| rest.strip_suffix(')'))
+          .is_some_and(|precision| precision.trim().chars().all(|c| c.is_ascii_digit()))
+    }
+    _ => false,
+  }
+}
+
+/// Quotes a string literal. Backslashes are escaped since they start escape sequences in MySQL.
+fn string(text: &str) -> String {
+  quote(&text.replace('\\', "\\\\"), '\'', '\'')
+}
+
+fn ident(name: &str) -> String {
+  quote(name, '`', '`')
+}
@@ -0,0 +1,418 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::err::Err;
+use super::*;
+
+/// The dialect name shown in diagnostics.
+const DIALECT: &str = "Oracle";
+
+/// Represents the options for generating Oracle data definitions.
+#[derive(Debug, Clone)]
+pub struct OracleOptions {
+  /// The maximum length of identifiers in bytes, which is 128 since Oracle 12.2 and 30 before.
+  pub max_ident_len: usize,
+}
+
+impl Default for OracleOptions {
+  fn default() -> Self {
+    Self { max_ident_len: 128 }
+  }
+}
+
+/// Generates Oracle data definition statements from the schema.
+///
+/// The first auto-incremented column of each table becomes an identity column. As Oracle allows a
+/// single identity column per table, any other one takes its default from a sequence created
+/// beforehand. Enums are emulated with `CHECK (... IN (...))` constraints on `VARCHAR2` columns.
+/// Oracle has no `ON UPDATE` actions and only supports `CASCADE` and `SET NULL` on delete, so
+/// other referential actions are left out. Schemas are users in Oracle and must already exist.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+/// * `options` - A reference to the generating options.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error, an `UnsupportedIndexType` error for
+/// each `hash`, `gin` or `gist` index, or an `IdentifierTooLong` error for each schema, table,
+/// column, index, sequence or foreign key name longer than the maximum length, including the
+/// names generated for the junction tables of many-to-many refs.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::oracle::{generate, OracleOptions};
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'Oracle' }\nTable users { id int [pk] }").unwrap();
+/// let sql = generate(&ast, &OracleOptions::default()).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock, options: &OracleOptions) -> Result<String, Vec<Diagnostic>> {
+  let model = Model::new(schema_block)?;
+  let mut checker = IdentChecker {
+    max_len: options.max_ident_len,
+    diags: vec![],
+  };
+  let mut sections = vec![];
+
+  let tables: Vec<_> = model
+    .tables
+    .iter()
+    .map(|table| create_table(&model, table, &mut checker))
+    .collect();
+  sections.push(tables.iter().map(|(sequences, _)| sequences.as_str()).collect());
+  sections.extend(tables.iter().map(|(_, table)| table.clone()));
+  sections.extend(
+    model
+      .junction_tables
+      .iter()
+      .map(|junction_table| create_junction_table(&model, junction_table, &mut checker)),
+  );
+  sections.push(
+    model
+      .foreign_keys
+      .iter()
+      .map(|foreign_key| {
+        if let Some(name) = &foreign_key.name {
+          checker.check(name, &foreign_key.span_range);
+        }
+
+        let foreign_key = ForeignKey {
+          on_delete: foreign_key
+            .on_delete
+            .clone()
+            .filter(|action| matches!(action, ReferentialAction::Cascade | ReferentialAction::SetNull)),
+          on_update: None,
+          ..foreign_key.clone()
+        };
+
+        alter_table_foreign_key(&foreign_key, ident)
+      })
+      .collect(),
+  );
+  sections.push(
+    model
+      .tables
+      .iter()
+      .flat_map(|table| create_indexes(table, &mut checker))
+      .collect(),
+  );
+  sections.push(model.tables.iter().map(|table| comments(table)).collect());
+
+  if !checker.diags.is_empty() {
+    return Err(checker.diags);
+  }
+
+  let sections: Vec<String> = sections.into_iter().filter(|section| !section.is_empty()).collect();
+
+  Ok(sections.join("\n"))
+}
+
+/// Reports identifiers exceeding the maximum length.
+struct IdentChecker {
+  max_len: usize,
+  diags: Vec<Diagnostic>,
+}
+
+impl IdentChecker {
+  fn check(&mut self, name: &str, span_range: &SpanRange) {
+    if name.len() > self.max_len {
+      let err = Err::IdentifierTooLong {
+        ident: name.to_string(),
+        max_len: self.max_len,
+        dialect: DIALECT,
+      };
+
+      self.diags.push(Diagnostic::generator(err, span_range));
+    }
+  }
+}
+
+/// Writes the `CREATE TABLE` statement along with the sequences it takes defaults from.
+fn create_table(model: &Model, table: &TableBlock, checker: &mut IdentChecker) -> (String, String) {
+  let table_name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+  let pk = primary_key(table);
+  let inline_pk = inline_primary_key(table);
+  let mut sequences = String::new();
+  let mut has_identity = false;
+
+  if let Some(schema) = &table.ident.schema {
+    checker.check(&schema.to_string, &schema.span_range);
+  }
+  checker.check(&table_name.name, &table.ident.name.span_range);
+
+  let mut defs: Vec<_> = table
+    .cols
+    .iter()
+    .map(|col| {
+      checker.check(&col.name.to_string, &col.name.span_range);
+
+      let col_name = ident(&col.name.to_string);
+      let mut def = format!("{} {}", col_name, col_type(model, col));
+      let settings = col.settings.as_ref();
+
+      let is_incremental = settings.is_some_and(|settings| settings.is_incremental) || model.is_serial(col);
+      if is_incremental && is_integer(model, col) && !has_identity {
+        def += " GENERATED BY DEFAULT AS IDENTITY";
+        has_identity = true;
+      } else if is_incremental && is_integer(model, col) {
+        // only one identity column is allowed per table
+        let sequence_name = QualifiedName {
+          schema: table_name.schema.clone(),
+          name: format!("{}_{}_seq", table_name.name, col.name.to_string),
+        };
+        checker.check(&sequence_name.name, &col.name.span_range);
+
+        let sequence = qualified_ident(&sequence_name, ident);
+        let _ = writeln!(sequences, "CREATE SEQUENCE {};", sequence);
+        let _ = write!(def, " DEFAULT {}.NEXTVAL", sequence);
+      } else if let Some(default) = settings.and_then(|settings| settings.default.as_ref()) {
+        let _ = write!(def, " DEFAULT {}", value(default));
+      }
+
+      if let Some(settings) = settings {
+        if settings.nullable == Some(Nullable::NotNull) {
+          def += " NOT NULL";
+        }
+        if inline_pk == Some(col.name.to_string.as_str()) {
+          def += " PRIMARY KEY";
+        }
+        if settings.is_unique {
+          def += " UNIQUE";
+        }
+      }
+
+      if let ColumnKind::Enum(r#enum) = model.column_kind(col) {
+        let values: Vec<_> = r#enum
+          .values
+          .iter()
+          .map(|value| string(&value.value.to_string))
+          .collect();
+
+        let _ = write!(def, " CHECK ({} IN ({}))", col_name, values.join(", "));
+      }
+
+      def
+    })
+    .collect();
+
+  if !pk.is_empty() && inline_pk.is_none() {
+    defs.push(format!("PRIMARY KEY ({})", idents(pk, ident)));
+  }
+
+  let table = format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&table_name, ident),
+    defs.join(",\n  ")
+  );
+
+  (sequences, table)
+}
+
+fn create_junction_table(model: &Model, junction_table: &JunctionTable, checker: &mut IdentChecker) -> String {
+  checker.check(&junction_table.name.name, &junction_table.span_range);
+
+  let mut defs: Vec<_> = junction_table
+    .cols
+    .iter()
+    .map(|(name, col)| {
+      checker.check(name, &junction_table.span_range);
+
+      format!("{} {}", ident(name), col_type(model, col))
+    })
+    .collect();
+  defs.push(format!(
+    "PRIMARY KEY ({})",
+    idents(junction_table.cols.iter().map(|(name, _)| name), ident)
+  ));
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&junction_table.name, ident),
+    defs.join(",\n  ")
+  )
+}
+
+fn create_indexes(table: &TableBlock, checker: &mut IdentChecker) -> Vec<String> {
+  let table_name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+
+  indexes(table)
+    .enumerate()
+    .map(|(i, def)| {
+      let settings = def.settings.as_ref();
+      let mut out = String::from("CREATE ");
+
+      if let Some(settings) = settings {
+        if let Some(r#type @ (IndexesType::Hash | IndexesType::Gin | IndexesType::Gist)) = &settings.r#type {
+          let err = Err::UnsupportedIndexType {
+            r#type: r#type.to_string(),
+            dialect: DIALECT,
+          };
+
+          checker.diags.push(Diagnostic::generator(
+            err,
+            setting_span(&settings.attributes, "type", &settings.span_range),
+          ));
+        }
+        if settings.is_unique {
+          out += "UNIQUE ";
+        }
+      }
+
+      // index names are mandatory in Oracle
+      let name = match settings.and_then(|settings| settings.name.as_ref().map(|name| (settings, name))) {
+        Some((settings, name)) => {
+          checker.check(name, setting_span(&settings.attributes, "name", &settings.span_range));
+
+          name.clone()
+        }
+        None => {
+          let name = format!("{}_index_{}", table_name.name, i);
+          checker.check(&name, &def.span_range);
+
+          name
+        }
+      };
+      let index_name = QualifiedName {
+        schema: table_name.schema.clone(),
+        name,
+      };
+      let cols: Vec<_> = def
+        .cols
+        .iter()
+        .map(|col| {
+          match col {
+            IndexesColumnType::String(col) => ident(&col.to_string),
+            IndexesColumnType::Expr(expr) => expr.value.to_string(),
+          }
+        })
+        .collect();
+
+      let _ = writeln!(
+        out,
+        "INDEX {} ON {} ({});",
+        qualified_ident(&index_name, ident),
+        qualified_ident(&table_name, ident),
+        cols.join(", ")
+      );
+
+      out
+    })
+    .collect()
+}
+
+fn comments(table: &TableBlock) -> String {
+  let table_name = qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident);
+  let mut out = String::new();
+
+  if let Some(note) = &table.note {
+    let _ = writeln!(out, "COMMENT ON TABLE {} IS {};", table_name, string(&note_text(note)));
+  }
+
+  for col in &table.cols {
+    if let Some(note) = col.settings.as_ref().and_then(|settings| settings.note.as_ref()) {
+      let _ = writeln!(
+        out,
+        "COMMENT ON COLUMN {}.{} IS {};",
+        table_name,
+        ident(&col.name.to_string),
+        string(note)
+      );
+    }
+  }
+
+  out
+}
+
+/// Maps the column type to an Oracle type.
+fn col_type(model: &Model, col: &TableColumn) -> String {
+  // arrays are stored as JSON text
+  if !col.r#type.arrays.is_empty() {
+    return "CLOB".to_string();
+  }
+
+  let args: Vec<_> = col.r#type.args.iter().map(ToString::to_string).collect();
+  let with_args = |type_name: &str| {
+    match args.is_empty() {
+      true => type_name.to_string(),
+      false => format!("{}({})", type_name, args.join(", ")),
+    }
+  };
+
+  match model.column_kind(col) {
+    ColumnKind::Builtin(type_name) => {
+      match type_name {
+        ColumnTypeName::Char => with_args("CHAR"),
+        ColumnTypeName::VarChar if args.is_empty() => "VARCHAR2(255)".to_string(),
+        ColumnTypeName::VarChar => with_args("VARCHAR2"),
+        ColumnTypeName::Bit | ColumnTypeName::Varbit | ColumnTypeName::ByteArray => "BLOB".to_string(),
+        ColumnTypeName::SmallInt | ColumnTypeName::SmallSerial => "NUMBER(5)".to_string(),
+        ColumnTypeName::Integer | ColumnTypeName::Serial => "NUMBER(10)".to_string(),
+        ColumnTypeName::BigInt | ColumnTypeName::BigSerial => "NUMBER(19)".to_string(),
+        ColumnTypeName::Real => "BINARY_FLOAT".to_string(),
+        ColumnTypeName::DoublePrecision => "BINARY_DOUBLE".to_string(),
+        ColumnTypeName::Decimal => with_args("NUMBER"),
+        ColumnTypeName::Money => "NUMBER(19, 4)".to_string(),
+        ColumnTypeName::Bool => "NUMBER(1)".to_string(),
+        ColumnTypeName::Date => "DATE".to_string(),
+        ColumnTypeName::Time | ColumnTypeName::Timetz => "INTERVAL DAY(0) TO SECOND".to_string(),
+        ColumnTypeName::Timestamp => with_args("TIMESTAMP"),
+        ColumnTypeName::Timestamptz => format!("{} WITH TIME ZONE", with_args("TIMESTAMP")),
+        ColumnTypeName::Uuid => "RAW(16)".to_string(),
+        ColumnTypeName::Xml => "XMLTYPE".to_string(),
+        ColumnTypeName::Inet | ColumnTypeName::Cidr => "VARCHAR2(43)".to_string(),
+        ColumnTypeName::MacAddr | ColumnTypeName::MacAddr8 => "VARCHAR2(23)".to_string(),
+        ColumnTypeName::Point
+        | ColumnTypeName::Polygon
+        | ColumnTypeName::Line
+        | ColumnTypeName::LineSegment
+        | ColumnTypeName::Path
+        | ColumnTypeName::Box
+        | ColumnTypeName::Circle => "SDO_GEOMETRY".to_string(),
+        _ => "CLOB".to_string(),
+      }
+    }
+    ColumnKind::Enum(_) => "VARCHAR2(255)".to_string(),
+    ColumnKind::Custom(raw) => with_args(raw.trim()),
+  }
+}
+
+/// Checks if the column can be an identity column.
+fn is_integer(model: &Model, col: &TableColumn) -> bool {
+  matches!(
+    model.column_kind(col),
+    ColumnKind::Builtin(
+      ColumnTypeName::SmallInt
+        | ColumnTypeName::Integer
+        | ColumnTypeName::BigInt
+        | ColumnTypeName::SmallSerial
+        | ColumnTypeName::Serial
+        | ColumnTypeName::BigSerial
+    )
+  )
+}
+
+fn value(value: &Value) -> String {
+  match value {
+    Value::String(s) | Value::Enum(s) | Value::HexColor(s) => string(s),
+    Value::Expr(expr) => expr.clone(),
+    Value::Bool(true) => "1".to_string(),
+    Value::Bool(false) => "0".to_string(),
+    Value::Null => "NULL".to_string(),
+    value => value.to_string(),
+  }
+}
+
+fn string(text: &str) -> String {
+  quote(text, '\'', '\'')
+}
+
+fn ident(name: &str) -> String {
+  quote(name, '"', '"')
+}
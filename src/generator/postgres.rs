@@ -0,0 +1,998 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::{
+  self,
+  Write,
+};
+
+use super::*;
+use crate::diff::{
+  diff,
+  ColumnChange,
+  EnumDiff,
+  TableChange,
+  TableDiff,
+};
+
+/// Represents a migration script turning one schema into another.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Migration {
+  /// The statements in the order they must be run.
+  pub statements: Vec<MigrationStatement>,
+}
+
+/// Represents a single statement of a migration script.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MigrationStatement {
+  /// The statement terminated by a semicolon and a newline.
+  pub sql: String,
+  /// Whether the statement may lose data or fail on existing rows, such as dropping a table, a
+  /// column or an enum, changing the type of a column, removing an enum value, adding a not null
+  /// column without a default or adding a not null, unique or primary key constraint.
+  pub is_destructive: bool,
+}
+
+impl Migration {
+  /// Checks if there is nothing to migrate.
+  pub fn is_empty(&self) -> bool {
+    self.statements.is_empty()
+  }
+
+  /// Checks if any of the statements may lose data.
+  pub fn is_destructive(&self) -> bool {
+    self.statements.iter().any(|statement| statement.is_destructive)
+  }
+
+  fn push(&mut self, sql: String) {
+    self.statements.push(MigrationStatement {
+      sql,
+      is_destructive: false,
+    });
+  }
+
+  fn push_destructive(&mut self, sql: String) {
+    self.statements.push(MigrationStatement {
+      sql,
+      is_destructive: true,
+    });
+  }
+}
+
+/// Writes the statements as a script, preceding each destructive statement with a comment.
+impl fmt::Display for Migration {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for statement in &self.statements {
+      if statement.is_destructive {
+        writeln!(f, "-- destructive: may lose data")?;
+      }
+
+      write!(f, "{}", statement.sql)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Generates PostgreSQL data definition statements from the schema.
+///
+/// The schema is semantically checked first. Statements are ordered by their dependencies:
+/// `CREATE SCHEMA`, `CREATE TYPE ... AS ENUM`, `CREATE TABLE`, foreign keys added with
+/// `ALTER TABLE` once all tables exist, `CREATE INDEX` and finally `COMMENT ON` from notes.
+/// Many-to-many refs produce a junction table referencing both sides.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::postgres;
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id int [pk] }").unwrap();
+/// let sql = postgres::generate(&ast).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock) -> Result<String, Vec<Diagnostic>> {
+  let model = Model::new(schema_block)?;
+  let mut sections = vec![];
+
+  sections.push(
+    model
+      .schemas()
+      .into_iter()
+      .map(|schema| format!("CREATE SCHEMA IF NOT EXISTS {};\n", ident(schema)))
+      .collect(),
+  );
+  sections.push(model.enums.iter().map(|r#enum| create_type(r#enum)).collect());
+  sections.extend(model.tables.iter().map(|table| create_table(&model, table)));
+  sections.extend(
+    model
+      .junction_tables
+      .iter()
+      .map(|junction_table| create_junction_table(&model, junction_table)),
+  );
+  sections.push(
+    model
+      .foreign_keys
+      .iter()
+      .map(|foreign_key| alter_table_foreign_key(foreign_key, ident))
+      .collect(),
+  );
+  sections.push(model.tables.iter().flat_map(|table| create_indexes(table)).collect());
+  sections.push(model.tables.iter().map(|table| comments(table)).collect());
+
+  let sections: Vec<String> = sections.into_iter().filter(|section| !section.is_empty()).collect();
+
+  Ok(sections.join("\n"))
+}
+
+/// Generates the PostgreSQL statements migrating a database from the old schema to the new one.
+///
+/// Both schemas are semantically checked first and compared with [`diff`]. Statements are ordered
+/// by their dependencies: `CREATE SCHEMA`, dropping foreign keys, indexes and constraints, renaming
+/// tables, creating and altering enums, `CREATE TABLE`, altering columns, `DROP TABLE`, `DROP TYPE`,
+/// `CREATE INDEX`, adding foreign keys and finally `COMMENT ON` from notes. Tables are created with
+/// the referenced tables first and dropped with the referencing tables first, following the
+/// foreign keys of the refs. Unnamed constraints and indexes are dropped by the names PostgreSQL
+/// gives them.
+///
+/// New enum values are added with `ALTER TYPE ... ADD VALUE`. Since PostgreSQL cannot remove enum
+/// values, an enum losing values is recreated and the columns using it are converted.
+///
+/// The backward migration is generated by swapping the schemas.
+///
+/// # Arguments
+///
+/// * `old` - A reference to the unsanitized AST of the current schema.
+/// * `new` - A reference to the unsanitized AST of the target schema.
+///
+/// # Errors
+///
+/// All diagnostics of a schema if any of them is an error.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::postgres;
+///
+/// let old = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id int [pk] }").unwrap();
+/// let new = parse_dbml_unchecked("Project p { database_type: 'PostgreSQL' }\nTable users { id bigint [pk] }").unwrap();
+/// let up = postgres::migrate(&old, &new).unwrap();
+/// let down = postgres::migrate(&new, &old).unwrap();
+/// ```
+pub fn migrate(old: &SchemaBlock, new: &SchemaBlock) -> Result<Migration, Vec<Diagnostic>> {
+  let old_model = Model::new(old)?;
+  let new_model = Model::new(new)?;
+  let schema_diff = diff(old, new);
+  let mut migration = Migration::default();
+
+  let renames: Vec<_> = schema_diff
+    .tables
+    .iter()
+    .filter_map(|table_diff| {
+      match table_diff {
+        TableDiff::Changed { old, new, .. } if table_diff.is_renamed() => Some((table_name(old), table_name(new))),
+        _ => None,
+      }
+    })
+    .collect();
+  let renamed = |name: &QualifiedName| {
+    renames
+      .iter()
+      .find(|(old, _)| old == name)
+      .map_or_else(|| name.clone(), |(_, new)| new.clone())
+  };
+
+  let mut dropped_tables: Vec<_> = schema_diff
+    .tables
+    .iter()
+    .filter_map(|table_diff| {
+      match table_diff {
+        TableDiff::Removed(table) => Some(table_name(table)),
+        _ => None,
+      }
+    })
+    .collect();
+  dropped_tables.extend(
+    old_model
+      .junction_tables
+      .iter()
+      .filter(|old| !new_model.junction_tables.iter().any(|new| new.name == old.name))
+      .map(|old| old.name.clone()),
+  );
+  let (mut drop_order, cyclic_tables) = dependency_order(&dropped_tables, &old_model.foreign_keys);
+  drop_order.reverse();
+  drop_order.extend(cyclic_tables.iter().cloned());
+
+  let old_schemas = old_model.schemas();
+  for schema in new_model.schemas().difference(&old_schemas) {
+    migration.push(format!("CREATE SCHEMA IF NOT EXISTS {};\n", ident(schema)));
+  }
+
+  // Foreign keys are compared with the old table names mapped to the renamed ones.
+  let old_foreign_keys: Vec<_> = old_model
+    .foreign_keys
+    .iter()
+    .map(|foreign_key| {
+      let key = foreign_key_key(foreign_key, &renamed(&foreign_key.from), &renamed(&foreign_key.to));
+
+      (foreign_key, key)
+    })
+    .collect();
+  let new_foreign_keys: Vec<_> = new_model
+    .foreign_keys
+    .iter()
+    .map(|foreign_key| foreign_key_key(foreign_key, &foreign_key.from, &foreign_key.to))
+    .collect();
+
+  for (foreign_key, key) in &old_foreign_keys {
+    let is_dropped_with_table = dropped_tables.contains(&foreign_key.from)
+      && !(cyclic_tables.contains(&foreign_key.from) && cyclic_tables.contains(&foreign_key.to));
+
+    if !new_foreign_keys.contains(key) && !is_dropped_with_table {
+      migration.push(format!(
+        "ALTER TABLE {} DROP CONSTRAINT {};\n",
+        qualified_ident(&foreign_key.from, ident),
+        ident(&foreign_key_name(foreign_key))
+      ));
+    }
+  }
+
+  for table_diff in &schema_diff.tables {
+    if let TableDiff::Changed { old, new, changes } = table_diff {
+      drop_table_constraints(&mut migration, old, new, changes);
+    }
+  }
+
+  for (old, new) in &renames {
+    let mut name = old.clone();
+
+    if old.schema != new.schema {
+      migration.push(format!(
+        "ALTER TABLE {} SET SCHEMA {};\n",
+        qualified_ident(&name, ident),
+        ident(new.schema.as_deref().unwrap_or(DEFAULT_SCHEMA))
+      ));
+      name.schema = new.schema.clone();
+    }
+    if old.name != new.name {
+      migration.push(format!(
+        "ALTER TABLE {} RENAME TO {};\n",
+        qualified_ident(&name, ident),
+        ident(&new.name)
+      ));
+    }
+  }
+
+  let mut converted_cols = vec![];
+  for enum_diff in &schema_diff.enums {
+    match enum_diff {
+      EnumDiff::Added(r#enum) => migration.push(create_type(r#enum)),
+      EnumDiff::Changed {
+        old,
+        new,
+        added_values,
+        removed_values,
+      } => {
+        if removed_values.is_empty() {
+          add_enum_values(&mut migration, old, new, added_values);
+        } else {
+          converted_cols.extend(recreate_type(
+            &mut migration,
+            &old_model,
+            &new_model,
+            old,
+            new,
+            &renamed,
+          ));
+        }
+      }
+      EnumDiff::Removed(_) => (),
+    }
+  }
+
+  let added_tables: Vec<_> = schema_diff
+    .tables
+    .iter()
+    .filter_map(|table_diff| {
+      match table_diff {
+        TableDiff::Added(table) => Some(*table),
+        _ => None,
+      }
+    })
+    .collect();
+  let (mut create_order, cyclic_tables) = dependency_order(
+    &added_tables.iter().map(|table| table_name(table)).collect::<Vec<_>>(),
+    &new_model.foreign_keys,
+  );
+  create_order.extend(cyclic_tables);
+  for name in &create_order {
+    if let Some(table) = added_tables.iter().find(|table| &table_name(table) == name) {
+      migration.push(create_table(&new_model, table));
+    }
+  }
+  for junction_table in &new_model.junction_tables {
+    if !old_model
+      .junction_tables
+      .iter()
+      .any(|old| old.name == junction_table.name)
+    {
+      migration.push(create_junction_table(&new_model, junction_table));
+    }
+  }
+
+  for table_diff in &schema_diff.tables {
+    if let TableDiff::Changed { old, new, changes } = table_diff {
+      alter_table(
+        &mut migration,
+        &old_model,
+        &new_model,
+        old,
+        new,
+        changes,
+        &converted_cols,
+      );
+    }
+  }
+
+  for name in &drop_order {
+    migration.push_destructive(format!("DROP TABLE {};\n", qualified_ident(name, ident)));
+  }
+
+  for enum_diff in &schema_diff.enums {
+    match enum_diff {
+      EnumDiff::Removed(r#enum) => {
+        migration.push_destructive(format!(
+          "DROP TYPE {};\n",
+          qualified_ident(&QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name), ident)
+        ))
+      }
+      EnumDiff::Changed {
+        old, removed_values, ..
+      } if !removed_values.is_empty() => {
+        migration.push(format!(
+          "DROP TYPE {};\n",
+          qualified_ident(&replaced_type_name(old), ident)
+        ))
+      }
+      _ => (),
+    }
+  }
+
+  for table_diff in &schema_diff.tables {
+    match table_diff {
+      TableDiff::Added(table) => {
+        for sql in create_indexes(table) {
+          migration.push(sql);
+        }
+      }
+      TableDiff::Changed { new, changes, .. } => {
+        let table = qualified_ident(&table_name(new), ident);
+
+        for change in changes {
+          match change {
+            TableChange::IndexAdded(def) if !is_pk_index(def) => migration.push(create_index(&table, def)),
+            _ => (),
+          }
+        }
+      }
+      TableDiff::Removed(_) => (),
+    }
+  }
+
+  for foreign_key in &new_model.foreign_keys {
+    let key = foreign_key_key(foreign_key, &foreign_key.from, &foreign_key.to);
+
+    if !old_foreign_keys.iter().any(|(_, old_key)| old_key == &key) {
+      migration.push(alter_table_foreign_key(foreign_key, ident));
+    }
+  }
+
+  for table_diff in &schema_diff.tables {
+    match table_diff {
+      TableDiff::Added(table) => {
+        for line in comments(table).lines() {
+          migration.push(format!("{}\n", line));
+        }
+      }
+      TableDiff::Changed { old, new, changes } => alter_comments(&mut migration, old, new, changes),
+      TableDiff::Removed(_) => (),
+    }
+  }
+
+  Ok(migration)
+}
+
+/// Drops the indexes, unique constraints and primary key of a changed table which are removed or
+/// changed, using the old table name.
+fn drop_table_constraints(migration: &mut Migration, old: &TableBlock, new: &TableBlock, changes: &[TableChange]) {
+  let old_name = table_name(old);
+  let table = qualified_ident(&old_name, ident);
+
+  for change in changes {
+    match change {
+      TableChange::IndexRemoved(def) if !is_pk_index(def) => {
+        let index = QualifiedName {
+          schema: old_name.schema.clone(),
+          name: index_name(&old_name, def),
+        };
+
+        migration.push(format!("DROP INDEX {};\n", qualified_ident(&index, ident)));
+      }
+      TableChange::ColumnChanged {
+        old: old_col, changes, ..
+      } if changes.contains(&ColumnChange::Unique) && is_unique(old_col) => {
+        migration.push(format!(
+          "ALTER TABLE {} DROP CONSTRAINT {};\n",
+          table,
+          ident(&format!("{}_{}_key", old_name.name, old_col.name.to_string))
+        ));
+      }
+      _ => (),
+    }
+  }
+
+  let old_pk = primary_key(old);
+  if !old_pk.is_empty() && old_pk != primary_key(new) {
+    migration.push(format!(
+      "ALTER TABLE {} DROP CONSTRAINT {};\n",
+      table,
+      ident(&format!("{}_pkey", old_name.name))
+    ));
+  }
+}
+
+/// Adds, drops and alters the columns of a changed table, using the new table name.
+fn alter_table(
+  migration: &mut Migration,
+  old_model: &Model,
+  new_model: &Model,
+  old: &TableBlock,
+  new: &TableBlock,
+  changes: &[TableChange],
+  converted_cols: &[(QualifiedName, String)],
+) {
+  let new_name = table_name(new);
+  let table = qualified_ident(&new_name, ident);
+
+  for change in changes {
+    match change {
+      TableChange::ColumnAdded(col) => {
+        let sql = format!("ALTER TABLE {} ADD COLUMN {};\n", table, col_def(new_model, col, false));
+        let is_incremental = col.settings.as_ref().is_some_and(|settings| settings.is_incremental);
+
+        // existing rows cannot be filled in without a default
+        match is_not_null(col) && default_of(col).is_none() && !is_incremental && !new_model.is_serial(col) {
+          true => migration.push_destructive(sql),
+          false => migration.push(sql),
+        }
+      }
+      TableChange::ColumnRemoved(col) => {
+        migration.push_destructive(format!(
+          "ALTER TABLE {} DROP COLUMN {};\n",
+          table,
+          ident(&col.name.to_string)
+        ))
+      }
+      TableChange::ColumnChanged {
+        old: old_col,
+        new: new_col,
+        changes,
+      } => {
+        let alter_col = format!("ALTER TABLE {} ALTER COLUMN {}", table, ident(&new_col.name.to_string));
+        let new_settings = new_col.settings.as_ref();
+        let is_converted = converted_cols
+          .iter()
+          .any(|(name, col)| name == &new_name && col == &new_col.name.to_string);
+
+        for change in changes {
+          match change {
+            ColumnChange::Type => {
+              if default_of(old_col).is_some() {
+                migration.push(format!("{} DROP DEFAULT;\n", alter_col));
+              }
+              migration.push_destructive(format!("{} TYPE {};\n", alter_col, converted_type(new_model, new_col)));
+              if let Some(default) = default_of(new_col) {
+                migration.push(format!("{} SET DEFAULT {};\n", alter_col, value(default)));
+              }
+            }
+            ColumnChange::Nullable => {
+              match is_not_null(new_col) {
+                true => migration.push_destructive(format!("{} SET NOT NULL;\n", alter_col)),
+                false => migration.push(format!("{} DROP NOT NULL;\n", alter_col)),
+              }
+            }
+            ColumnChange::Default if !changes.contains(&ColumnChange::Type) && !is_converted => {
+              match default_of(new_col) {
+                Some(default) => migration.push(format!("{} SET DEFAULT {};\n", alter_col, value(default))),
+                None => migration.push(format!("{} DROP DEFAULT;\n", alter_col)),
+              }
+            }
+            ColumnChange::Unique if is_unique(new_col) => {
+              migration.push_destructive(format!(
+                "ALTER TABLE {} ADD UNIQUE ({});\n",
+                table,
+                ident(&new_col.name.to_string)
+              ))
+            }
+            ColumnChange::Increment => {
+              if new_settings.is_some_and(|settings| settings.is_incremental) && is_integer(new_model, new_col) {
+                if !is_not_null(new_col) {
+                  migration.push_destructive(format!("{} SET NOT NULL;\n", alter_col));
+                }
+                migration.push(format!("{} ADD GENERATED BY DEFAULT AS IDENTITY;\n", alter_col));
+              } else if is_integer(old_model, old_col) {
+                migration.push(format!("{} DROP IDENTITY IF EXISTS;\n", alter_col));
+              }
+            }
+            _ => (),
+          }
+        }
+      }
+      _ => (),
+    }
+  }
+
+  let new_pk = primary_key(new);
+  if !new_pk.is_empty() && primary_key(old) != new_pk {
+    migration.push_destructive(format!(
+      "ALTER TABLE {} ADD PRIMARY KEY ({});\n",
+      table,
+      idents(new_pk, ident)
+    ));
+  }
+}
+
+/// Updates the comments of a changed table from its notes.
+fn alter_comments(migration: &mut Migration, old: &TableBlock, new: &TableBlock, changes: &[TableChange]) {
+  let table = qualified_ident(&table_name(new), ident);
+  let comment = |note: Option<String>| note.map_or_else(|| "NULL".to_string(), |note| quote(&note, '\'', '\''));
+
+  let old_note = old.note.as_ref().map(note_text);
+  let new_note = new.note.as_ref().map(note_text);
+  if old_note != new_note {
+    migration.push(format!("COMMENT ON TABLE {} IS {};\n", table, comment(new_note)));
+  }
+
+  for change in changes {
+    let col = match change {
+      TableChange::ColumnAdded(col) if col.settings.as_ref().is_some_and(|settings| settings.note.is_some()) => col,
+      TableChange::ColumnChanged { new, changes, .. } if changes.contains(&ColumnChange::Note) => new,
+      _ => continue,
+    };
+
+    migration.push(format!(
+      "COMMENT ON COLUMN {}.{} IS {};\n",
+      table,
+      ident(&col.name.to_string),
+      comment(col.settings.as_ref().and_then(|settings| settings.note.clone()))
+    ));
+  }
+}
+
+/// Adds the new enum values, placing each one before the next value which already exists.
+fn add_enum_values(migration: &mut Migration, old: &EnumBlock, new: &EnumBlock, added_values: &[&EnumValue]) {
+  let r#type = qualified_ident(&QualifiedName::new(&new.ident.schema, &new.ident.name), ident);
+  let is_old_value = |value: &EnumValue| {
+    old
+      .values
+      .iter()
+      .any(|old_value| old_value.value.to_string == value.value.to_string)
+  };
+
+  for added_value in added_values {
+    let mut sql = format!(
+      "ALTER TYPE {} ADD VALUE {}",
+      r#type,
+      quote(&added_value.value.to_string, '\'', '\'')
+    );
+
+    let next_value = new
+      .values
+      .iter()
+      .skip_while(|value| value.value.to_string != added_value.value.to_string)
+      .find(|value| is_old_value(value));
+    if let Some(next_value) = next_value {
+      let _ = write!(sql, " BEFORE {}", quote(&next_value.value.to_string, '\'', '\''));
+    }
+
+    migration.push(format!("{};\n", sql));
+  }
+}
+
+/// Recreates an enum losing values by renaming the old type out of the way, creating the new type
+/// and converting the columns which keep using it. The old type is dropped later, once the tables
+/// and columns using it are gone.
+///
+/// Returns the table names and column names of the converted columns.
+fn recreate_type(
+  migration: &mut Migration,
+  old_model: &Model,
+  new_model: &Model,
+  old: &EnumBlock,
+  new: &EnumBlock,
+  renamed: &dyn Fn(&QualifiedName) -> QualifiedName,
+) -> Vec<(QualifiedName, String)> {
+  let enum_name = QualifiedName::new(&new.ident.schema, &new.ident.name);
+  let is_enum = |model: &Model, col| {
+    match model.column_kind(col) {
+      ColumnKind::Enum(r#enum) => QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name) == enum_name,
+      _ => false,
+    }
+  };
+
+  migration.push(format!(
+    "ALTER TYPE {} RENAME TO {};\n",
+    qualified_ident(&QualifiedName::new(&old.ident.schema, &old.ident.name), ident),
+    ident(&replaced_type_name(old).name)
+  ));
+  migration.push(create_type(new));
+
+  let mut converted_cols = vec![];
+  for old_table in &old_model.tables {
+    let name = renamed(&table_name(old_table));
+    let Some(new_table) = new_model.tables.iter().find(|table| table_name(table) == name) else {
+      continue;
+    };
+
+    for old_col in &old_table.cols {
+      let Some(new_col) = new_table
+        .cols
+        .iter()
+        .find(|col| col.name.to_string == old_col.name.to_string)
+      else {
+        continue;
+      };
+      if !is_enum(old_model, old_col) || !is_enum(new_model, new_col) {
+        continue;
+      }
+
+      let alter_col = format!(
+        "ALTER TABLE {} ALTER COLUMN {}",
+        qualified_ident(&name, ident),
+        ident(&new_col.name.to_string)
+      );
+
+      if default_of(old_col).is_some() {
+        migration.push(format!("{} DROP DEFAULT;\n", alter_col));
+      }
+      migration.push_destructive(format!("{} TYPE {};\n", alter_col, converted_type(new_model, new_col)));
+      if let Some(default) = default_of(new_col) {
+        migration.push(format!("{} SET DEFAULT {};\n", alter_col, value(default)));
+      }
+
+      converted_cols.push((name.clone(), new_col.name.to_string.clone()));
+    }
+  }
+
+  converted_cols
+}
+
+/// Writes the new type of a column followed by a `USING` clause casting the old values, through
+/// text if the new type is an enum.
+fn converted_type(model: &Model, col: &TableColumn) -> String {
+  let r#type = col_type(model, col);
+  let cast = match model.column_kind(col) {
+    ColumnKind::Enum(_) => format!("::text::{}", r#type),
+    _ => format!("::{}", r#type),
+  };
+
+  format!("{} USING {}{}", r#type, ident(&col.name.to_string), cast)
+}
+
+/// Orders the tables so that referenced tables come before the tables referencing them, following
+/// the foreign keys between them.
+///
+/// Returns the ordered tables and the remaining ones, which are part of or depend on a reference
+/// cycle, in their original order.
+fn dependency_order(tables: &[QualifiedName], foreign_keys: &[ForeignKey]) -> (Vec<QualifiedName>, Vec<QualifiedName>) {
+  let mut ordered = vec![];
+  let mut remaining = tables.to_vec();
+
+  while let Some(i) = remaining.iter().position(|table| {
+    !foreign_keys.iter().any(|foreign_key| {
+      &foreign_key.from == table && foreign_key.to != foreign_key.from && remaining.contains(&foreign_key.to)
+    })
+  }) {
+    ordered.push(remaining.remove(i));
+  }
+
+  (ordered, remaining)
+}
+
+/// Identifies a foreign key by its tables, which are given to allow renaming them, columns, name
+/// and actions.
+fn foreign_key_key(foreign_key: &ForeignKey, from: &QualifiedName, to: &QualifiedName) -> String {
+  format!(
+    "{:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+    from,
+    foreign_key.from_cols,
+    to,
+    foreign_key.to_cols,
+    foreign_key.name,
+    foreign_key.on_delete.as_ref().map(ToString::to_string),
+    foreign_key.on_update.as_ref().map(ToString::to_string)
+  )
+}
+
+/// Gets the name of the foreign key, or the name PostgreSQL gives to an unnamed one.
+fn foreign_key_name(foreign_key: &ForeignKey) -> String {
+  match &foreign_key.name {
+    Some(name) => name.clone(),
+    None => format!("{}_{}_fkey", foreign_key.from.name, foreign_key.from_cols.join("_")),
+  }
+}
+
+/// Gets the name of the index, or the name PostgreSQL gives to an unnamed one.
+fn index_name(table: &QualifiedName, def: &IndexesDef) -> String {
+  if let Some(name) = def.settings.as_ref().and_then(|settings| settings.name.as_ref()) {
+    return name.clone();
+  }
+
+  let mut names: Vec<String> = vec![];
+  for col in &def.cols {
+    let name = match col {
+      IndexesColumnType::String(col) => col.to_string.clone(),
+      IndexesColumnType::Expr(_) => "expr".to_string(),
+    };
+
+    let mut unique_name = name.clone();
+    let mut i = 0;
+    while names.contains(&unique_name) {
+      i += 1;
+      unique_name = format!("{}{}", name, i);
+    }
+    names.push(unique_name);
+  }
+
+  format!("{}_{}_idx", table.name, names.join("_"))
+}
+
+/// Gets the name the old enum type is renamed to while it is being replaced.
+fn replaced_type_name(r#enum: &EnumBlock) -> QualifiedName {
+  let mut name = QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name);
+  name.name += "_old";
+
+  name
+}
+
+fn table_name(table: &TableBlock) -> QualifiedName {
+  QualifiedName::new(&table.ident.schema, &table.ident.name)
+}
+
+fn is_pk_index(def: &IndexesDef) -> bool {
+  def.settings.as_ref().is_some_and(|settings| settings.is_pk)
+}
+
+fn is_unique(col: &TableColumn) -> bool {
+  col.settings.as_ref().is_some_and(|settings| settings.is_unique)
+}
+
+fn is_not_null(col: &TableColumn) -> bool {
+  col
+    .settings
+    .as_ref()
+    .is_some_and(|settings| settings.nullable == Some(Nullable::NotNull))
+}
+
+fn default_of(col: &TableColumn) -> Option<&Value> {
+  col.settings.as_ref().and_then(|settings| settings.default.as_ref())
+}
+
+fn create_type(r#enum: &EnumBlock) -> String {
+  let values: Vec<_> = r#enum
+    .values
+    .iter()
+    .map(|value| quote(&value.value.to_string, '\'', '\''))
+    .collect();
+
+  format!(
+    "CREATE TYPE {} AS ENUM ({});\n",
+    qualified_ident(&QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name), ident),
+    values.join(", ")
+  )
+}
+
+fn create_table(model: &Model, table: &TableBlock) -> String {
+  let pk = primary_key(table);
+  let inline_pk = inline_primary_key(table);
+  let mut defs: Vec<_> = table
+    .cols
+    .iter()
+    .map(|col| col_def(model, col, inline_pk == Some(col.name.to_string.as_str())))
+    .collect();
+
+  if !pk.is_empty() && inline_pk.is_none() {
+    defs.push(format!("PRIMARY KEY ({})", idents(pk, ident)));
+  }
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident),
+    defs.join(",\n  ")
+  )
+}
+
+/// Writes the column definition, declaring the primary key inline only if `is_inline_pk` is set.
+fn col_def(model: &Model, col: &TableColumn, is_inline_pk: bool) -> String {
+  let mut def = format!("{} {}", ident(&col.name.to_string), col_type(model, col));
+
+  if let Some(settings) = &col.settings {
+    if settings.is_incremental && is_integer(model, col) {
+      def += " GENERATED BY DEFAULT AS IDENTITY";
+    }
+    if is_inline_pk {
+      def += " PRIMARY KEY";
+    }
+    if settings.is_unique {
+      def += " UNIQUE";
+    }
+    if settings.nullable == Some(Nullable::NotNull) {
+      def += " NOT NULL";
+    }
+    if let Some(default) = &settings.default {
+      let _ = write!(def, " DEFAULT {}", value(default));
+    }
+  }
+
+  def
+}
+
+fn create_junction_table(model: &Model, junction_table: &JunctionTable) -> String {
+  let mut defs: Vec<_> = junction_table
+    .cols
+    .iter()
+    .map(|(name, col)| format!("{} {}", ident(name), referencing_col_type(model, col)))
+    .collect();
+  defs.push(format!(
+    "PRIMARY KEY ({})",
+    idents(junction_table.cols.iter().map(|(name, _)| name), ident)
+  ));
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    qualified_ident(&junction_table.name, ident),
+    defs.join(",\n  ")
+  )
+}
+
+fn create_indexes(table: &TableBlock) -> Vec<String> {
+  let table_name = qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident);
+
+  indexes(table).map(|def| create_index(&table_name, def)).collect()
+}
+
+fn create_index(table_name: &str, def: &IndexesDef) -> String {
+  let settings = def.settings.as_ref();
+  let mut out = String::from("CREATE ");
+
+  if settings.is_some_and(|settings| settings.is_unique) {
+    out += "UNIQUE ";
+  }
+  out += "INDEX ";
+  if let Some(name) = settings.and_then(|settings| settings.name.as_ref()) {
+    let _ = write!(out, "{} ", ident(name));
+  }
+  let _ = write!(out, "ON {}", table_name);
+  if let Some(r#type) = settings.and_then(|settings| settings.r#type.as_ref()) {
+    let _ = write!(out, " USING {}", r#type);
+  }
+
+  let cols: Vec<_> = def
+    .cols
+    .iter()
+    .map(|col| {
+      match col {
+        IndexesColumnType::String(col) => ident(&col.to_string),
+        IndexesColumnType::Expr(expr) => format!("({})", expr.value),
+      }
+    })
+    .collect();
+
+  format!("{} ({});\n", out, cols.join(", "))
+}
+
+fn comments(table: &TableBlock) -> String {
+  let table_name = qualified_ident(&QualifiedName::new(&table.ident.schema, &table.ident.name), ident);
+  let mut out = String::new();
+
+  if let Some(note) = &table.note {
+    let _ = writeln!(
+      out,
+      "COMMENT ON TABLE {} IS {};",
+      table_name,
+      quote(&note_text(note), '\'', '\'')
+    );
+  }
+
+  for col in &table.cols {
+    if let Some(note) = col.settings.as_ref().and_then(|settings| settings.note.as_ref()) {
+      let _ = writeln!(
+        out,
+        "COMMENT ON COLUMN {}.{} IS {};",
+        table_name,
+        ident(&col.name.to_string),
+        quote(note, '\'', '\'')
+      );
+    }
+  }
+
+  out
+}
+
+/// Maps the column type to a PostgreSQL type, keeping the arguments and array dimensions.
+fn col_type(model: &Model, col: &TableColumn) -> String {
+  let type_name = match model.column_kind(col) {
+    ColumnKind::Builtin(type_name) => type_name.to_string(),
+    ColumnKind::Enum(r#enum) => qualified_ident(&QualifiedName::new(&r#enum.ident.schema, &r#enum.ident.name), ident),
+    ColumnKind::Custom(raw) => raw.to_string(),
+  };
+
+  with_modifiers(col, type_name)
+}
+
+/// Writes the type of a column referencing the given column, which uses the underlying integer
+/// type of a serial column instead of creating another sequence.
+fn referencing_col_type(model: &Model, col: &TableColumn) -> String {
+  let type_name = match model.column_kind(col) {
+    ColumnKind::Builtin(ColumnTypeName::SmallSerial) => ColumnTypeName::SmallInt,
+    ColumnKind::Builtin(ColumnTypeName::Serial) => ColumnTypeName::Integer,
+    ColumnKind::Builtin(ColumnTypeName::BigSerial) => ColumnTypeName::BigInt,
+    _ => return col_type(model, col),
+  };
+
+  with_modifiers(col, type_name.to_string())
+}
+
+/// Appends the type arguments and array dimensions of the column to the type name.
+fn with_modifiers(col: &TableColumn, type_name: String) -> String {
+  let mut out = type_name;
+
+  if !col.r#type.args.is_empty() {
+    let args: Vec<_> = col.r#type.args.iter().map(ToString::to_string).collect();
+
+    let _ = write!(out, "({})", args.join(", "));
+  }
+
+  for array in &col.r#type.arrays {
+    match array {
+      Some(len) => {
+        let _ = write!(out, "[{}]", len);
+      }
+      None => out += "[]",
+    }
+  }
+
+  out
+}
+
+/// Checks if the column can be an identity column.
+fn is_integer(model: &Model, col: &TableColumn) -> bool {
+  matches!(
+    model.column_kind(col),
+    ColumnKind::Builtin(ColumnTypeName::SmallInt | ColumnTypeName::Integer | ColumnTypeName::BigInt)
+  )
+}
+
+fn value(value: &Value) -> String {
+  match value {
+    Value::String(s) | Value::Enum(s) | Value::HexColor(s) => quote(s, '\'', '\''),
+    Value::Expr(expr) => expr.clone(),
+    Value::Bool(b) => b.to_string().to_uppercase(),
+    Value::Null => "NULL".to_string(),
+    value => value.to_string(),
+  }
+}
+
+fn ident(name: &str) -> String {
+  quote(name, '"', '"')
+}
@@ -0,0 +1,307 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::err::Err;
+use super::*;
+
+/// The dialect name shown in diagnostics.
+const DIALECT: &str = "SQLite";
+
+/// Represents how tables outside of the default schema are generated, since SQLite has no schemas.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SchemaMode {
+  /// Flattens the schema into the table name, as in `schema_table`.
+  #[default]
+  Prefix,
+  /// Reports an `UnsupportedSchema` error for each table outside of the default schema.
+  Reject,
+}
+
+/// Represents the options for generating SQLite data definitions.
+#[derive(Debug, Clone, Default)]
+pub struct SqliteOptions {
+  /// How tables outside of the default schema are generated.
+  pub schema_mode: SchemaMode,
+}
+
+/// Generates SQLite data definition statements from the schema.
+///
+/// Column types are mapped to their type affinity (`INTEGER`, `REAL`, `NUMERIC`, `TEXT` or `BLOB`)
+/// and an auto-incremented single primary key becomes `INTEGER PRIMARY KEY AUTOINCREMENT`.
+/// As SQLite cannot add constraints to existing tables, foreign keys are declared inline in
+/// `CREATE TABLE`, and enums are emulated with `CHECK (... IN (...))` constraints.
+///
+/// # Arguments
+///
+/// * `schema_block` - A reference to the unsanitized AST.
+/// * `options` - A reference to the generating options.
+///
+/// # Errors
+///
+/// All diagnostics of the schema if any of them is an error, an `UnsupportedIndexType` error for
+/// each `hash`, `gin` or `gist` index, or an `UnsupportedSchema` error for each table outside of
+/// the default schema when rejecting schemas.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_unchecked;
+/// use dbml_rs::generator::sqlite::{generate, SqliteOptions};
+///
+/// let ast = parse_dbml_unchecked("Project p { database_type: 'SQLite' }\nTable users { id int [pk] }").unwrap();
+/// let sql = generate(&ast, &SqliteOptions::default()).unwrap();
+/// ```
+pub fn generate(schema_block: &SchemaBlock, options: &SqliteOptions) -> Result<String, Vec<Diagnostic>> {
+  let model = Model::new(schema_block)?;
+  let mut diags = vec![];
+  let mut sections = vec![];
+
+  if options.schema_mode == SchemaMode::Reject {
+    for table in &model.tables {
+      if let Some(schema) = table
+        .ident
+        .schema
+        .as_ref()
+        .filter(|schema| schema.to_string != DEFAULT_SCHEMA)
+      {
+        diags.push(Diagnostic::generator(
+          Err::UnsupportedSchema { dialect: DIALECT },
+          &schema.span_range,
+        ));
+      }
+    }
+  }
+
+  sections.extend(model.tables.iter().map(|table| create_table(&model, table)));
+  sections.extend(
+    model
+      .junction_tables
+      .iter()
+      .map(|junction_table| create_junction_table(&model, junction_table)),
+  );
+  sections.push(
+    model
+      .tables
+      .iter()
+      .flat_map(|table| create_indexes(table, &mut diags))
+      .collect(),
+  );
+
+  if !diags.is_empty() {
+    return Err(diags);
+  }
+
+  let sections: Vec<String> = sections.into_iter().filter(|section| !section.is_empty()).collect();
+
+  Ok(sections.join("\n"))
+}
+
+fn create_table(model: &Model, table: &TableBlock) -> String {
+  let name = QualifiedName::new(&table.ident.schema, &table.ident.name);
+  let pk = primary_key(table);
+  let inline_pk = inline_primary_key(table);
+  let mut defs: Vec<_> = table
+    .cols
+    .iter()
+    .map(|col| {
+      let col_name = ident(&col.name.to_string);
+      let mut def = format!("{} {}", col_name, col_type(model, col));
+
+      if let Some(settings) = &col.settings {
+        if inline_pk == Some(col.name.to_string.as_str()) {
+          def += " PRIMARY KEY";
+
+          // `AUTOINCREMENT` is only allowed on an `INTEGER PRIMARY KEY`
+          if (settings.is_incremental || model.is_serial(col)) && col_type(model, col) == "INTEGER" {
+            def += " AUTOINCREMENT";
+          }
+        }
+        if settings.is_unique {
+          def += " UNIQUE";
+        }
+        if settings.nullable == Some(Nullable::NotNull) {
+          def += " NOT NULL";
+        }
+        if let Some(default) = &settings.default {
+          let _ = write!(def, " DEFAULT {}", value(default));
+        }
+      }
+
+      if let ColumnKind::Enum(r#enum) = model.column_kind(col) {
+        let values: Vec<_> = r#enum
+          .values
+          .iter()
+          .map(|value| quote(&value.value.to_string, '\'', '\''))
+          .collect();
+
+        let _ = write!(def, " CHECK ({} IN ({}))", col_name, values.join(", "));
+      }
+
+      def
+    })
+    .collect();
+
+  if !pk.is_empty() && inline_pk.is_none() {
+    defs.push(format!("PRIMARY KEY ({})", idents(pk, ident)));
+  }
+  defs.extend(foreign_keys(model, &name));
+
+  format!("CREATE TABLE {} (\n  {}\n);\n", table_ident(&name), defs.join(",\n  "))
+}
+
+fn create_junction_table(model: &Model, junction_table: &JunctionTable) -> String {
+  let mut defs: Vec<_> = junction_table
+    .cols
+    .iter()
+    .map(|(name, col)| format!("{} {}", ident(name), col_type(model, col)))
+    .collect();
+  defs.push(format!(
+    "PRIMARY KEY ({})",
+    idents(junction_table.cols.iter().map(|(name, _)| name), ident)
+  ));
+  defs.extend(foreign_keys(model, &junction_table.name));
+
+  format!(
+    "CREATE TABLE {} (\n  {}\n);\n",
+    table_ident(&junction_table.name),
+    defs.join(",\n  ")
+  )
+}
+
+/// Writes the foreign key constraints of the table, with the referenced tables flattened.
+fn foreign_keys(model: &Model, table_name: &QualifiedName) -> Vec<String> {
+  model
+    .foreign_keys
+    .iter()
+    .filter(|foreign_key| &foreign_key.from == table_name)
+    .map(|foreign_key| {
+      let foreign_key = ForeignKey {
+        to: flatten(&foreign_key.to),
+        ..foreign_key.clone()
+      };
+
+      foreign_key_constraint(&foreign_key, ident)
+    })
+    .collect()
+}
+
+fn create_indexes(table: &TableBlock, diags: &mut Vec<Diagnostic>) -> Vec<String> {
+  let table_name = flatten(&QualifiedName::new(&table.ident.schema, &table.ident.name));
+
+  indexes(table)
+    .enumerate()
+    .map(|(i, def)| {
+      let settings = def.settings.as_ref();
+      let mut out = String::from("CREATE ");
+
+      if let Some(settings) = settings {
+        if let Some(r#type @ (IndexesType::Hash | IndexesType::Gin | IndexesType::Gist)) = &settings.r#type {
+          let err = Err::UnsupportedIndexType {
+            r#type: r#type.to_string(),
+            dialect: DIALECT,
+          };
+
+          diags.push(Diagnostic::generator(
+            err,
+            setting_span(&settings.attributes, "type", &settings.span_range),
+          ));
+        }
+        if settings.is_unique {
+          out += "UNIQUE ";
+        }
+      }
+
+      // index names are mandatory in SQLite
+      let name = settings
+        .and_then(|settings| settings.name.clone())
+        .unwrap_or_else(|| format!("{}_index_{}", table_name.name, i));
+      let cols: Vec<_> = def
+        .cols
+        .iter()
+        .map(|col| {
+          match col {
+            IndexesColumnType::String(col) => ident(&col.to_string),
+            IndexesColumnType::Expr(expr) => format!("({})", expr.value),
+          }
+        })
+        .collect();
+
+      let _ = writeln!(
+        out,
+        "INDEX {} ON {} ({});",
+        ident(&name),
+        ident(&table_name.name),
+        cols.join(", ")
+      );
+
+      out
+    })
+    .collect()
+}
+
+/// Maps the column type to its SQLite type affinity.
+fn col_type(model: &Model, col: &TableColumn) -> String {
+  // arrays are stored as JSON text
+  if !col.r#type.arrays.is_empty() {
+    return "TEXT".to_string();
+  }
+
+  let affinity = match model.column_kind(col) {
+    ColumnKind::Builtin(type_name) => {
+      match type_name {
+        ColumnTypeName::SmallInt
+        | ColumnTypeName::Integer
+        | ColumnTypeName::BigInt
+        | ColumnTypeName::SmallSerial
+        | ColumnTypeName::Serial
+        | ColumnTypeName::BigSerial
+        | ColumnTypeName::Bool
+        | ColumnTypeName::Bit => "INTEGER",
+        ColumnTypeName::Real | ColumnTypeName::DoublePrecision => "REAL",
+        ColumnTypeName::Decimal | ColumnTypeName::Money => "NUMERIC",
+        ColumnTypeName::ByteArray | ColumnTypeName::Varbit => "BLOB",
+        _ => "TEXT",
+      }
+    }
+    ColumnKind::Enum(_) => "TEXT",
+    // the affinity of other types is derived from their names by SQLite itself
+    ColumnKind::Custom(raw) => return raw.trim().to_string(),
+  };
+
+  affinity.to_string()
+}
+
+fn value(value: &Value) -> String {
+  match value {
+    Value::String(s) | Value::Enum(s) | Value::HexColor(s) => quote(s, '\'', '\''),
+    // expressions must be parenthesized in `DEFAULT`
+    Value::Expr(expr) => format!("({})", expr),
+    Value::Bool(true) => "1".to_string(),
+    Value::Bool(false) => "0".to_string(),
+    Value::Null => "NULL".to_string(),
+    value => value.to_string(),
+  }
+}
+
+/// Moves the schema name into the table name, as SQLite has no schemas.
+fn flatten(name: &QualifiedName) -> QualifiedName {
+  QualifiedName {
+    schema: None,
+    name: match &name.schema {
+      Some(schema) => format!("{}_{}", schema, name.name),
+      None => name.name.clone(),
+    },
+  }
+}
+
+fn table_ident(name: &QualifiedName) -> String {
+  ident(&flatten(name).name)
+}
+
+fn ident(name: &str) -> String {
+  quote(name, '"', '"')
+}
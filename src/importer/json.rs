@@ -0,0 +1,499 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use serde_json::Value as Json;
+
+use super::*;
+use crate::DEFAULT_SCHEMA;
+
+/// Represents a JSON value along with its path from the root, which is reported in errors.
+#[derive(Clone, Copy)]
+struct Node<'a> {
+  value: Option<&'a Json>,
+  parent: Option<&'a Node<'a>>,
+  key: PathKey<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum PathKey<'a> {
+  Root,
+  Field(&'a str),
+  Item(usize),
+}
+
+/// Imports the JSON database model exchanged by `@dbml/core`, as exported by its
+/// `ModelExporter`.
+///
+/// Tables, enums, table groups and refs of every schema are imported, and the name, database
+/// type and note of the database become a project if any of them is set.
+/// The project is named `project` if the database has no name. Since JSON values have no
+/// position, errors about the model are reported at the start of the input along with the
+/// path of the offending value.
+///
+/// # Errors
+///
+/// The first syntax error of the JSON text, or the first value not matching the model.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::importer::json;
+///
+/// let ast = json::import(r#"{ "schemas": [{ "name": "public", "tables": [] }] }"#).unwrap();
+/// let dbml = ast.to_dbml();
+/// ```
+pub fn import(input: &str) -> ImportResult<SchemaBlock<'_>> {
+  let json: Json = match serde_json::from_str(input) {
+    Ok(json) => json,
+    Err(err) => {
+      let offset = offset_of(input, err.line(), err.column());
+      let message = err.to_string();
+      // the position is already given by the span
+      let message = message.split(" at line ").next().unwrap_or_default();
+
+      return throw_syntax(message, &(offset..offset));
+    }
+  };
+
+  let root = Node {
+    value: Some(&json),
+    parent: None,
+    key: PathKey::Root,
+  };
+  let span_range = SpanRange::default();
+  let mut blocks = vec![];
+
+  if let Some(project) = project(&root)? {
+    blocks.push(TopLevelBlock::Project(project));
+  }
+
+  let mut tables = vec![];
+  let mut table_groups = vec![];
+  let mut refs = vec![];
+  for schema_node in root.field("schemas").items()? {
+    let schema = match schema_node.field("name").opt_str()? {
+      Some(name) if name != DEFAULT_SCHEMA => Some(ident(name, &span_range)),
+      _ => None,
+    };
+
+    for enum_node in schema_node.field("enums").items()? {
+      blocks.push(TopLevelBlock::Enum(enum_block(&enum_node, &schema)?));
+    }
+    for table_node in schema_node.field("tables").items()? {
+      tables.push(TopLevelBlock::Table(table(&table_node, &schema)?.into_block()));
+    }
+    for table_group_node in schema_node.field("tableGroups").items()? {
+      table_groups.push(TopLevelBlock::TableGroup(table_group_block(&table_group_node)?));
+    }
+    for ref_node in schema_node.field("refs").items()? {
+      refs.push(TopLevelBlock::Ref(ref_block(&ref_node)?));
+    }
+  }
+  blocks.extend(tables);
+  blocks.extend(table_groups);
+  blocks.extend(refs);
+
+  // the JSON text is not DBML, so it is not kept as the input
+  Ok(SchemaBlock {
+    span_range: SpanRange::default(),
+    input: "",
+    blocks,
+  })
+}
+
+fn project(root: &Node) -> ImportResult<Option<ProjectBlock>> {
+  let span_range = SpanRange::default();
+  let name = root.field("name").opt_str()?;
+  let database_type = root.field("databaseType").opt_str()?;
+  let note = root.field("note").opt_str()?;
+
+  if name.is_none() && database_type.is_none() && note.is_none() {
+    return Ok(None);
+  }
+
+  let mut properties = vec![];
+  if let Some(database_type) = database_type {
+    properties.push(Property {
+      span_range: span_range.clone(),
+      key: ident("database_type", &span_range),
+      value: literal(Value::String(database_type.to_string()), &span_range),
+    });
+  }
+
+  Ok(Some(ProjectBlock {
+    span_range: span_range.clone(),
+    properties,
+    ident: ident(name.unwrap_or("project"), &span_range),
+    database_type: match database_type {
+      Some(database_type) => {
+        DatabaseType::from_str(database_type).or_else(|err| root.field("databaseType").throw(err))?
+      }
+      None => DatabaseType::Undef,
+    },
+    note: note.map(|note| note_block(note, &span_range)),
+  }))
+}
+
+fn table(node: &Node, schema: &Option<Ident>) -> ImportResult<Table> {
+  let span_range = SpanRange::default();
+  let header_color_node = node.field("headerColor");
+  let header_color = match header_color_node.opt_str()? {
+    Some(color) if is_hex_color(color) => Some(color.to_string()),
+    Some(_) => return header_color_node.throw("expected a '#RGB' or '#RRGGBB' color"),
+    None => None,
+  };
+  let mut table = Table {
+    schema: schema.clone(),
+    name: ident(node.field("name").str()?, &span_range),
+    alias: node.field("alias").opt_str()?.map(|alias| ident(alias, &span_range)),
+    note: node.field("note").opt_str()?.map(ToString::to_string),
+    header_color,
+    ..Default::default()
+  };
+
+  for field_node in node.field("fields").items()? {
+    table.cols.push(Column {
+      name: ident(field_node.field("name").str()?, &span_range),
+      r#type: field_type(&field_node.field("type"))?,
+      is_pk: field_node.field("pk").bool()?,
+      is_unique: field_node.field("unique").bool()?,
+      is_incremental: field_node.field("increment").bool()?,
+      nullable: field_node.field("not_null").bool()?.then_some(Nullable::NotNull),
+      default: dbdefault(&field_node.field("dbdefault"))?,
+      note: field_node.field("note").opt_str()?.map(ToString::to_string),
+      ..Default::default()
+    });
+  }
+
+  for index_node in node.field("indexes").items()? {
+    let mut cols = vec![];
+    for col_node in index_node.field("columns").items()? {
+      let value = col_node.field("value").str()?;
+
+      cols.push(match col_node.field("type").str()? {
+        "column" => IndexesColumnType::String(ident(value, &span_range)),
+        _ => IndexesColumnType::Expr(literal(Value::Expr(value.to_string()), &span_range)),
+      });
+    }
+
+    table.indexes.push(Index {
+      cols,
+      name: index_node.field("name").opt_str()?.map(ToString::to_string),
+      is_pk: index_node.field("pk").bool()?,
+      is_unique: index_node.field("unique").bool()?,
+      r#type: match index_node.field("type").opt_str()? {
+        Some(r#type) => Some(IndexesType::from_str(r#type).or_else(|err| index_node.field("type").throw(err))?),
+        None => None,
+      },
+      note: index_node.field("note").opt_str()?.map(ToString::to_string),
+      ..Default::default()
+    });
+  }
+
+  Ok(table)
+}
+
+/// Reads a type such as `varchar(255)` or `int[]`, keeping the case of the type name.
+fn field_type(node: &Node) -> ImportResult<ColumnType> {
+  let type_name = node.field("type_name").str()?.trim();
+  let name_end = type_name.find(['(', '[']).unwrap_or(type_name.len());
+  let mut name = type_name[..name_end].trim().to_string();
+  let mut rest = &type_name[name_end..];
+
+  if let Some(schema) = node.field("schemaName").opt_str()? {
+    if schema != DEFAULT_SCHEMA {
+      name = format!("{}.{}", schema, name);
+    }
+  }
+
+  let mut args = vec![];
+  if let Some(inner) = rest.strip_prefix('(') {
+    let Some(end) = inner.find(')') else {
+      return node.field("type_name").throw("unclosed type arguments");
+    };
+    args = inner[..end]
+      .split(',')
+      .map(|arg| {
+        let arg = arg.trim();
+        match arg.strip_prefix('\'').and_then(|arg| arg.strip_suffix('\'')) {
+          Some(s) => Value::String(s.to_string()),
+          None => number(arg),
+        }
+      })
+      .collect();
+    rest = &inner[end + 1..];
+  }
+
+  let mut arrays = vec![];
+  while let Some(inner) = rest.trim_start().strip_prefix('[') {
+    let Some(end) = inner.find(']') else {
+      return node.field("type_name").throw("unclosed array dimension");
+    };
+    arrays.push(inner[..end].trim().parse().ok());
+    rest = &inner[end + 1..];
+  }
+
+  let mut col_type = ColumnType {
+    type_name: ColumnTypeName::Raw(name),
+    args,
+    arrays,
+    ..Default::default()
+  };
+  col_type.raw = col_type.to_string();
+
+  Ok(col_type)
+}
+
+fn dbdefault(node: &Node) -> ImportResult<Option<Value>> {
+  if node.is_null() {
+    return Ok(None);
+  }
+
+  let value_node = node.field("value");
+  let value = match (node.field("type").str()?, value_node.value) {
+    ("number", Some(Json::Number(number))) => {
+      match number.as_i64() {
+        Some(int) => Value::Integer(int),
+        None => Value::Decimal(number.as_f64().unwrap_or_default()),
+      }
+    }
+    ("number", _) => number(value_node.str()?),
+    ("boolean", Some(Json::Bool(bool))) => Value::Bool(*bool),
+    ("boolean", _) => {
+      match value_node.str()? {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => return value_node.throw("expected 'true', 'false' or 'null'"),
+      }
+    }
+    ("string", _) => Value::String(value_node.str()?.to_string()),
+    ("expression", _) => Value::Expr(value_node.str()?.to_string()),
+    _ => {
+      return node
+        .field("type")
+        .throw("expected 'number', 'boolean', 'string' or 'expression'")
+    }
+  };
+
+  Ok(Some(value))
+}
+
+fn enum_block(node: &Node, schema: &Option<Ident>) -> ImportResult<EnumBlock> {
+  let span_range = SpanRange::default();
+  let mut values = vec![];
+
+  for value_node in node.field("values").items()? {
+    let settings = value_node.field("note").opt_str()?.map(|note| {
+      EnumValueSettings {
+        span_range: span_range.clone(),
+        attributes: vec![attribute("note", Some(Value::String(note.to_string())), &span_range)],
+        note: Some(note.to_string()),
+      }
+    });
+
+    values.push(EnumValue {
+      span_range: span_range.clone(),
+      value: ident(value_node.field("name").str()?, &span_range),
+      settings,
+    });
+  }
+
+  Ok(EnumBlock {
+    span_range: span_range.clone(),
+    ident: EnumIdent {
+      span_range: span_range.clone(),
+      schema: schema.clone(),
+      name: ident(node.field("name").str()?, &span_range),
+    },
+    values,
+  })
+}
+
+fn table_group_block(node: &Node) -> ImportResult<TableGroupBlock> {
+  let span_range = SpanRange::default();
+  let mut items = vec![];
+
+  for table_node in node.field("tables").items()? {
+    items.push(TableGroupItem {
+      span_range: span_range.clone(),
+      schema: schema_ident(&table_node.field("schemaName"))?,
+      ident_alias: ident(table_node.field("tableName").str()?, &span_range),
+    });
+  }
+
+  Ok(TableGroupBlock {
+    span_range: span_range.clone(),
+    ident: ident(node.field("name").str()?, &span_range),
+    items,
+  })
+}
+
+fn ref_block(node: &Node) -> ImportResult<RefBlock> {
+  let span_range = SpanRange::default();
+  let endpoints_node = node.field("endpoints");
+  let endpoints = endpoints_node.items()?;
+  let [lhs, rhs] = endpoints.as_slice() else {
+    return endpoints_node.throw("expected 2 endpoints");
+  };
+
+  let rel = match (lhs.field("relation").str()?, rhs.field("relation").str()?) {
+    ("1", "*") => Relation::One2Many,
+    ("*", "1") => Relation::Many2One,
+    ("1", "1") => Relation::One2One,
+    ("*", "*") => Relation::Many2Many,
+    _ => return endpoints_node.throw("expected relations of '1' or '*'"),
+  };
+  let action = |key: &str| -> ImportResult<Option<ReferentialAction>> {
+    let action_node = node.field(key);
+
+    match action_node.opt_str()? {
+      Some(action) => {
+        ReferentialAction::from_str(&action.to_lowercase())
+          .map(Some)
+          .or_else(|err| action_node.throw(err))
+      }
+      None => Ok(None),
+    }
+  };
+
+  Ok(RefBlock {
+    span_range: span_range.clone(),
+    name: node.field("name").opt_str()?.map(|name| ident(name, &span_range)),
+    rel,
+    lhs: endpoint(lhs)?,
+    rhs: endpoint(rhs)?,
+    settings: ref_settings(action("onDelete")?, action("onUpdate")?, &span_range),
+  })
+}
+
+fn endpoint(node: &Node) -> ImportResult<RefIdent> {
+  let span_range = SpanRange::default();
+  let mut cols = vec![];
+  for field_node in node.field("fieldNames").items()? {
+    cols.push(ident(field_node.str()?, &span_range));
+  }
+
+  Ok(ref_ident(
+    schema_ident(&node.field("schemaName"))?,
+    ident(node.field("tableName").str()?, &span_range),
+    cols,
+    &span_range,
+  ))
+}
+
+fn schema_ident(node: &Node) -> ImportResult<Option<Ident>> {
+  Ok(
+    node
+      .opt_str()?
+      .filter(|schema| schema != &DEFAULT_SCHEMA)
+      .map(|schema| ident(schema, &SpanRange::default())),
+  )
+}
+
+fn note_block(note: &str, span_range: &SpanRange) -> NoteBlock {
+  NoteBlock {
+    span_range: span_range.clone(),
+    value: literal(Value::String(note.to_string()), span_range),
+  }
+}
+
+/// Checks if the color is written as `#RGB` or `#RRGGBB`, as the DBML grammar requires.
+fn is_hex_color(color: &str) -> bool {
+  color
+    .strip_prefix('#')
+    .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Converts the one-based line and column of a JSON error into an offset of the input.
+fn offset_of(input: &str, line: usize, column: usize) -> usize {
+  let line_start: usize = input
+    .split_inclusive('\n')
+    .take(line.saturating_sub(1))
+    .map(str::len)
+    .sum();
+
+  (line_start + column.saturating_sub(1)).min(input.len())
+}
+
+impl<'a> Node<'a> {
+  fn field(&'a self, key: &'a str) -> Node<'a> {
+    Node {
+      value: self.value.and_then(|value| value.get(key)),
+      parent: Some(self),
+      key: PathKey::Field(key),
+    }
+  }
+
+  fn is_null(&self) -> bool {
+    matches!(self.value, None | Some(Json::Null))
+  }
+
+  /// Returns the items of an array, where a missing array is empty.
+  fn items(&'a self) -> ImportResult<Vec<Node<'a>>> {
+    match self.value {
+      Some(Json::Array(items)) => {
+        Ok(
+          items
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+              Node {
+                value: Some(value),
+                parent: Some(self),
+                key: PathKey::Item(i),
+              }
+            })
+            .collect(),
+        )
+      }
+      _ if self.is_null() => Ok(vec![]),
+      _ => self.throw("expected an array"),
+    }
+  }
+
+  fn str(&self) -> ImportResult<&'a str> {
+    match self.value {
+      Some(Json::String(s)) => Ok(s),
+      _ => self.throw("expected a string"),
+    }
+  }
+
+  fn opt_str(&self) -> ImportResult<Option<&'a str>> {
+    match self.is_null() {
+      true => Ok(None),
+      false => self.str().map(Some),
+    }
+  }
+
+  /// Returns a boolean, where a missing boolean is `false`.
+  fn bool(&self) -> ImportResult<bool> {
+    match self.value {
+      Some(Json::Bool(bool)) => Ok(*bool),
+      _ if self.is_null() => Ok(false),
+      _ => self.throw("expected a boolean"),
+    }
+  }
+
+  /// Returns the JSON pointer of the value, such as `/schemas/0/tables/1/name`.
+  fn path(&self) -> String {
+    let mut path = self.parent.map(Node::path).unwrap_or_default();
+    match self.key {
+      PathKey::Root => (),
+      PathKey::Field(key) => path += &format!("/{}", key),
+      PathKey::Item(i) => path += &format!("/{}", i),
+    }
+    path
+  }
+
+  fn throw<T>(&self, message: impl ToString) -> ImportResult<T> {
+    throw_syntax(
+      format!("{} at '{}'", message.to_string(), self.path()),
+      &SpanRange::default(),
+    )
+  }
+}
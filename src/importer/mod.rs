@@ -0,0 +1,1358 @@
+use alloc::boxed::Box;
+use alloc::string::{
+  String,
+  ToString,
+};
+use alloc::vec::Vec;
+
+use crate::ast::*;
+use crate::diagnostic::Diagnostic;
+
+#[cfg(feature = "json")]
+pub mod json;
+pub mod mssql;
+pub mod mysql;
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// The result of importing, which fails at the first syntax error.
+pub type ImportResult<T> = Result<T, Box<Diagnostic>>;
+
+/// Represents the lexical rules of a SQL dialect.
+pub(crate) struct SqlDialect {
+  /// The opening and closing characters of quoted identifiers.
+  pub ident_quotes: &'static [(char, char)],
+  /// Whether backslashes escape characters in string literals.
+  pub backslash_escapes: bool,
+  /// Whether `#` starts a line comment.
+  pub hash_comments: bool,
+  /// Whether `$tag$ ... $tag$` quotes a string literal.
+  pub dollar_quotes: bool,
+  /// Whether a `GO` line separates statements.
+  pub go_separator: bool,
+  /// Whether unquoted identifiers are folded into lowercase.
+  pub fold_lowercase: bool,
+  /// The schema of objects created without a schema name, which is left out of the output.
+  pub default_schema: &'static str,
+}
+
+/// Represents the kind of a SQL token.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum TokenKind {
+  /// A keyword or an unquoted identifier.
+  Word,
+  /// A quoted identifier.
+  QuotedIdent,
+  /// A string literal.
+  String,
+  /// A numeric literal.
+  Number,
+  /// Any other single character.
+  Punct,
+}
+
+/// Represents a SQL token.
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+  pub kind: TokenKind,
+  /// The text of the token without quotes and escapes.
+  pub text: String,
+  /// The range of the span in the source text.
+  pub span_range: SpanRange,
+}
+
+/// Splits the SQL input into tokens, skipping whitespace and comments.
+pub(crate) fn tokenize(input: &str, dialect: &SqlDialect) -> ImportResult<Vec<Token>> {
+  let chars: Vec<(usize, char)> = input.char_indices().collect();
+  let offset_at = |i: usize| chars.get(i).map_or(input.len(), |(offset, _)| *offset);
+  let char_at = |i: usize| chars.get(i).map(|(_, c)| *c);
+  let mut tokens = vec![];
+  let mut i = 0;
+
+  while let Some(c) = char_at(i) {
+    let start = i;
+
+    if c.is_whitespace() {
+      i += 1;
+      continue;
+    }
+
+    // comments
+    if (c == '-' && char_at(i + 1) == Some('-')) || (c == '#' && dialect.hash_comments) {
+      while char_at(i).is_some_and(|c| c != '\n') {
+        i += 1;
+      }
+      continue;
+    }
+    if c == '/' && char_at(i + 1) == Some('*') {
+      i += 2;
+      while !(char_at(i) == Some('*') && char_at(i + 1) == Some('/')) {
+        if char_at(i).is_none() {
+          return throw_syntax("unterminated comment", &(offset_at(start)..input.len()));
+        }
+        i += 1;
+      }
+      i += 2;
+      continue;
+    }
+
+    let (kind, text) = if c == '\'' || (matches!(c, 'E' | 'e' | 'N' | 'n') && char_at(i + 1) == Some('\'')) {
+      let backslash_escapes = dialect.backslash_escapes || matches!(c, 'E' | 'e');
+      if c != '\'' {
+        i += 1;
+      }
+
+      let mut text = String::new();
+      i += 1;
+      loop {
+        match char_at(i) {
+          Some('\'') if char_at(i + 1) == Some('\'') => {
+            text.push('\'');
+            i += 2;
+          }
+          Some('\'') => break,
+          Some('\\') if backslash_escapes => {
+            match char_at(i + 1) {
+              Some('n') => text.push('\n'),
+              Some('t') => text.push('\t'),
+              Some('r') => text.push('\r'),
+              Some('0') => text.push('\0'),
+              Some(c) => text.push(c),
+              None => (),
+            }
+            i += 2;
+          }
+          Some(c) => {
+            text.push(c);
+            i += 1;
+          }
+          None => return throw_syntax("unterminated string literal", &(offset_at(start)..input.len())),
+        }
+      }
+      i += 1;
+
+      (TokenKind::String, text)
+    } else if c == '$' && dialect.dollar_quotes && char_at(i + 1).is_some_and(|c| c == '$' || c.is_alphabetic()) {
+      let tag_end = (i + 1..chars.len()).find(|j| char_at(*j) == Some('$'));
+      let tag: String = match tag_end {
+        Some(tag_end) if (i + 1..tag_end).all(|j| char_at(j).is_some_and(|c| c.is_alphanumeric() || c == '_')) => {
+          chars[i..=tag_end].iter().map(|(_, c)| c).collect()
+        }
+        _ => {
+          return throw_syntax(
+            "invalid dollar-quoted string",
+            &(offset_at(start)..offset_at(start + 1)),
+          )
+        }
+      };
+
+      let body_start = offset_at(i + tag.chars().count());
+      match input[body_start..].find(&tag) {
+        Some(len) => {
+          let end = body_start + len + tag.len();
+          i = chars.partition_point(|(offset, _)| *offset < end);
+
+          (TokenKind::String, input[body_start..body_start + len].to_string())
+        }
+        None => return throw_syntax("unterminated dollar-quoted string", &(offset_at(start)..input.len())),
+      }
+    } else if let Some((_, close)) = dialect.ident_quotes.iter().find(|(open, _)| *open == c) {
+      let mut text = String::new();
+      i += 1;
+      loop {
+        match char_at(i) {
+          Some(c) if c == *close && char_at(i + 1) == Some(*close) => {
+            text.push(c);
+            i += 2;
+          }
+          Some(c) if c == *close => break,
+          Some(c) => {
+            text.push(c);
+            i += 1;
+          }
+          None => return throw_syntax("unterminated quoted identifier", &(offset_at(start)..input.len())),
+        }
+      }
+      i += 1;
+
+      (TokenKind::QuotedIdent, text)
+    } else if c.is_ascii_digit() || (c == '.' && char_at(i + 1).is_some_and(|c| c.is_ascii_digit())) {
+      while char_at(i).is_some_and(|c| c.is_ascii_digit() || c == '.') {
+        i += 1;
+      }
+      if char_at(i).is_some_and(|c| c == 'e' || c == 'E')
+        && char_at(i + 1).is_some_and(|c| c.is_ascii_digit() || c == '-' || c == '+')
+      {
+        i += 2;
+        while char_at(i).is_some_and(|c| c.is_ascii_digit()) {
+          i += 1;
+        }
+      }
+
+      (TokenKind::Number, input[offset_at(start)..offset_at(i)].to_string())
+    } else if c.is_alphabetic() || c == '_' || c == '@' {
+      while char_at(i).is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '@')) {
+        i += 1;
+      }
+
+      (TokenKind::Word, input[offset_at(start)..offset_at(i)].to_string())
+    } else {
+      i += 1;
+
+      (TokenKind::Punct, c.to_string())
+    };
+
+    tokens.push(Token {
+      kind,
+      text,
+      span_range: offset_at(start)..offset_at(i),
+    });
+  }
+
+  Ok(tokens)
+}
+
+/// Splits the tokens into statements separated by semicolons, or `GO` lines if allowed.
+pub(crate) fn split_statements<'a>(input: &str, tokens: &'a [Token], dialect: &SqlDialect) -> Vec<&'a [Token]> {
+  let is_separator = |i: usize, token: &Token| {
+    match token.kind {
+      TokenKind::Punct => token.text == ";",
+      TokenKind::Word if dialect.go_separator && token.text.eq_ignore_ascii_case("GO") => {
+        let line_start = input[..token.span_range.start].rfind('\n').map_or(0, |i| i + 1);
+        let is_line_start = input[line_start..token.span_range.start].trim().is_empty();
+        let is_line_end = match tokens.get(i + 1) {
+          Some(next) => input[token.span_range.end..next.span_range.start].contains('\n'),
+          None => true,
+        };
+
+        is_line_start && is_line_end
+      }
+      _ => false,
+    }
+  };
+
+  let mut statements = vec![];
+  let mut start = 0;
+  for (i, token) in tokens.iter().enumerate() {
+    if is_separator(i, token) {
+      if start < i {
+        statements.push(&tokens[start..i]);
+      }
+      start = i + 1;
+    }
+  }
+  if start < tokens.len() {
+    statements.push(&tokens[start..]);
+  }
+
+  statements
+}
+
+/// Represents a position in the tokens of a statement.
+pub(crate) struct Cursor<'a> {
+  input: &'a str,
+  tokens: &'a [Token],
+  dialect: &'a SqlDialect,
+  default_schema: &'a str,
+  current_schema: Option<Ident>,
+  pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+  pub fn new(input: &'a str, tokens: &'a [Token], dialect: &'a SqlDialect) -> Self {
+    Self {
+      input,
+      tokens,
+      dialect,
+      default_schema: dialect.default_schema,
+      current_schema: None,
+      pos: 0,
+    }
+  }
+
+  /// Sets the schema whose name is left out of the output, and the schema of unqualified names.
+  pub fn with_schemas(mut self, default_schema: &'a str, current_schema: Option<Ident>) -> Self {
+    self.default_schema = default_schema;
+    self.current_schema = current_schema;
+    self
+  }
+
+  pub fn peek(&self) -> Option<&'a Token> {
+    self.tokens.get(self.pos)
+  }
+
+  pub fn peek_nth(&self, n: usize) -> Option<&'a Token> {
+    self.tokens.get(self.pos + n)
+  }
+
+  pub fn next(&mut self) -> Option<&'a Token> {
+    let token = self.tokens.get(self.pos);
+    if token.is_some() {
+      self.pos += 1;
+    }
+
+    token
+  }
+
+  pub fn is_done(&self) -> bool {
+    self.pos >= self.tokens.len()
+  }
+
+  /// Checks if the next tokens are the given keywords, ignoring case.
+  pub fn is_keywords(&self, keywords: &[&str]) -> bool {
+    keywords.iter().enumerate().all(|(i, keyword)| {
+      self
+        .peek_nth(i)
+        .is_some_and(|token| token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case(keyword))
+    })
+  }
+
+  pub fn is_keyword(&self, keyword: &str) -> bool {
+    self.is_keywords(&[keyword])
+  }
+
+  /// Consumes the given keywords if all of them are next.
+  pub fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+    let is_matched = self.is_keywords(keywords);
+    if is_matched {
+      self.pos += keywords.len();
+    }
+
+    is_matched
+  }
+
+  pub fn eat_keyword(&mut self, keyword: &str) -> bool {
+    self.eat_keywords(&[keyword])
+  }
+
+  pub fn expect_keyword(&mut self, keyword: &str) -> ImportResult<()> {
+    match self.eat_keyword(keyword) {
+      true => Ok(()),
+      false => Err(self.error(format!("expected '{}'", keyword))),
+    }
+  }
+
+  pub fn is_punct(&self, punct: &str) -> bool {
+    self
+      .peek()
+      .is_some_and(|token| token.kind == TokenKind::Punct && token.text == punct)
+  }
+
+  pub fn eat_punct(&mut self, punct: &str) -> bool {
+    let is_matched = self.is_punct(punct);
+    if is_matched {
+      self.pos += 1;
+    }
+
+    is_matched
+  }
+
+  pub fn expect_punct(&mut self, punct: &str) -> ImportResult<()> {
+    match self.eat_punct(punct) {
+      true => Ok(()),
+      false => Err(self.error(format!("expected '{}'", punct))),
+    }
+  }
+
+  /// Consumes an identifier, folding its case if it is unquoted and the dialect does so.
+  pub fn ident(&mut self) -> ImportResult<Ident> {
+    match self.peek() {
+      Some(token) if matches!(token.kind, TokenKind::Word | TokenKind::QuotedIdent) => {
+        self.pos += 1;
+
+        let to_string = match token.kind == TokenKind::Word && self.dialect.fold_lowercase {
+          true => token.text.to_lowercase(),
+          false => token.text.clone(),
+        };
+
+        Ok(Ident {
+          span_range: token.span_range.clone(),
+          raw: self.input[token.span_range.clone()].to_string(),
+          to_string,
+        })
+      }
+      _ => Err(self.error("expected an identifier")),
+    }
+  }
+
+  /// Consumes a possibly qualified name, returning its schema and name.
+  ///
+  /// The database part of a three-part name is dropped, and so is the default schema.
+  /// Unqualified names are in the current schema if any.
+  pub fn qualified_name(&mut self) -> ImportResult<(Option<Ident>, Ident)> {
+    let mut parts = vec![self.ident()?];
+    while self.eat_punct(".") {
+      parts.push(self.ident()?);
+    }
+
+    let name = parts.pop().unwrap();
+    let schema = parts
+      .pop()
+      .or_else(|| self.current_schema.clone())
+      .filter(|schema| !schema.to_string.eq_ignore_ascii_case(self.default_schema));
+
+    Ok((schema, name))
+  }
+
+  /// Consumes a parenthesized list of identifiers.
+  pub fn ident_list(&mut self) -> ImportResult<Vec<Ident>> {
+    self.expect_punct("(")?;
+
+    let mut idents = vec![];
+    loop {
+      idents.push(self.ident()?);
+      // sort orders and prefix lengths of key columns are not represented in DBML
+      self.skip_element();
+
+      if !self.eat_punct(",") {
+        break;
+      }
+    }
+    self.expect_punct(")")?;
+
+    Ok(idents)
+  }
+
+  /// Consumes a parenthesized group including nested groups, returning the span inside.
+  pub fn group(&mut self) -> ImportResult<SpanRange> {
+    self.expect_punct("(")?;
+
+    let start = self.pos;
+    self.skip_until(|cursor| cursor.is_punct(")"));
+    let span_range = self.span_of(start, self.pos);
+    self.expect_punct(")")?;
+
+    Ok(span_range)
+  }
+
+  /// Consumes tokens until the predicate holds outside of any parentheses or the tokens end.
+  ///
+  /// Closing parentheses without a matching opening one always stop.
+  pub fn skip_until(&mut self, is_stop: impl Fn(&Self) -> bool) {
+    let mut depth = 0usize;
+
+    while !self.is_done() {
+      if depth == 0 && (is_stop(self) || self.is_punct(")")) {
+        break;
+      }
+      if self.is_punct("(") {
+        depth += 1;
+      } else if self.is_punct(")") {
+        depth -= 1;
+      }
+      self.pos += 1;
+    }
+  }
+
+  /// Consumes an element of a comma-separated list inside parentheses.
+  pub fn skip_element(&mut self) {
+    self.skip_until(|cursor| cursor.is_punct(","));
+  }
+
+  /// Gets the span of the tokens from `start` up to `end` exclusively.
+  pub fn span_of(&self, start: usize, end: usize) -> SpanRange {
+    match (
+      self.tokens.get(start),
+      end.checked_sub(1).and_then(|end| self.tokens.get(end)),
+    ) {
+      (Some(first), Some(last)) if start < end => first.span_range.start..last.span_range.end,
+      _ => self.here()..self.here(),
+    }
+  }
+
+  /// Gets the span of the whole statement.
+  pub fn statement_span(&self) -> SpanRange {
+    self.span_of(0, self.tokens.len())
+  }
+
+  pub fn pos(&self) -> usize {
+    self.pos
+  }
+
+  pub fn text(&self, span_range: &SpanRange) -> &'a str {
+    &self.input[span_range.clone()]
+  }
+
+  /// Creates a syntax error at the next token, or at the end of the statement.
+  pub fn error(&self, message: impl ToString) -> Box<Diagnostic> {
+    let span_range = match self.peek() {
+      Some(token) => token.span_range.clone(),
+      None => self.here()..self.here(),
+    };
+
+    Box::new(Diagnostic::custom_syntax(message, &span_range))
+  }
+
+  fn here(&self) -> usize {
+    self.peek().or_else(|| self.tokens.last()).map_or(0, |token| {
+      match self.peek() {
+        Some(_) => token.span_range.start,
+        None => token.span_range.end,
+      }
+    })
+  }
+}
+
+/// Consumes a data type, stopping before any of the keywords.
+///
+/// Multi-word type names are joined with spaces, and the arguments and array dimensions are
+/// parsed as in DBML, e.g. `character varying(25)[]`.
+pub(crate) fn parse_type(cursor: &mut Cursor, stop_keywords: &[&str]) -> ImportResult<ColumnType> {
+  let start = cursor.pos();
+  let mut words: Vec<String> = vec![];
+  let mut args = vec![];
+  let mut args_span = None;
+  let mut arrays = vec![];
+
+  while let Some(token) = cursor.peek() {
+    match token.kind {
+      TokenKind::Word
+        if stop_keywords
+          .iter()
+          .any(|keyword| token.text.eq_ignore_ascii_case(keyword)) =>
+      {
+        break
+      }
+      TokenKind::Word if token.text.eq_ignore_ascii_case("ARRAY") => {
+        cursor.next();
+        // the size is given in brackets as in `ARRAY[4]`
+        if !cursor.is_punct("[") {
+          arrays.push(None);
+        }
+      }
+      TokenKind::Word | TokenKind::QuotedIdent => {
+        let mut name = match token.kind {
+          TokenKind::Word => token.text.to_lowercase(),
+          _ => token.text.clone(),
+        };
+        cursor.next();
+
+        // a type in another schema
+        if cursor.eat_punct(".") {
+          let ident = cursor.ident()?;
+          match name.eq_ignore_ascii_case(cursor.default_schema) {
+            true => name = ident.to_string,
+            false => name = format!("{}.{}", name, ident.to_string),
+          }
+        }
+
+        // words after the arguments, as in `int(11) unsigned`, are kept in the type name
+        if let Some(args_span) = &args_span {
+          if !args.is_empty() {
+            words = vec![format!("{}{}", words.join(" "), cursor.text(args_span))];
+            args.clear();
+          }
+        }
+
+        words.push(name);
+      }
+      TokenKind::Punct if token.text == "(" && args_span.is_none() && !words.is_empty() => {
+        let args_start = cursor.pos();
+        cursor.next();
+        while !cursor.eat_punct(")") {
+          match cursor.next() {
+            Some(token) if token.kind == TokenKind::Number => args.push(number(&token.text)),
+            Some(token) if token.kind == TokenKind::String => args.push(Value::String(token.text.clone())),
+            Some(token) if token.kind == TokenKind::Word => args.push(Value::Enum(token.text.to_lowercase())),
+            Some(token) if token.text == "," => (),
+            _ => return Err(cursor.error("expected a type argument")),
+          }
+        }
+        args_span = Some(cursor.span_of(args_start, cursor.pos()));
+      }
+      TokenKind::Punct if token.text == "[" => {
+        cursor.next();
+        match cursor.next() {
+          Some(token) if token.text == "]" => arrays.push(None),
+          Some(token) if token.kind == TokenKind::Number => {
+            arrays.push(token.text.parse().ok());
+            cursor.expect_punct("]")?;
+          }
+          _ => return Err(cursor.error("expected an array size")),
+        }
+      }
+      _ => break,
+    }
+  }
+
+  if words.is_empty() {
+    return Err(cursor.error("expected a data type"));
+  }
+
+  let mut col_type = ColumnType {
+    span_range: cursor.span_of(start, cursor.pos()),
+    type_name: ColumnTypeName::Raw(words.join(" ")),
+    args,
+    arrays,
+    ..Default::default()
+  };
+  col_type.raw = col_type.to_string();
+
+  Ok(col_type)
+}
+
+/// Consumes a default value, stopping before any of the keywords.
+///
+/// Literals become the matching values, with PostgreSQL casts (`'a'::text`) and the parentheses
+/// SQL Server puts around defaults removed. Anything else becomes an expression.
+pub(crate) fn parse_default(cursor: &mut Cursor, stop_keywords: &[&str]) -> ImportResult<Value> {
+  let start = cursor.pos();
+  // the first token is never a stop keyword, as in `DEFAULT NULL`
+  if cursor.peek().is_some_and(|token| token.kind != TokenKind::Punct) {
+    cursor.next();
+  }
+  cursor.skip_until(|cursor| cursor.is_punct(",") || stop_keywords.iter().any(|keyword| cursor.is_keyword(keyword)));
+  let end = cursor.pos();
+
+  if start == end {
+    return Err(cursor.error("expected a default value"));
+  }
+
+  let mut tokens = &cursor.tokens[start..end];
+  while let [first, inner @ .., last] = tokens {
+    if first.text == "(" && last.text == ")" && first.kind == TokenKind::Punct && is_balanced(inner) {
+      tokens = inner;
+    } else {
+      break;
+    }
+  }
+
+  let literal = match tokens.iter().position(|token| token.text == ":") {
+    Some(i) if tokens.get(i + 1).is_some_and(|token| token.text == ":") => &tokens[..i],
+    _ => tokens,
+  };
+  let value = match literal {
+    [token] if token.kind == TokenKind::String => Value::String(token.text.clone()),
+    [token] if token.kind == TokenKind::Number => number(&token.text),
+    [sign, token] if sign.text == "-" && token.kind == TokenKind::Number => number(&format!("-{}", token.text)),
+    [token] if token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case("true") => Value::Bool(true),
+    [token] if token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case("false") => Value::Bool(false),
+    [token] if token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case("null") => Value::Null,
+    [] => return Err(cursor.error("expected a default value")),
+    _ => {
+      let span_range = tokens[0].span_range.start..tokens[tokens.len() - 1].span_range.end;
+
+      Value::Expr(cursor.text(&span_range).to_string())
+    }
+  };
+
+  Ok(value)
+}
+
+/// Consumes the referenced table and columns after `REFERENCES`.
+pub(crate) fn parse_references(
+  cursor: &mut Cursor,
+  name: Option<Ident>,
+  from: RefIdent,
+  span_range: SpanRange,
+) -> ImportResult<Ref> {
+  let (schema, table) = cursor.qualified_name()?;
+  let cols = match cursor.is_punct("(") {
+    true => cursor.ident_list()?,
+    false => vec![],
+  };
+  let (on_delete, on_update) = parse_referential_actions(cursor)?;
+
+  Ok(Ref {
+    to: ref_ident(schema, table, cols, &span_range),
+    span_range,
+    name,
+    from,
+    on_delete,
+    on_update,
+  })
+}
+
+/// Consumes the `ON DELETE` and `ON UPDATE` actions of a foreign key along with the other options.
+pub(crate) fn parse_referential_actions(
+  cursor: &mut Cursor,
+) -> ImportResult<(Option<ReferentialAction>, Option<ReferentialAction>)> {
+  let mut on_delete = None;
+  let mut on_update = None;
+
+  loop {
+    if cursor.eat_keywords(&["ON", "DELETE"]) {
+      on_delete = Some(parse_referential_action(cursor)?);
+    } else if cursor.eat_keywords(&["ON", "UPDATE"]) {
+      on_update = Some(parse_referential_action(cursor)?);
+    } else if cursor.eat_keyword("MATCH") || cursor.eat_keyword("INITIALLY") {
+      cursor.next();
+    } else if !(cursor.eat_keyword("DEFERRABLE")
+      || cursor.eat_keywords(&["NOT", "DEFERRABLE"])
+      || cursor.eat_keywords(&["NOT", "VALID"])
+      || cursor.eat_keywords(&["NOT", "FOR", "REPLICATION"]))
+    {
+      break;
+    }
+  }
+
+  Ok((on_delete, on_update))
+}
+
+fn parse_referential_action(cursor: &mut Cursor) -> ImportResult<ReferentialAction> {
+  if cursor.eat_keyword("CASCADE") {
+    Ok(ReferentialAction::Cascade)
+  } else if cursor.eat_keyword("RESTRICT") {
+    Ok(ReferentialAction::Restrict)
+  } else if cursor.eat_keywords(&["NO", "ACTION"]) {
+    Ok(ReferentialAction::NoAction)
+  } else if cursor.eat_keywords(&["SET", "NULL"]) {
+    Ok(ReferentialAction::SetNull)
+  } else if cursor.eat_keywords(&["SET", "DEFAULT"]) {
+    Ok(ReferentialAction::SetDefault)
+  } else {
+    Err(cursor.error("expected a referential action"))
+  }
+}
+
+/// Consumes the parenthesized key parts of an index, either columns or expressions.
+pub(crate) fn parse_index_cols(cursor: &mut Cursor) -> ImportResult<Vec<IndexesColumnType>> {
+  cursor.expect_punct("(")?;
+
+  let mut cols = vec![];
+  loop {
+    let start = cursor.pos();
+    cursor.skip_element();
+    let tokens = &cursor.tokens[start..cursor.pos()];
+
+    // a column possibly followed by its sort order, operator class or prefix length
+    let is_col = match tokens {
+      [first, rest @ ..] if matches!(first.kind, TokenKind::Word | TokenKind::QuotedIdent) => {
+        match rest {
+          [] => true,
+          [open, len, close, ..] if open.text == "(" => len.kind == TokenKind::Number && close.text == ")",
+          [next, ..] => next.kind == TokenKind::Word,
+        }
+      }
+      _ => false,
+    };
+
+    if is_col {
+      let mut col_cursor = Cursor::new(cursor.input, tokens, cursor.dialect);
+      cols.push(IndexesColumnType::String(col_cursor.ident()?));
+    } else {
+      let mut span_range = cursor.span_of(start, cursor.pos());
+      if let [first, inner @ .., last] = tokens {
+        if first.text == "(" && last.text == ")" && is_balanced(inner) {
+          span_range = cursor.span_of(start + 1, cursor.pos() - 1);
+        }
+      }
+
+      cols.push(IndexesColumnType::Expr(Literal {
+        span_range: span_range.clone(),
+        raw: cursor.text(&span_range).to_string(),
+        value: Value::Expr(cursor.text(&span_range).to_string()),
+      }));
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+  cursor.expect_punct(")")?;
+
+  Ok(cols)
+}
+
+pub(crate) fn throw_syntax<T>(message: impl ToString, span_range: &SpanRange) -> ImportResult<T> {
+  Err(Box::new(Diagnostic::custom_syntax(message, span_range)))
+}
+
+fn is_balanced(tokens: &[Token]) -> bool {
+  let mut depth = 0i32;
+
+  for token in tokens.iter().filter(|token| token.kind == TokenKind::Punct) {
+    match token.text.as_str() {
+      "(" => depth += 1,
+      ")" => depth -= 1,
+      _ => (),
+    }
+    if depth < 0 {
+      return false;
+    }
+  }
+
+  depth == 0
+}
+
+fn number(text: &str) -> Value {
+  match text.parse() {
+    Ok(integer) => Value::Integer(integer),
+    Err(_) => {
+      text
+        .parse()
+        .map_or_else(|_| Value::Expr(text.to_string()), Value::Decimal)
+    }
+  }
+}
+
+/// Represents the schema being imported, which is turned into DBML blocks at the end.
+#[derive(Debug, Default)]
+pub(crate) struct Schema {
+  pub enums: Vec<Enum>,
+  pub tables: Vec<Table>,
+  pub refs: Vec<Ref>,
+}
+
+/// Represents an imported table.
+#[derive(Debug, Default)]
+pub(crate) struct Table {
+  pub span_range: SpanRange,
+  pub schema: Option<Ident>,
+  pub name: Ident,
+  pub alias: Option<Ident>,
+  pub cols: Vec<Column>,
+  pub indexes: Vec<Index>,
+  pub note: Option<String>,
+  pub header_color: Option<String>,
+}
+
+/// Represents an imported column.
+#[derive(Debug, Default)]
+pub(crate) struct Column {
+  pub span_range: SpanRange,
+  pub name: Ident,
+  pub r#type: ColumnType,
+  pub is_pk: bool,
+  pub is_unique: bool,
+  pub is_incremental: bool,
+  pub nullable: Option<Nullable>,
+  pub default: Option<Value>,
+  pub note: Option<String>,
+}
+
+/// Represents an imported index or a primary key or unique constraint over several columns.
+#[derive(Debug, Default)]
+pub(crate) struct Index {
+  pub span_range: SpanRange,
+  pub name: Option<String>,
+  pub cols: Vec<IndexesColumnType>,
+  pub is_pk: bool,
+  pub is_unique: bool,
+  pub r#type: Option<IndexesType>,
+  pub note: Option<String>,
+}
+
+/// Represents an imported enum.
+#[derive(Debug, Default)]
+pub(crate) struct Enum {
+  pub span_range: SpanRange,
+  pub schema: Option<Ident>,
+  pub name: Ident,
+  pub values: Vec<Ident>,
+}
+
+/// Represents an imported foreign key, pointing from the referencing columns to the referenced
+/// columns.
+#[derive(Debug, Default)]
+pub(crate) struct Ref {
+  pub span_range: SpanRange,
+  pub name: Option<Ident>,
+  pub from: RefIdent,
+  pub to: RefIdent,
+  pub on_delete: Option<ReferentialAction>,
+  pub on_update: Option<ReferentialAction>,
+}
+
+impl Schema {
+  /// Finds the table with the given schema and name.
+  pub fn table_mut(&mut self, schema: &Option<Ident>, name: &Ident) -> Option<&mut Table> {
+    let schema = schema.as_ref().map(|schema| &schema.to_string);
+
+    self
+      .tables
+      .iter_mut()
+      .find(|table| table.name.to_string == name.to_string && table.schema.as_ref().map(|s| &s.to_string) == schema)
+  }
+
+  /// Removes `NOT NULL` from the single-column primary keys, where it is implied in DBML.
+  pub fn drop_implied_not_null(&mut self) {
+    for col in self.tables.iter_mut().flat_map(|table| table.cols.iter_mut()) {
+      if col.is_pk && col.nullable == Some(Nullable::NotNull) {
+        col.nullable = None;
+      }
+    }
+  }
+
+  /// Turns the imported schema into DBML blocks: enums first, then tables and refs.
+  ///
+  /// The AST has an empty input, as the imported text is not DBML and its comments must not be
+  /// picked up when formatting. All spans are cleared accordingly, so that diagnostics of the AST
+  /// stay within the input.
+  pub fn into_schema_block(self) -> SchemaBlock<'static> {
+    let mut blocks = vec![];
+
+    for r#enum in self.enums {
+      blocks.push(TopLevelBlock::Enum(EnumBlock {
+        span_range: r#enum.span_range.clone(),
+        ident: EnumIdent {
+          span_range: r#enum.span_range,
+          schema: r#enum.schema,
+          name: r#enum.name,
+        },
+        values: r#enum
+          .values
+          .into_iter()
+          .map(|value| {
+            EnumValue {
+              span_range: value.span_range.clone(),
+              value,
+              settings: None,
+            }
+          })
+          .collect(),
+      }));
+    }
+
+    let refs: Vec<_> = self
+      .refs
+      .into_iter()
+      .map(|r#ref| r#ref.into_block(&self.tables))
+      .collect();
+    blocks.extend(
+      self
+        .tables
+        .into_iter()
+        .map(|table| TopLevelBlock::Table(table.into_block())),
+    );
+    blocks.extend(refs.into_iter().map(TopLevelBlock::Ref));
+    clear_spans(&mut blocks);
+
+    SchemaBlock {
+      span_range: SpanRange::default(),
+      input: "",
+      blocks,
+    }
+  }
+}
+
+impl Table {
+  pub fn col_mut(&mut self, name: &Ident) -> Option<&mut Column> {
+    self.cols.iter_mut().find(|col| col.name.to_string == name.to_string)
+  }
+
+  /// Adds a primary key, which is kept on the column if there is only one.
+  pub fn add_primary_key(&mut self, cols: Vec<Ident>, span_range: SpanRange) {
+    match cols.as_slice() {
+      [col] if self.col_mut(col).is_some() => self.col_mut(col).unwrap().is_pk = true,
+      _ => {
+        self.indexes.push(Index {
+          span_range,
+          cols: cols.into_iter().map(IndexesColumnType::String).collect(),
+          is_pk: true,
+          ..Default::default()
+        })
+      }
+    }
+  }
+
+  /// Adds a unique constraint, which is kept on the column if there is only one.
+  pub fn add_unique(&mut self, name: Option<String>, cols: Vec<Ident>, span_range: SpanRange) {
+    match cols.as_slice() {
+      [col] if self.col_mut(col).is_some() => self.col_mut(col).unwrap().is_unique = true,
+      _ => {
+        self.indexes.push(Index {
+          span_range,
+          name,
+          cols: cols.into_iter().map(IndexesColumnType::String).collect(),
+          is_unique: true,
+          ..Default::default()
+        })
+      }
+    }
+  }
+
+  fn primary_key(&self) -> Vec<Ident> {
+    let col_pk = self.cols.iter().filter(|col| col.is_pk).map(|col| col.name.clone());
+    let index_pk = self
+      .indexes
+      .iter()
+      .filter(|index| index.is_pk)
+      .flat_map(|index| index.cols.iter())
+      .filter_map(|col| {
+        match col {
+          IndexesColumnType::String(ident) => Some(ident.clone()),
+          IndexesColumnType::Expr(_) => None,
+        }
+      });
+
+    col_pk.chain(index_pk).collect()
+  }
+
+  fn into_block(self) -> TableBlock {
+    let indexes = match self.indexes.is_empty() {
+      true => None,
+      false => {
+        Some(IndexesBlock {
+          span_range: self.span_range.clone(),
+          defs: self.indexes.into_iter().map(Index::into_def).collect(),
+        })
+      }
+    };
+
+    let settings = self.header_color.map(|color| {
+      TableSettings {
+        span_range: self.span_range.clone(),
+        attributes: vec![attribute("headercolor", Some(Value::HexColor(color)), &self.span_range)],
+      }
+    });
+
+    TableBlock {
+      span_range: self.span_range.clone(),
+      cols: self.cols.into_iter().map(Column::into_col).collect(),
+      ident: TableIdent {
+        span_range: self.name.span_range.clone(),
+        name: self.name,
+        schema: self.schema,
+        alias: self.alias,
+      },
+      note: self.note.map(|note| {
+        NoteBlock {
+          span_range: self.span_range.clone(),
+          value: literal(Value::String(note), &self.span_range),
+        }
+      }),
+      indexes,
+      settings,
+    }
+  }
+}
+
+impl Column {
+  fn into_col(self) -> TableColumn {
+    let span_range = &self.span_range;
+    let nullable = self.nullable;
+
+    let mut attributes = vec![];
+    if self.is_pk {
+      attributes.push(attribute("pk", None, span_range));
+    }
+    if self.is_incremental {
+      attributes.push(attribute("increment", None, span_range));
+    }
+    match nullable {
+      Some(Nullable::NotNull) => attributes.push(attribute("not null", None, span_range)),
+      Some(Nullable::Null) => attributes.push(attribute("null", None, span_range)),
+      None => (),
+    }
+    if self.is_unique {
+      attributes.push(attribute("unique", None, span_range));
+    }
+    if let Some(default) = &self.default {
+      attributes.push(attribute("default", Some(default.clone()), span_range));
+    }
+    if let Some(note) = &self.note {
+      attributes.push(attribute("note", Some(Value::String(note.clone())), span_range));
+    }
+
+    let settings = match attributes.is_empty() {
+      true => None,
+      false => {
+        Some(ColumnSettings {
+          span_range: span_range.clone(),
+          attributes,
+          is_pk: self.is_pk,
+          is_unique: self.is_unique,
+          nullable,
+          is_incremental: self.is_incremental,
+          note: self.note,
+          default: self.default,
+          refs: vec![],
+        })
+      }
+    };
+
+    TableColumn {
+      span_range: self.span_range,
+      name: self.name,
+      r#type: self.r#type,
+      settings,
+    }
+  }
+}
+
+impl Index {
+  fn into_def(self) -> IndexesDef {
+    let span_range = &self.span_range;
+
+    let mut attributes = vec![];
+    if self.is_pk {
+      attributes.push(attribute("pk", None, span_range));
+    }
+    if let Some(r#type) = &self.r#type {
+      attributes.push(attribute("type", Some(Value::Enum(r#type.to_string())), span_range));
+    }
+    if self.is_unique {
+      attributes.push(attribute("unique", None, span_range));
+    }
+    if let Some(name) = &self.name {
+      attributes.push(attribute("name", Some(Value::String(name.clone())), span_range));
+    }
+    if let Some(note) = &self.note {
+      attributes.push(attribute("note", Some(Value::String(note.clone())), span_range));
+    }
+
+    let settings = match attributes.is_empty() {
+      true => None,
+      false => {
+        Some(IndexesSettings {
+          span_range: span_range.clone(),
+          attributes,
+          r#type: self.r#type,
+          is_unique: self.is_unique,
+          is_pk: self.is_pk,
+          note: self.note,
+          name: self.name,
+        })
+      }
+    };
+
+    IndexesDef {
+      span_range: self.span_range,
+      cols: self.cols,
+      settings,
+    }
+  }
+}
+
+impl Ref {
+  /// Turns the foreign key into a one-to-many ref from the referenced columns.
+  ///
+  /// If the referenced columns are omitted, the primary key of the referenced table is used.
+  fn into_block(mut self, tables: &[Table]) -> RefBlock {
+    if self.to.compositions.is_empty() {
+      let schema = self.to.schema.as_ref().map(|schema| &schema.to_string);
+
+      if let Some(table) = tables.iter().find(|table| {
+        table.name.to_string == self.to.table.to_string && table.schema.as_ref().map(|s| &s.to_string) == schema
+      }) {
+        self.to.compositions = table.primary_key();
+      }
+    }
+
+    RefBlock {
+      settings: ref_settings(self.on_delete, self.on_update, &self.span_range),
+      span_range: self.span_range,
+      name: self.name,
+      rel: Relation::One2Many,
+      lhs: self.to,
+      rhs: self.from,
+    }
+  }
+}
+
+/// Creates the settings of a ref from its referential actions, if any.
+pub(crate) fn ref_settings(
+  on_delete: Option<ReferentialAction>,
+  on_update: Option<ReferentialAction>,
+  span_range: &SpanRange,
+) -> Option<RefSettings> {
+  let mut attributes = vec![];
+  if let Some(action) = &on_delete {
+    attributes.push(attribute("delete", Some(Value::Enum(action.to_string())), span_range));
+  }
+  if let Some(action) = &on_update {
+    attributes.push(attribute("update", Some(Value::Enum(action.to_string())), span_range));
+  }
+
+  match attributes.is_empty() {
+    true => None,
+    false => {
+      Some(RefSettings {
+        span_range: span_range.clone(),
+        attributes,
+        on_delete,
+        on_update,
+      })
+    }
+  }
+}
+
+/// Declares an enum with the values allowed in a column and returns the new type of the column.
+///
+/// The enum is named `<table>_<column>_enum`, prefixed with the schema of the table if any.
+pub(crate) fn inline_enum(
+  enums: &mut Vec<Enum>,
+  table: &Table,
+  col_name: &Ident,
+  values: Vec<String>,
+  span_range: &SpanRange,
+) -> ColumnType {
+  let name = match &table.schema {
+    Some(schema) => {
+      format!(
+        "{}_{}_{}_enum",
+        schema.to_string, table.name.to_string, col_name.to_string
+      )
+    }
+    None => format!("{}_{}_enum", table.name.to_string, col_name.to_string),
+  };
+
+  enums.push(Enum {
+    span_range: span_range.clone(),
+    schema: None,
+    name: ident(&name, span_range),
+    values: values.iter().map(|value| ident(value, span_range)).collect(),
+  });
+
+  ColumnType {
+    span_range: span_range.clone(),
+    raw: name.clone(),
+    type_name: ColumnTypeName::Raw(name),
+    ..Default::default()
+  }
+}
+
+/// Creates an identifier which is not found in the source text as is.
+pub(crate) fn ident(name: &str, span_range: &SpanRange) -> Ident {
+  Ident {
+    span_range: span_range.clone(),
+    raw: name.to_string(),
+    to_string: name.to_string(),
+  }
+}
+
+/// Creates a ref identifier for the columns of the table.
+pub(crate) fn ref_ident(schema: Option<Ident>, table: Ident, cols: Vec<Ident>, span_range: &SpanRange) -> RefIdent {
+  RefIdent {
+    span_range: span_range.clone(),
+    schema,
+    table,
+    compositions: cols,
+  }
+}
+
+fn attribute(key: &str, value: Option<Value>, span_range: &SpanRange) -> Attribute {
+  Attribute {
+    span_range: span_range.clone(),
+    key: ident(key, span_range),
+    value: value.map(|value| literal(value, span_range)),
+  }
+}
+
+fn literal(value: Value, span_range: &SpanRange) -> Literal {
+  Literal {
+    span_range: span_range.clone(),
+    raw: value.to_string(),
+    value,
+  }
+}
+
+/// Resets the spans of the blocks to `0..0`, for an AST not parsed from its input.
+fn clear_spans(blocks: &mut [TopLevelBlock]) {
+  fn clear_ident(ident: &mut Ident) {
+    ident.span_range = SpanRange::default();
+  }
+
+  fn clear_attributes(attributes: &mut [Attribute]) {
+    for attr in attributes {
+      attr.span_range = SpanRange::default();
+      clear_ident(&mut attr.key);
+      if let Some(value) = &mut attr.value {
+        value.span_range = SpanRange::default();
+      }
+    }
+  }
+
+  fn clear_ref_ident(ref_ident: &mut RefIdent) {
+    ref_ident.span_range = SpanRange::default();
+    ref_ident.schema.iter_mut().for_each(clear_ident);
+    clear_ident(&mut ref_ident.table);
+    ref_ident.compositions.iter_mut().for_each(clear_ident);
+  }
+
+  fn clear_note(note: &mut NoteBlock) {
+    note.span_range = SpanRange::default();
+    note.value.span_range = SpanRange::default();
+  }
+
+  for block in blocks {
+    match block {
+      TopLevelBlock::Project(project) => {
+        project.span_range = SpanRange::default();
+        clear_ident(&mut project.ident);
+        for prop in &mut project.properties {
+          prop.span_range = SpanRange::default();
+          clear_ident(&mut prop.key);
+          prop.value.span_range = SpanRange::default();
+        }
+        project.note.iter_mut().for_each(clear_note);
+      }
+      TopLevelBlock::Table(table) => {
+        table.span_range = SpanRange::default();
+        table.ident.span_range = SpanRange::default();
+        clear_ident(&mut table.ident.name);
+        table.ident.schema.iter_mut().for_each(clear_ident);
+        table.ident.alias.iter_mut().for_each(clear_ident);
+        for col in &mut table.cols {
+          col.span_range = SpanRange::default();
+          clear_ident(&mut col.name);
+          col.r#type.span_range = SpanRange::default();
+          if let Some(settings) = &mut col.settings {
+            settings.span_range = SpanRange::default();
+            clear_attributes(&mut settings.attributes);
+            for ref_inline in &mut settings.refs {
+              ref_inline.span_range = SpanRange::default();
+              clear_ref_ident(&mut ref_inline.rhs);
+            }
+          }
+        }
+        table.note.iter_mut().for_each(clear_note);
+        if let Some(indexes) = &mut table.indexes {
+          indexes.span_range = SpanRange::default();
+          for def in &mut indexes.defs {
+            def.span_range = SpanRange::default();
+            for col in &mut def.cols {
+              match col {
+                IndexesColumnType::String(ident) => clear_ident(ident),
+                IndexesColumnType::Expr(expr) => expr.span_range = SpanRange::default(),
+              }
+            }
+            if let Some(settings) = &mut def.settings {
+              settings.span_range = SpanRange::default();
+              clear_attributes(&mut settings.attributes);
+            }
+          }
+        }
+        if let Some(settings) = &mut table.settings {
+          settings.span_range = SpanRange::default();
+          clear_attributes(&mut settings.attributes);
+        }
+      }
+      TopLevelBlock::TableGroup(table_group) => {
+        table_group.span_range = SpanRange::default();
+        clear_ident(&mut table_group.ident);
+        for item in &mut table_group.items {
+          item.span_range = SpanRange::default();
+          item.schema.iter_mut().for_each(clear_ident);
+          clear_ident(&mut item.ident_alias);
+        }
+      }
+      TopLevelBlock::Ref(r#ref) => {
+        r#ref.span_range = SpanRange::default();
+        r#ref.name.iter_mut().for_each(clear_ident);
+        clear_ref_ident(&mut r#ref.lhs);
+        clear_ref_ident(&mut r#ref.rhs);
+        if let Some(settings) = &mut r#ref.settings {
+          settings.span_range = SpanRange::default();
+          clear_attributes(&mut settings.attributes);
+        }
+      }
+      TopLevelBlock::Enum(r#enum) => {
+        r#enum.span_range = SpanRange::default();
+        r#enum.ident.span_range = SpanRange::default();
+        r#enum.ident.schema.iter_mut().for_each(clear_ident);
+        clear_ident(&mut r#enum.ident.name);
+        for value in &mut r#enum.values {
+          value.span_range = SpanRange::default();
+          clear_ident(&mut value.value);
+          if let Some(settings) = &mut value.settings {
+            settings.span_range = SpanRange::default();
+            clear_attributes(&mut settings.attributes);
+          }
+        }
+      }
+    }
+  }
+}
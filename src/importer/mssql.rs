@@ -0,0 +1,513 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::*;
+
+const DIALECT: SqlDialect = SqlDialect {
+  ident_quotes: &[('[', ']'), ('"', '"')],
+  backslash_escapes: false,
+  hash_comments: false,
+  dollar_quotes: false,
+  go_separator: true,
+  fold_lowercase: false,
+  default_schema: "dbo",
+};
+
+/// The keywords ending a data type or a default value in a column definition.
+const COLUMN_KEYWORDS: &[&str] = &[
+  "CONSTRAINT",
+  "NOT",
+  "NULL",
+  "DEFAULT",
+  "IDENTITY",
+  "PRIMARY",
+  "UNIQUE",
+  "FOREIGN",
+  "REFERENCES",
+  "CHECK",
+  "COLLATE",
+  "ROWGUIDCOL",
+  "SPARSE",
+  "FILESTREAM",
+  "GENERATED",
+  "HIDDEN",
+  "MASKED",
+  "ENCRYPTED",
+  "WITH",
+];
+
+/// The parameters of `sp_addextendedproperty` in their positional order.
+const PROPERTY_PARAMS: [&str; 8] = [
+  "@name",
+  "@value",
+  "@level0type",
+  "@level0name",
+  "@level1type",
+  "@level1name",
+  "@level2type",
+  "@level2name",
+];
+
+/// Imports SQL Server data definition scripts, such as the ones generated by SQL Server
+/// Management Studio.
+///
+/// The supported statements are `CREATE TABLE`, `CREATE [UNIQUE] INDEX`, `ALTER TABLE` adding
+/// columns, defaults or constraints, and `sp_addextendedproperty` calls describing tables or
+/// columns, which become notes. Other statements are skipped. Statements are separated by
+/// semicolons or `GO` lines, and objects in the `dbo` schema are imported without a schema name.
+/// Columns checked with `CHECK (col IN (...))` become enums named `<table>_<column>_enum`,
+/// prefixed with the schema name outside of the default schema.
+///
+/// # Arguments
+///
+/// * `input` - The SQL text, which the span of a syntax error points into. The spans of the
+///   returned AST are all empty, as it is not parsed from DBML.
+///
+/// # Errors
+///
+/// A syntax error if a supported statement cannot be parsed.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::importer::mssql::import;
+///
+/// let ast = import("CREATE TABLE [dbo].[users] ([id] int IDENTITY(1,1) NOT NULL PRIMARY KEY)\nGO").unwrap();
+/// ```
+pub fn import(input: &str) -> ImportResult<SchemaBlock<'_>> {
+  let tokens = tokenize(input, &DIALECT)?;
+  let mut schema = Schema::default();
+
+  for statement in split_statements(input, &tokens, &DIALECT) {
+    let mut cursor = Cursor::new(input, statement, &DIALECT);
+
+    if cursor.eat_keyword("CREATE") {
+      if cursor.eat_keyword("TABLE") {
+        create_table(&mut cursor, &mut schema)?;
+      } else if cursor.is_keyword("INDEX")
+        || cursor.is_keyword("UNIQUE")
+        || cursor.is_keyword("CLUSTERED")
+        || cursor.is_keyword("NONCLUSTERED")
+      {
+        create_index(&mut cursor, &mut schema)?;
+      }
+    } else if cursor.eat_keywords(&["ALTER", "TABLE"]) {
+      alter_table(&mut cursor, &mut schema)?;
+    } else if cursor.eat_keyword("EXEC") || cursor.eat_keyword("EXECUTE") {
+      exec(&mut cursor, &mut schema)?;
+    }
+  }
+
+  // generated scripts declare the columns of primary keys as `NOT NULL`
+  schema.drop_implied_not_null();
+
+  Ok(schema.into_schema_block())
+}
+
+fn create_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let (table_schema, name) = cursor.qualified_name()?;
+  let mut table = Table {
+    span_range: cursor.statement_span(),
+    schema: table_schema,
+    name,
+    ..Default::default()
+  };
+
+  // `CREATE TABLE ... AS FileTable` has no definitions to import
+  if !cursor.is_punct("(") {
+    return Ok(());
+  }
+  cursor.expect_punct("(")?;
+  elements(cursor, &mut table, schema)?;
+  cursor.expect_punct(")")?;
+
+  // `ON [PRIMARY]`, `TEXTIMAGE_ON` and `WITH (...)` options are skipped
+  schema.tables.push(table);
+
+  Ok(())
+}
+
+/// Consumes the comma-separated column definitions and table constraints.
+fn elements(cursor: &mut Cursor, table: &mut Table, schema: &mut Schema) -> ImportResult<()> {
+  loop {
+    if is_table_constraint(cursor) {
+      table_constraint(cursor, table, schema)?;
+    } else {
+      column(cursor, table, schema)?;
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+
+  Ok(())
+}
+
+fn is_table_constraint(cursor: &Cursor) -> bool {
+  [
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "FOREIGN",
+    "CHECK",
+    "DEFAULT",
+    "INDEX",
+    "PERIOD",
+  ]
+  .iter()
+  .any(|keyword| cursor.is_keyword(keyword))
+}
+
+fn column(cursor: &mut Cursor, table: &mut Table, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = cursor.ident()?;
+
+  // computed columns have no DBML equivalent
+  if cursor.eat_keyword("AS") {
+    cursor.skip_element();
+    return Ok(());
+  }
+
+  let mut col = Column {
+    name,
+    r#type: parse_type(cursor, COLUMN_KEYWORDS)?,
+    ..Default::default()
+  };
+
+  loop {
+    let constraint_name = match cursor.eat_keyword("CONSTRAINT") {
+      true => Some(cursor.ident()?),
+      false => None,
+    };
+
+    if cursor.eat_keywords(&["NOT", "NULL"]) {
+      col.nullable = Some(Nullable::NotNull);
+    } else if cursor.eat_keyword("NULL") {
+      col.nullable = Some(Nullable::Null);
+    } else if cursor.eat_keyword("DEFAULT") {
+      col.default = Some(parse_default(cursor, COLUMN_KEYWORDS)?);
+    } else if cursor.eat_keyword("IDENTITY") {
+      if cursor.is_punct("(") {
+        cursor.group()?;
+      }
+      cursor.eat_keywords(&["NOT", "FOR", "REPLICATION"]);
+      col.is_incremental = true;
+    } else if cursor.eat_keywords(&["PRIMARY", "KEY"]) {
+      clustering(cursor);
+      col.is_pk = true;
+    } else if cursor.eat_keyword("UNIQUE") {
+      clustering(cursor);
+      col.is_unique = true;
+    } else if cursor.eat_keyword("REFERENCES") || cursor.eat_keywords(&["FOREIGN", "KEY", "REFERENCES"]) {
+      let span_range = cursor.span_of(start, cursor.pos());
+      let from = ref_ident(
+        table.schema.clone(),
+        table.name.clone(),
+        vec![col.name.clone()],
+        &span_range,
+      );
+
+      schema
+        .refs
+        .push(parse_references(cursor, constraint_name, from, span_range)?);
+    } else if cursor.eat_keyword("CHECK") {
+      cursor.eat_keywords(&["NOT", "FOR", "REPLICATION"]);
+      let span_range = cursor.group()?;
+
+      if let Some(values) = check_values(cursor, &span_range, &col.name) {
+        col.r#type = inline_enum(&mut schema.enums, table, &col.name, values, &span_range);
+      }
+    } else if cursor.eat_keyword("COLLATE") {
+      cursor.ident()?;
+    } else if cursor.eat_keyword("ROWGUIDCOL")
+      || cursor.eat_keyword("SPARSE")
+      || cursor.eat_keyword("FILESTREAM")
+      || cursor.eat_keyword("HIDDEN")
+    {
+      continue;
+    } else if cursor.eat_keywords(&["GENERATED", "ALWAYS", "AS"]) {
+      // the period columns of temporal tables, as in `GENERATED ALWAYS AS ROW START`
+      let _ =
+        cursor.eat_keyword("ROW") || cursor.eat_keyword("TRANSACTION_ID") || cursor.eat_keyword("SEQUENCE_NUMBER");
+      let _ = cursor.eat_keyword("START") || cursor.eat_keyword("END");
+    } else if cursor.eat_keyword("MASKED") || cursor.eat_keyword("ENCRYPTED") {
+      cursor.expect_keyword("WITH")?;
+      cursor.group()?;
+    } else if constraint_name.is_some() {
+      return Err(cursor.error("expected a column constraint"));
+    } else {
+      break;
+    }
+  }
+
+  if !cursor.is_done() && !cursor.is_punct(",") && !cursor.is_punct(")") {
+    return Err(cursor.error("expected ',' or ')'"));
+  }
+
+  col.span_range = cursor.span_of(start, cursor.pos());
+  table.cols.push(col);
+
+  Ok(())
+}
+
+fn table_constraint(cursor: &mut Cursor, table: &mut Table, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = match cursor.eat_keyword("CONSTRAINT") {
+    true => Some(cursor.ident()?),
+    false => None,
+  };
+
+  if cursor.eat_keywords(&["PRIMARY", "KEY"]) {
+    clustering(cursor);
+    let cols = cursor.ident_list()?;
+    table.add_primary_key(cols, cursor.span_of(start, cursor.pos()));
+  } else if cursor.eat_keyword("UNIQUE") {
+    clustering(cursor);
+    let cols = cursor.ident_list()?;
+    table.add_unique(
+      name.map(|name| name.to_string),
+      cols,
+      cursor.span_of(start, cursor.pos()),
+    );
+  } else if cursor.eat_keywords(&["FOREIGN", "KEY"]) {
+    let cols = cursor.ident_list()?;
+    cursor.expect_keyword("REFERENCES")?;
+
+    let span_range = cursor.span_of(start, cursor.pos());
+    let from = ref_ident(table.schema.clone(), table.name.clone(), cols, &span_range);
+    schema.refs.push(parse_references(cursor, name, from, span_range)?);
+  } else if cursor.eat_keyword("CHECK") {
+    cursor.eat_keywords(&["NOT", "FOR", "REPLICATION"]);
+    let span_range = cursor.group()?;
+
+    // a check listing the values of a column makes it an enum
+    let checked_col = table
+      .cols
+      .iter()
+      .map(|col| col.name.clone())
+      .find(|col_name| check_values(cursor, &span_range, col_name).is_some());
+    if let Some(col_name) = checked_col {
+      let values = check_values(cursor, &span_range, &col_name).unwrap_or_default();
+      let r#type = inline_enum(&mut schema.enums, table, &col_name, values, &span_range);
+
+      if let Some(col) = table.col_mut(&col_name) {
+        col.r#type = r#type;
+      }
+    }
+  } else if cursor.eat_keyword("DEFAULT") {
+    // a default added to an existing column, as in `DEFAULT ((1)) FOR [quantity]`
+    let default = parse_default(cursor, &["FOR"])?;
+    cursor.expect_keyword("FOR")?;
+    let col_name = cursor.ident()?;
+
+    match table.col_mut(&col_name) {
+      Some(col) => col.default = Some(default),
+      None => {
+        return throw_syntax(
+          format!("column '{}' is not defined", col_name.to_string),
+          &col_name.span_range,
+        )
+      }
+    }
+  } else if cursor.eat_keyword("INDEX") {
+    let index_name = cursor.ident()?.to_string;
+    let is_unique = cursor.eat_keyword("UNIQUE");
+    clustering(cursor);
+
+    table.indexes.push(Index {
+      name: Some(index_name),
+      cols: parse_index_cols(cursor)?,
+      is_unique,
+      span_range: cursor.span_of(start, cursor.pos()),
+      ..Default::default()
+    });
+  } else if cursor.eat_keywords(&["PERIOD", "FOR", "SYSTEM_TIME"]) {
+    cursor.group()?;
+  } else {
+    return Err(cursor.error("expected a table constraint"));
+  }
+
+  // index options, as in `WITH (PAD_INDEX = OFF) ON [PRIMARY]`
+  cursor.skip_element();
+
+  Ok(())
+}
+
+/// Consumes the optional `CLUSTERED` or `NONCLUSTERED` of an index, which is not represented in
+/// DBML.
+fn clustering(cursor: &mut Cursor) {
+  let _ = cursor.eat_keyword("CLUSTERED") || cursor.eat_keyword("NONCLUSTERED");
+}
+
+/// Gets the values allowed in the column by a check, either written as `col IN ('a', 'b')` or as
+/// `col = 'a' OR col = 'b'`.
+fn check_values(cursor: &Cursor, span_range: &SpanRange, col_name: &Ident) -> Option<Vec<String>> {
+  let tokens: Vec<_> = cursor
+    .tokens
+    .iter()
+    .filter(|token| token.span_range.start >= span_range.start && token.span_range.end <= span_range.end)
+    .filter(|token| !(token.kind == TokenKind::Punct && (token.text == "(" || token.text == ")")))
+    .collect();
+  let is_col =
+    |token: &Token| matches!(token.kind, TokenKind::Word | TokenKind::QuotedIdent) && token.text == col_name.to_string;
+  let is_keyword =
+    |token: &Token, keyword: &str| token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case(keyword);
+
+  let values: Vec<_> = match tokens.as_slice() {
+    [col, r#in, values @ ..] if is_col(col) && is_keyword(r#in, "IN") => {
+      let is_list = values.iter().skip(1).step_by(2).all(|token| token.text == ",");
+      let values = values
+        .iter()
+        .step_by(2)
+        .map(|token| (token.kind == TokenKind::String).then(|| token.text.clone()))
+        .collect::<Option<_>>();
+
+      values.filter(|_| is_list)?
+    }
+    tokens => {
+      tokens
+        .split(|token| is_keyword(token, "OR"))
+        .map(|chunk| {
+          match chunk {
+            [col, eq, value] if is_col(col) && eq.text == "=" && value.kind == TokenKind::String => {
+              Some(value.text.clone())
+            }
+            _ => None,
+          }
+        })
+        .collect::<Option<_>>()?
+    }
+  };
+
+  (!values.is_empty()).then_some(values)
+}
+
+fn create_index(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let is_unique = cursor.eat_keyword("UNIQUE");
+  clustering(cursor);
+  cursor.expect_keyword("INDEX")?;
+
+  let name = Some(cursor.ident()?.to_string);
+  cursor.expect_keyword("ON")?;
+  let (table_schema, table_name) = cursor.qualified_name()?;
+  let cols = parse_index_cols(cursor)?;
+
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+
+  // `INCLUDE`, `WHERE` and `WITH` clauses are skipped
+  table.indexes.push(Index {
+    span_range: cursor.span_of(start, cursor.pos()),
+    name,
+    cols,
+    is_unique,
+    ..Default::default()
+  });
+
+  Ok(())
+}
+
+fn alter_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let (table_schema, table_name) = cursor.qualified_name()?;
+  let Some(i) = schema.tables.iter().position(|table| {
+    table.name.to_string == table_name.to_string
+      && table.schema.as_ref().map(|s| &s.to_string) == table_schema.as_ref().map(|s| &s.to_string)
+  }) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+
+  let _ = cursor.eat_keywords(&["WITH", "CHECK"]) || cursor.eat_keywords(&["WITH", "NOCHECK"]);
+
+  // `ALTER COLUMN`, `DROP`, `CHECK CONSTRAINT` and other actions are skipped
+  if cursor.eat_keyword("ADD") {
+    // the table is taken out while its constraints add enums and refs to the schema
+    let mut table = schema.tables.remove(i);
+    let result = elements(cursor, &mut table, schema);
+    schema.tables.insert(i, table);
+
+    result?;
+  }
+
+  Ok(())
+}
+
+/// Consumes a stored procedure call, where `sp_addextendedproperty` adds a note to a table or a
+/// column.
+fn exec(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let (_, procedure) = cursor.qualified_name()?;
+  if !procedure.to_string.eq_ignore_ascii_case("sp_addextendedproperty") {
+    return Ok(());
+  }
+
+  let mut args: [Option<&Token>; 8] = Default::default();
+  let mut i = 0;
+  while let Some(token) = cursor.next() {
+    // arguments are either positional or named, as in `@value = N'...'`
+    if token.kind == TokenKind::Word && token.text.starts_with('@') {
+      cursor.expect_punct("=")?;
+      i = PROPERTY_PARAMS
+        .iter()
+        .position(|param| token.text.eq_ignore_ascii_case(param))
+        .unwrap_or(PROPERTY_PARAMS.len());
+      continue;
+    }
+
+    if let Some(arg) = args.get_mut(i) {
+      *arg = Some(token);
+    }
+    i += 1;
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+
+  let arg = |i: usize| args[i].map(|token| token.text.as_str());
+  let is_type = |i: usize, r#type: &str| arg(i).is_some_and(|arg| arg.eq_ignore_ascii_case(r#type));
+  let (Some(value), Some(table_token)) = (args[1], args[5]) else {
+    return Ok(());
+  };
+  if !is_type(4, "TABLE") {
+    return Ok(());
+  }
+
+  let table_schema = args[3]
+    .filter(|_| is_type(2, "SCHEMA"))
+    .map(|token| ident(&token.text, &token.span_range))
+    .filter(|table_schema| !table_schema.to_string.eq_ignore_ascii_case(DIALECT.default_schema));
+  let table_name = ident(&table_token.text, &table_token.span_range);
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+
+  match args[7].filter(|_| is_type(6, "COLUMN")) {
+    Some(col_token) => {
+      let col_name = ident(&col_token.text, &col_token.span_range);
+
+      match table.col_mut(&col_name) {
+        Some(col) => col.note = Some(value.text.clone()),
+        None => {
+          return throw_syntax(
+            format!("column '{}' is not defined", col_name.to_string),
+            &col_name.span_range,
+          )
+        }
+      }
+    }
+    None => table.note = Some(value.text.clone()),
+  }
+
+  Ok(())
+}
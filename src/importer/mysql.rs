@@ -0,0 +1,446 @@
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use super::*;
+
+const DIALECT: SqlDialect = SqlDialect {
+  ident_quotes: &[('`', '`')],
+  backslash_escapes: true,
+  hash_comments: true,
+  dollar_quotes: false,
+  go_separator: false,
+  fold_lowercase: false,
+  default_schema: "",
+};
+
+/// The keywords ending a data type or a default value in a column definition.
+const COLUMN_KEYWORDS: &[&str] = &[
+  "CONSTRAINT",
+  "NOT",
+  "NULL",
+  "DEFAULT",
+  "AUTO_INCREMENT",
+  "PRIMARY",
+  "KEY",
+  "UNIQUE",
+  "COMMENT",
+  "COLLATE",
+  "CHARACTER",
+  "CHARSET",
+  "ON",
+  "REFERENCES",
+  "CHECK",
+  "GENERATED",
+  "AS",
+  "VISIBLE",
+  "INVISIBLE",
+  "SRID",
+  "COLUMN_FORMAT",
+  "STORAGE",
+  "FIRST",
+  "AFTER",
+];
+
+/// Represents the databases selected by `USE` statements.
+#[derive(Default)]
+struct Databases {
+  /// The first selected database, whose tables are imported without a schema name.
+  default: Option<String>,
+  /// The currently selected database if it is not the default one.
+  current: Option<Ident>,
+}
+
+/// Imports MySQL or MariaDB data definition statements, such as the ones dumped by `mysqldump`.
+///
+/// The supported statements are `CREATE TABLE`, `CREATE [UNIQUE] INDEX` and `ALTER TABLE`
+/// adding columns, indexes or constraints. Other statements are skipped.
+/// Inline `ENUM(...)` columns become enums named `<table>_<column>_enum`, prefixed with the
+/// database name outside of the default database.
+///
+/// Tables in other databases are imported into schemas of the same name. The first database
+/// selected by `USE` is the default one, so a dump of a single database has no schemas.
+///
+/// # Arguments
+///
+/// * `input` - The SQL text, which the span of a syntax error points into. The spans of the
+///   returned AST are all empty, as it is not parsed from DBML.
+///
+/// # Errors
+///
+/// A syntax error if a supported statement cannot be parsed.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::importer::mysql::import;
+///
+/// let ast = import("CREATE TABLE `users` (`id` int NOT NULL AUTO_INCREMENT, PRIMARY KEY (`id`));").unwrap();
+/// ```
+pub fn import(input: &str) -> ImportResult<SchemaBlock<'_>> {
+  let tokens = tokenize(input, &DIALECT)?;
+  let mut schema = Schema::default();
+  let mut databases = Databases::default();
+
+  for statement in split_statements(input, &tokens, &DIALECT) {
+    let mut cursor = Cursor::new(input, statement, &DIALECT);
+
+    if cursor.eat_keyword("USE") {
+      let database = cursor.ident()?;
+
+      match &databases.default {
+        Some(default) => databases.current = Some(database).filter(|database| &database.to_string != default),
+        None => databases.default = Some(database.to_string),
+      }
+      continue;
+    }
+
+    let default = databases.default.as_deref().unwrap_or(DIALECT.default_schema);
+    let mut cursor = cursor.with_schemas(default, databases.current.clone());
+
+    if cursor.eat_keyword("CREATE") {
+      cursor.eat_keyword("TEMPORARY");
+
+      if cursor.eat_keyword("TABLE") {
+        create_table(&mut cursor, &mut schema)?;
+      } else if cursor.is_keyword("INDEX")
+        || cursor.is_keywords(&["UNIQUE", "INDEX"])
+        || cursor.is_keywords(&["FULLTEXT", "INDEX"])
+        || cursor.is_keywords(&["SPATIAL", "INDEX"])
+      {
+        create_index(&mut cursor, &mut schema)?;
+      }
+    } else if cursor.eat_keywords(&["ALTER", "TABLE"]) {
+      alter_table(&mut cursor, &mut schema)?;
+    }
+  }
+
+  Ok(schema.into_schema_block())
+}
+
+fn create_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  cursor.eat_keywords(&["IF", "NOT", "EXISTS"]);
+
+  let (table_schema, name) = cursor.qualified_name()?;
+  let mut table = Table {
+    span_range: cursor.statement_span(),
+    schema: table_schema,
+    name,
+    ..Default::default()
+  };
+
+  // `CREATE TABLE ... LIKE` and `AS SELECT` have no definitions to import
+  if !cursor.is_punct("(") {
+    return Ok(());
+  }
+  cursor.expect_punct("(")?;
+
+  loop {
+    if is_table_constraint(cursor) {
+      table_constraint(cursor, &mut table, schema)?;
+    } else {
+      column(cursor, &mut table, schema)?;
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+  cursor.expect_punct(")")?;
+
+  // table options, as in `ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='...'`
+  while !cursor.is_done() {
+    if cursor.eat_keyword("COMMENT") {
+      cursor.eat_punct("=");
+      table.note = Some(string(cursor)?);
+    } else {
+      cursor.next();
+    }
+  }
+
+  schema.tables.push(table);
+
+  Ok(())
+}
+
+fn is_table_constraint(cursor: &Cursor) -> bool {
+  [
+    "CONSTRAINT",
+    "PRIMARY",
+    "UNIQUE",
+    "KEY",
+    "INDEX",
+    "FULLTEXT",
+    "SPATIAL",
+    "FOREIGN",
+    "CHECK",
+  ]
+  .iter()
+  .any(|keyword| cursor.is_keyword(keyword))
+}
+
+fn column(cursor: &mut Cursor, table: &mut Table, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = cursor.ident()?;
+  let mut r#type = parse_type(cursor, COLUMN_KEYWORDS)?;
+
+  // inline enums are declared as separate enums named after the column
+  if matches!(&r#type.type_name, ColumnTypeName::Raw(raw) if raw == "enum") {
+    let values = r#type.args.iter().map(|arg| arg.to_string()).collect();
+    r#type = inline_enum(&mut schema.enums, table, &name, values, &r#type.span_range);
+  }
+
+  let mut col = Column {
+    name,
+    r#type,
+    ..Default::default()
+  };
+
+  loop {
+    let constraint_name = match cursor.eat_keyword("CONSTRAINT") {
+      true if !cursor.is_keyword("CHECK") => Some(cursor.ident()?),
+      _ => None,
+    };
+
+    if cursor.eat_keywords(&["NOT", "NULL"]) {
+      col.nullable = Some(Nullable::NotNull);
+    } else if cursor.eat_keyword("NULL") {
+      col.nullable = Some(Nullable::Null);
+    } else if cursor.eat_keyword("DEFAULT") {
+      col.default = Some(parse_default(cursor, COLUMN_KEYWORDS)?);
+    } else if cursor.eat_keyword("AUTO_INCREMENT") {
+      col.is_incremental = true;
+    } else if cursor.eat_keywords(&["PRIMARY", "KEY"]) || cursor.eat_keyword("KEY") {
+      col.is_pk = true;
+    } else if cursor.eat_keyword("UNIQUE") {
+      cursor.eat_keyword("KEY");
+      col.is_unique = true;
+    } else if cursor.eat_keyword("COMMENT") {
+      col.note = Some(string(cursor)?);
+    } else if cursor.eat_keyword("COLLATE")
+      || cursor.eat_keywords(&["CHARACTER", "SET"])
+      || cursor.eat_keyword("CHARSET")
+    {
+      cursor.ident()?;
+    } else if cursor.eat_keywords(&["ON", "UPDATE"]) {
+      parse_default(cursor, COLUMN_KEYWORDS)?;
+    } else if cursor.eat_keyword("REFERENCES") {
+      let span_range = cursor.span_of(start, cursor.pos());
+      let from = ref_ident(
+        table.schema.clone(),
+        table.name.clone(),
+        vec![col.name.clone()],
+        &span_range,
+      );
+
+      schema
+        .refs
+        .push(parse_references(cursor, constraint_name, from, span_range)?);
+    } else if cursor.eat_keyword("CHECK") {
+      cursor.group()?;
+      let _ = cursor.eat_keyword("ENFORCED") || cursor.eat_keywords(&["NOT", "ENFORCED"]);
+    } else if cursor.eat_keywords(&["GENERATED", "ALWAYS", "AS"]) || cursor.eat_keyword("AS") {
+      // generated columns have no DBML equivalent
+      cursor.group()?;
+      let _ = cursor.eat_keyword("VIRTUAL") || cursor.eat_keyword("STORED");
+    } else if cursor.eat_keyword("VISIBLE") || cursor.eat_keyword("INVISIBLE") || cursor.eat_keyword("FIRST") {
+      continue;
+    } else if cursor.eat_keyword("AFTER") {
+      cursor.ident()?;
+    } else if cursor.eat_keyword("SRID") || cursor.eat_keyword("COLUMN_FORMAT") || cursor.eat_keyword("STORAGE") {
+      cursor.next();
+    } else if constraint_name.is_some() {
+      return Err(cursor.error("expected a column constraint"));
+    } else {
+      break;
+    }
+  }
+
+  if !cursor.is_done() && !cursor.is_punct(",") && !cursor.is_punct(")") {
+    return Err(cursor.error("expected ',' or ')'"));
+  }
+
+  col.span_range = cursor.span_of(start, cursor.pos());
+  table.cols.push(col);
+
+  Ok(())
+}
+
+fn table_constraint(cursor: &mut Cursor, table: &mut Table, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = match cursor.eat_keyword("CONSTRAINT") {
+    true
+      if !["PRIMARY", "UNIQUE", "FOREIGN", "CHECK"]
+        .iter()
+        .any(|keyword| cursor.is_keyword(keyword)) =>
+    {
+      Some(cursor.ident()?)
+    }
+    _ => None,
+  };
+
+  if cursor.eat_keywords(&["PRIMARY", "KEY"]) {
+    index_type(cursor)?;
+    let cols = cursor.ident_list()?;
+    table.add_primary_key(cols, cursor.span_of(start, cursor.pos()));
+  } else if cursor.eat_keyword("UNIQUE") {
+    let is_index = cursor.eat_keyword("KEY") || cursor.eat_keyword("INDEX");
+    let index_name = index_name(cursor)?;
+    let r#type = index_type(cursor)?;
+
+    match is_index || index_name.is_some() || r#type.is_some() {
+      true => {
+        let mut index = index(cursor, index_name.or(name.map(|name| name.to_string)), r#type)?;
+        index.is_unique = true;
+        index.span_range = cursor.span_of(start, cursor.pos());
+        table.indexes.push(index);
+      }
+      false => {
+        let cols = cursor.ident_list()?;
+        table.add_unique(
+          name.map(|name| name.to_string),
+          cols,
+          cursor.span_of(start, cursor.pos()),
+        );
+      }
+    }
+  } else if cursor.eat_keyword("KEY") || cursor.eat_keyword("INDEX") {
+    let index_name = index_name(cursor)?;
+    let r#type = index_type(cursor)?;
+
+    let mut index = index(cursor, index_name, r#type)?;
+    index.span_range = cursor.span_of(start, cursor.pos());
+    table.indexes.push(index);
+  } else if cursor.eat_keyword("FULLTEXT") || cursor.eat_keyword("SPATIAL") {
+    let _ = cursor.eat_keyword("KEY") || cursor.eat_keyword("INDEX");
+    let index_name = index_name(cursor)?;
+
+    // full-text and spatial indexes have no DBML type
+    let mut index = index(cursor, index_name, None)?;
+    index.span_range = cursor.span_of(start, cursor.pos());
+    table.indexes.push(index);
+  } else if cursor.eat_keywords(&["FOREIGN", "KEY"]) {
+    index_name(cursor)?;
+    let cols = cursor.ident_list()?;
+    cursor.expect_keyword("REFERENCES")?;
+
+    let span_range = cursor.span_of(start, cursor.pos());
+    let from = ref_ident(table.schema.clone(), table.name.clone(), cols, &span_range);
+    schema.refs.push(parse_references(cursor, name, from, span_range)?);
+  } else if cursor.eat_keyword("CHECK") {
+    cursor.skip_element();
+  } else {
+    return Err(cursor.error("expected a table constraint"));
+  }
+
+  // index options, such as `COMMENT`, `KEY_BLOCK_SIZE` or `VISIBLE`
+  cursor.skip_element();
+
+  Ok(())
+}
+
+/// Consumes the optional name of an index before its columns or type.
+fn index_name(cursor: &mut Cursor) -> ImportResult<Option<String>> {
+  match cursor.is_punct("(") || cursor.is_keyword("USING") || cursor.is_keyword("ON") {
+    true => Ok(None),
+    false => Ok(Some(cursor.ident()?.to_string)),
+  }
+}
+
+/// Consumes the optional `USING BTREE` or `USING HASH` of an index.
+fn index_type(cursor: &mut Cursor) -> ImportResult<Option<IndexesType>> {
+  match cursor.eat_keyword("USING") {
+    true => Ok(cursor.ident()?.to_string.to_lowercase().parse().ok()),
+    false => Ok(None),
+  }
+}
+
+/// Consumes the key parts of an index, followed by its type if not given before.
+fn index(cursor: &mut Cursor, name: Option<String>, r#type: Option<IndexesType>) -> ImportResult<Index> {
+  let cols = parse_index_cols(cursor)?;
+  let r#type = match r#type {
+    Some(r#type) => Some(r#type),
+    None => index_type(cursor)?,
+  };
+
+  Ok(Index {
+    name,
+    cols,
+    r#type,
+    ..Default::default()
+  })
+}
+
+fn create_index(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let start = cursor.pos();
+  let is_unique = cursor.eat_keyword("UNIQUE");
+  let _ = cursor.eat_keyword("FULLTEXT") || cursor.eat_keyword("SPATIAL");
+  cursor.expect_keyword("INDEX")?;
+
+  let name = Some(cursor.ident()?.to_string);
+  let r#type = index_type(cursor)?;
+  cursor.expect_keyword("ON")?;
+  let (table_schema, table_name) = cursor.qualified_name()?;
+
+  let mut index = index(cursor, name, r#type)?;
+  index.is_unique = is_unique;
+  index.span_range = cursor.span_of(start, cursor.pos());
+
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+  table.indexes.push(index);
+
+  Ok(())
+}
+
+fn alter_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let (table_schema, table_name) = cursor.qualified_name()?;
+  let Some(i) = schema.tables.iter().position(|table| {
+    table.name.to_string == table_name.to_string
+      && table.schema.as_ref().map(|s| &s.to_string) == table_schema.as_ref().map(|s| &s.to_string)
+  }) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+  // the table is taken out while its constraints add enums and refs to the schema
+  let mut table = schema.tables.remove(i);
+
+  loop {
+    if cursor.eat_keyword("ADD") {
+      if is_table_constraint(cursor) {
+        table_constraint(cursor, &mut table, schema)?;
+      } else {
+        cursor.eat_keyword("COLUMN");
+        column(cursor, &mut table, schema)?;
+      }
+    } else {
+      // `MODIFY`, `DROP`, `AUTO_INCREMENT=...` and other actions are skipped
+      cursor.skip_element();
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+
+  schema.tables.insert(i, table);
+
+  Ok(())
+}
+
+fn string(cursor: &mut Cursor) -> ImportResult<String> {
+  match cursor.peek() {
+    Some(token) if token.kind == TokenKind::String => {
+      cursor.next();
+      Ok(token.text.clone())
+    }
+    _ => Err(cursor.error("expected a string literal")),
+  }
+}
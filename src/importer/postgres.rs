@@ -0,0 +1,465 @@
+use alloc::string::ToString;
+
+use super::*;
+
+const DIALECT: SqlDialect = SqlDialect {
+  ident_quotes: &[('"', '"')],
+  backslash_escapes: false,
+  hash_comments: false,
+  dollar_quotes: true,
+  go_separator: false,
+  fold_lowercase: true,
+  default_schema: crate::DEFAULT_SCHEMA,
+};
+
+/// The keywords ending a data type or a default value in a column definition.
+const COLUMN_KEYWORDS: &[&str] = &[
+  "CONSTRAINT",
+  "NOT",
+  "NULL",
+  "DEFAULT",
+  "PRIMARY",
+  "UNIQUE",
+  "REFERENCES",
+  "CHECK",
+  "GENERATED",
+  "COLLATE",
+];
+
+/// Imports PostgreSQL data definition statements, such as the ones dumped by `pg_dump`.
+///
+/// The supported statements are `CREATE TABLE`, `CREATE TYPE ... AS ENUM`,
+/// `CREATE [UNIQUE] INDEX`, `ALTER TABLE` adding constraints, defaults or identities,
+/// and `COMMENT ON TABLE` or `COLUMN`. Other statements are skipped.
+/// Objects in the `public` schema are imported without a schema name.
+///
+/// # Arguments
+///
+/// * `input` - The SQL text, which the span of a syntax error points into. The spans of the
+///   returned AST are all empty, as it is not parsed from DBML.
+///
+/// # Errors
+///
+/// A syntax error if a supported statement cannot be parsed.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::importer::postgres::import;
+///
+/// let ast = import("CREATE TABLE users (id serial PRIMARY KEY, name text NOT NULL);").unwrap();
+/// ```
+pub fn import(input: &str) -> ImportResult<SchemaBlock<'_>> {
+  let tokens = tokenize(input, &DIALECT)?;
+  let mut schema = Schema::default();
+
+  for statement in split_statements(input, &tokens, &DIALECT) {
+    let mut cursor = Cursor::new(input, statement, &DIALECT);
+
+    if cursor.eat_keyword("CREATE") {
+      cursor.eat_keywords(&["OR", "REPLACE"]);
+
+      if cursor.is_keyword("TABLE")
+        || cursor.is_keywords(&["UNLOGGED", "TABLE"])
+        || cursor.is_keywords(&["TEMP", "TABLE"])
+        || cursor.is_keywords(&["TEMPORARY", "TABLE"])
+      {
+        create_table(&mut cursor, &mut schema)?;
+      } else if cursor.eat_keyword("TYPE") {
+        create_type(&mut cursor, &mut schema)?;
+      } else if cursor.is_keyword("INDEX") || cursor.is_keywords(&["UNIQUE", "INDEX"]) {
+        create_index(&mut cursor, &mut schema)?;
+      }
+    } else if cursor.eat_keywords(&["ALTER", "TABLE"]) {
+      alter_table(&mut cursor, &mut schema)?;
+    } else if cursor.eat_keywords(&["COMMENT", "ON"]) {
+      comment_on(&mut cursor, &mut schema)?;
+    }
+  }
+
+  // `pg_dump` declares the columns of primary keys as `NOT NULL`
+  schema.drop_implied_not_null();
+
+  Ok(schema.into_schema_block())
+}
+
+fn create_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let _ = cursor.eat_keyword("UNLOGGED") || cursor.eat_keyword("TEMP") || cursor.eat_keyword("TEMPORARY");
+  cursor.expect_keyword("TABLE")?;
+  cursor.eat_keywords(&["IF", "NOT", "EXISTS"]);
+
+  let (table_schema, name) = cursor.qualified_name()?;
+  let mut table = Table {
+    span_range: cursor.statement_span(),
+    schema: table_schema,
+    name,
+    ..Default::default()
+  };
+  let mut refs = vec![];
+
+  // `CREATE TABLE ... PARTITION OF` and `AS` have no definitions to import
+  if !cursor.is_punct("(") {
+    return Ok(());
+  }
+  cursor.expect_punct("(")?;
+
+  loop {
+    if cursor.is_keyword("CONSTRAINT")
+      || cursor.is_keyword("PRIMARY")
+      || cursor.is_keyword("UNIQUE")
+      || cursor.is_keyword("FOREIGN")
+      || cursor.is_keyword("CHECK")
+      || cursor.is_keyword("EXCLUDE")
+      || cursor.is_keyword("LIKE")
+    {
+      table_constraint(cursor, &mut table, &mut refs)?;
+    } else {
+      column(cursor, &mut table, &mut refs)?;
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+  cursor.expect_punct(")")?;
+
+  schema.tables.push(table);
+  schema.refs.extend(refs);
+
+  Ok(())
+}
+
+fn column(cursor: &mut Cursor, table: &mut Table, refs: &mut Vec<Ref>) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = cursor.ident()?;
+  let mut r#type = parse_type(cursor, COLUMN_KEYWORDS)?;
+  let mut col = Column {
+    name,
+    ..Default::default()
+  };
+
+  // serial types are integers with a sequence
+  if let ColumnTypeName::Raw(raw) = &r#type.type_name {
+    let integer = match raw.as_str() {
+      "smallserial" | "serial2" => Some("smallint"),
+      "serial" | "serial4" => Some("int"),
+      "bigserial" | "serial8" => Some("bigint"),
+      _ => None,
+    };
+
+    if let Some(integer) = integer.filter(|_| r#type.arrays.is_empty()) {
+      r#type.type_name = ColumnTypeName::Raw(integer.to_string());
+      r#type.raw = integer.to_string();
+      col.is_incremental = true;
+    }
+  }
+  col.r#type = r#type;
+
+  loop {
+    let constraint_name = match cursor.eat_keyword("CONSTRAINT") {
+      true => Some(cursor.ident()?),
+      false => None,
+    };
+
+    if cursor.eat_keywords(&["NOT", "NULL"]) {
+      col.nullable = Some(Nullable::NotNull);
+    } else if cursor.eat_keyword("NULL") {
+      col.nullable = Some(Nullable::Null);
+    } else if cursor.eat_keyword("DEFAULT") {
+      set_default(&mut col, parse_default(cursor, COLUMN_KEYWORDS)?);
+    } else if cursor.eat_keywords(&["PRIMARY", "KEY"]) {
+      col.is_pk = true;
+    } else if cursor.eat_keyword("UNIQUE") {
+      cursor.eat_keywords(&["NULLS", "NOT", "DISTINCT"]);
+      col.is_unique = true;
+    } else if cursor.eat_keyword("REFERENCES") {
+      let span_range = cursor.span_of(start, cursor.pos());
+      let from = ref_ident(
+        table.schema.clone(),
+        table.name.clone(),
+        vec![col.name.clone()],
+        &span_range,
+      );
+
+      refs.push(parse_references(cursor, constraint_name, from, span_range)?);
+    } else if cursor.eat_keyword("CHECK") {
+      cursor.group()?;
+      cursor.eat_keywords(&["NO", "INHERIT"]);
+    } else if cursor.eat_keyword("GENERATED") {
+      generated(cursor, &mut col)?;
+    } else if cursor.eat_keyword("COLLATE") {
+      cursor.qualified_name()?;
+    } else if cursor.eat_keyword("DEFERRABLE")
+      || cursor.eat_keywords(&["NOT", "DEFERRABLE"])
+      || cursor.eat_keywords(&["INITIALLY", "DEFERRED"])
+      || cursor.eat_keywords(&["INITIALLY", "IMMEDIATE"])
+    {
+      continue;
+    } else if constraint_name.is_some() {
+      return Err(cursor.error("expected a column constraint"));
+    } else {
+      break;
+    }
+  }
+
+  if !cursor.is_punct(",") && !cursor.is_punct(")") {
+    return Err(cursor.error("expected ',' or ')'"));
+  }
+
+  col.span_range = cursor.span_of(start, cursor.pos());
+  table.cols.push(col);
+
+  Ok(())
+}
+
+/// Sets the default value of the column, where `nextval(...)` marks the column as incremental.
+fn set_default(col: &mut Column, default: Value) {
+  match &default {
+    Value::Expr(expr) if expr.to_lowercase().starts_with("nextval(") => col.is_incremental = true,
+    _ => col.default = Some(default),
+  }
+}
+
+/// Consumes an identity column definition after `GENERATED`.
+fn generated(cursor: &mut Cursor, col: &mut Column) -> ImportResult<()> {
+  let _ = cursor.eat_keyword("ALWAYS") || cursor.eat_keywords(&["BY", "DEFAULT"]);
+  cursor.expect_keyword("AS")?;
+
+  if cursor.eat_keyword("IDENTITY") {
+    col.is_incremental = true;
+    if cursor.is_punct("(") {
+      cursor.group()?;
+    }
+  } else {
+    // generated columns have no DBML equivalent
+    cursor.group()?;
+    cursor.eat_keyword("STORED");
+  }
+
+  Ok(())
+}
+
+fn table_constraint(cursor: &mut Cursor, table: &mut Table, refs: &mut Vec<Ref>) -> ImportResult<()> {
+  let start = cursor.pos();
+  let name = match cursor.eat_keyword("CONSTRAINT") {
+    true => Some(cursor.ident()?),
+    false => None,
+  };
+
+  if cursor.eat_keywords(&["PRIMARY", "KEY"]) {
+    let cols = cursor.ident_list()?;
+    table.add_primary_key(cols, cursor.span_of(start, cursor.pos()));
+  } else if cursor.eat_keyword("UNIQUE") {
+    cursor.eat_keywords(&["NULLS", "NOT", "DISTINCT"]);
+    let cols = cursor.ident_list()?;
+    table.add_unique(
+      name.map(|name| name.to_string),
+      cols,
+      cursor.span_of(start, cursor.pos()),
+    );
+  } else if cursor.eat_keywords(&["FOREIGN", "KEY"]) {
+    let cols = cursor.ident_list()?;
+    cursor.expect_keyword("REFERENCES")?;
+
+    let span_range = cursor.span_of(start, cursor.pos());
+    let from = ref_ident(table.schema.clone(), table.name.clone(), cols, &span_range);
+    refs.push(parse_references(cursor, name, from, span_range)?);
+  } else if cursor.eat_keyword("CHECK") || cursor.eat_keyword("EXCLUDE") || cursor.eat_keyword("LIKE") {
+    cursor.skip_element();
+  } else {
+    return Err(cursor.error("expected a table constraint"));
+  }
+
+  // index parameters and deferrability
+  cursor.skip_element();
+
+  Ok(())
+}
+
+fn create_type(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let (enum_schema, name) = cursor.qualified_name()?;
+
+  // only enums are imported among the types
+  if !cursor.eat_keywords(&["AS", "ENUM"]) {
+    return Ok(());
+  }
+
+  cursor.expect_punct("(")?;
+  let mut values = vec![];
+  while let Some(token) = cursor.peek().filter(|token| token.kind == TokenKind::String) {
+    cursor.next();
+    values.push(Ident {
+      span_range: token.span_range.clone(),
+      raw: cursor.text(&token.span_range).to_string(),
+      to_string: token.text.clone(),
+    });
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+  cursor.expect_punct(")")?;
+
+  schema.enums.push(Enum {
+    span_range: cursor.statement_span(),
+    schema: enum_schema,
+    name,
+    values,
+  });
+
+  Ok(())
+}
+
+fn create_index(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let is_unique = cursor.eat_keyword("UNIQUE");
+  cursor.expect_keyword("INDEX")?;
+  cursor.eat_keyword("CONCURRENTLY");
+  cursor.eat_keywords(&["IF", "NOT", "EXISTS"]);
+
+  let name = match cursor.is_keyword("ON") {
+    true => None,
+    false => Some(cursor.ident()?.to_string),
+  };
+  cursor.expect_keyword("ON")?;
+  cursor.eat_keyword("ONLY");
+
+  let (table_schema, table_name) = cursor.qualified_name()?;
+  let r#type = match cursor.eat_keyword("USING") {
+    true => {
+      let method = cursor.ident()?;
+      // other access methods, such as `brin` or `spgist`, have no DBML equivalent
+      method.to_string.parse().ok()
+    }
+    false => None,
+  };
+  let cols = parse_index_cols(cursor)?;
+
+  let span_range = cursor.statement_span();
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+
+  table.indexes.push(Index {
+    span_range,
+    name,
+    cols,
+    is_unique,
+    r#type,
+    ..Default::default()
+  });
+
+  Ok(())
+}
+
+fn alter_table(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  cursor.eat_keywords(&["IF", "EXISTS"]);
+  cursor.eat_keyword("ONLY");
+
+  let (table_schema, table_name) = cursor.qualified_name()?;
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+  let mut refs = vec![];
+
+  loop {
+    if cursor.eat_keyword("ADD") {
+      if cursor.is_keyword("CONSTRAINT")
+        || cursor.is_keyword("PRIMARY")
+        || cursor.is_keyword("UNIQUE")
+        || cursor.is_keyword("FOREIGN")
+        || cursor.is_keyword("CHECK")
+        || cursor.is_keyword("EXCLUDE")
+      {
+        table_constraint(cursor, table, &mut refs)?;
+      } else {
+        cursor.eat_keyword("COLUMN");
+        cursor.eat_keywords(&["IF", "NOT", "EXISTS"]);
+        column(cursor, table, &mut refs)?;
+      }
+    } else if cursor.eat_keyword("ALTER") {
+      cursor.eat_keyword("COLUMN");
+      let name = cursor.ident()?;
+      let Some(col) = table.col_mut(&name) else {
+        return throw_syntax(format!("column '{}' is not defined", name.to_string), &name.span_range);
+      };
+
+      if cursor.eat_keywords(&["SET", "DEFAULT"]) {
+        set_default(col, parse_default(cursor, &[])?);
+      } else if cursor.eat_keywords(&["SET", "NOT", "NULL"]) {
+        col.nullable = Some(Nullable::NotNull);
+      } else if cursor.eat_keyword("ADD") && cursor.eat_keyword("GENERATED") {
+        generated(cursor, col)?;
+      }
+      cursor.skip_element();
+    } else {
+      // `OWNER TO`, `SET`, `ENABLE` and other actions are skipped
+      cursor.skip_element();
+    }
+
+    if !cursor.eat_punct(",") {
+      break;
+    }
+  }
+
+  schema.refs.extend(refs);
+
+  Ok(())
+}
+
+fn comment_on(cursor: &mut Cursor, schema: &mut Schema) -> ImportResult<()> {
+  let is_table = cursor.eat_keyword("TABLE");
+  if !is_table && !cursor.eat_keyword("COLUMN") {
+    return Ok(());
+  }
+
+  let mut parts = vec![cursor.ident()?];
+  while cursor.eat_punct(".") {
+    parts.push(cursor.ident()?);
+  }
+  cursor.expect_keyword("IS")?;
+
+  let note = match cursor.next() {
+    Some(token) if token.kind == TokenKind::String => Some(token.text.clone()),
+    Some(token) if token.text.eq_ignore_ascii_case("NULL") => None,
+    _ => return Err(cursor.error("expected a string literal")),
+  };
+
+  let col_name = match is_table {
+    true => None,
+    false => parts.pop(),
+  };
+  let Some(table_name) = parts.pop() else {
+    return Err(cursor.error("expected a column name"));
+  };
+  let table_schema = parts.pop().filter(|schema| schema.to_string != crate::DEFAULT_SCHEMA);
+
+  let Some(table) = schema.table_mut(&table_schema, &table_name) else {
+    return throw_syntax(
+      format!("table '{}' is not defined", table_name.to_string),
+      &table_name.span_range,
+    );
+  };
+
+  match col_name {
+    Some(col_name) => {
+      match table.col_mut(&col_name) {
+        Some(col) => col.note = note,
+        None => {
+          return throw_syntax(
+            format!("column '{}' is not defined", col_name.to_string),
+            &col_name.span_range,
+          )
+        }
+      }
+    }
+    None => table.note = note,
+  }
+
+  Ok(())
+}
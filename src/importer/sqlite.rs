@@ -0,0 +1,299 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::path::Path;
+
+use rusqlite::{
+  Connection,
+  OpenFlags,
+};
+
+use super::*;
+
+const DIALECT: SqlDialect = SqlDialect {
+  ident_quotes: &[('"', '"'), ('`', '`'), ('[', ']')],
+  backslash_escapes: false,
+  hash_comments: false,
+  dollar_quotes: false,
+  go_separator: false,
+  fold_lowercase: false,
+  default_schema: "main",
+};
+
+/// Introspects the SQLite database file at the given path, which is opened as read-only.
+///
+/// See [`import_connection`] for how the schema is read.
+pub fn import_file(path: impl AsRef<Path>) -> rusqlite::Result<SchemaBlock<'static>> {
+  let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?;
+
+  import_connection(&conn)
+}
+
+/// Introspects the tables of the main database of an open SQLite connection.
+///
+/// Tables are listed from `sqlite_master`, and their columns, indexes and foreign keys are read
+/// with `pragma_table_info`, `pragma_index_list` and `pragma_foreign_key_list`.
+/// Internal tables and views are skipped. Since there is no source text, every span of the
+/// returned schema is empty.
+pub fn import_connection(conn: &Connection) -> rusqlite::Result<SchemaBlock<'static>> {
+  let mut schema = Schema::default();
+
+  let mut stmt = conn
+    .prepare("SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY rowid")?;
+  let tables = stmt
+    .query_map([], |row| {
+      Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+    })?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+  for (name, sql) in tables {
+    let mut table = Table {
+      name: ident(&name, &SpanRange::default()),
+      ..Default::default()
+    };
+
+    import_cols(conn, &mut table, sql.as_deref().unwrap_or_default())?;
+    import_indexes(conn, &mut table)?;
+    schema.refs.extend(import_foreign_keys(conn, &name)?);
+    schema.tables.push(table);
+  }
+  schema.drop_implied_not_null();
+
+  Ok(schema.into_schema_block())
+}
+
+fn import_cols(conn: &Connection, table: &mut Table, sql: &str) -> rusqlite::Result<()> {
+  let mut stmt =
+    conn.prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid")?;
+  let mut rows = stmt.query([&table.name.to_string])?;
+
+  let mut pk = vec![];
+  while let Some(row) = rows.next()? {
+    let name = ident(&row.get::<_, String>(0)?, &SpanRange::default());
+    let key_seq: u32 = row.get(4)?;
+    if key_seq > 0 {
+      pk.push((key_seq, name.clone()));
+    }
+
+    table.cols.push(Column {
+      r#type: col_type(&row.get::<_, String>(1)?),
+      nullable: row.get::<_, bool>(2)?.then_some(Nullable::NotNull),
+      default: row.get::<_, Option<String>>(3)?.map(|default| col_default(&default)),
+      name,
+      ..Default::default()
+    });
+  }
+
+  pk.sort_by_key(|(key_seq, _)| *key_seq);
+  let pk: Vec<_> = pk.into_iter().map(|(_, name)| name).collect();
+
+  // only an `INTEGER PRIMARY KEY` may be declared with `AUTOINCREMENT`
+  let is_incremental = tokenize(sql, &DIALECT).is_ok_and(|tokens| {
+    tokens
+      .iter()
+      .any(|token| token.kind == TokenKind::Word && token.text.eq_ignore_ascii_case("AUTOINCREMENT"))
+  });
+  if let [col] = pk.as_slice() {
+    if is_incremental {
+      if let Some(col) = table.col_mut(col) {
+        col.is_incremental = true;
+      }
+    }
+  }
+  if !pk.is_empty() {
+    table.add_primary_key(pk, SpanRange::default());
+  }
+
+  Ok(())
+}
+
+fn import_indexes(conn: &Connection, table: &mut Table) -> rusqlite::Result<()> {
+  // the most recently created index is listed first
+  let mut stmt = conn.prepare("SELECT name, \"unique\", origin FROM pragma_index_list(?1) ORDER BY seq DESC")?;
+  let indexes = stmt
+    .query_map([&table.name.to_string], |row| {
+      Ok((
+        row.get::<_, String>(0)?,
+        row.get::<_, bool>(1)?,
+        row.get::<_, String>(2)?,
+      ))
+    })?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+  for (name, is_unique, origin) in indexes {
+    match origin.as_str() {
+      // already read from the columns
+      "pk" => (),
+      // the name of an index created for a `UNIQUE` constraint is generated
+      "u" => {
+        let cols = index_cols(conn, &name)?
+          .into_iter()
+          .filter_map(|col| {
+            match col {
+              IndexesColumnType::String(ident) => Some(ident),
+              IndexesColumnType::Expr(_) => None,
+            }
+          })
+          .collect();
+
+        table.add_unique(None, cols, SpanRange::default());
+      }
+      _ => {
+        table.indexes.push(Index {
+          cols: index_cols(conn, &name)?,
+          name: Some(name),
+          is_unique,
+          ..Default::default()
+        })
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn index_cols(conn: &Connection, index_name: &str) -> rusqlite::Result<Vec<IndexesColumnType>> {
+  let mut stmt = conn.prepare("SELECT cid, name FROM pragma_index_xinfo(?1) WHERE key ORDER BY seqno")?;
+  let cols = stmt
+    .query_map([index_name], |row| {
+      Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?))
+    })?
+    .collect::<rusqlite::Result<Vec<_>>>()?;
+
+  match cols.iter().all(|(_, name)| name.is_some()) {
+    true => {
+      Ok(
+        cols
+          .into_iter()
+          .filter_map(|(_, name)| name)
+          .map(|name| IndexesColumnType::String(ident(&name, &SpanRange::default())))
+          .collect(),
+      )
+    }
+    // the text of an expression is only found in the `CREATE INDEX` statement
+    false => {
+      let sql: Option<String> = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'index' AND name = ?1",
+        [index_name],
+        |row| row.get(0),
+      )?;
+
+      Ok(sql.as_deref().and_then(expr_index_cols).unwrap_or_default())
+    }
+  }
+}
+
+/// Reads the key parts of an index from its `CREATE INDEX` statement.
+fn expr_index_cols(sql: &str) -> Option<Vec<IndexesColumnType>> {
+  let tokens = tokenize(sql, &DIALECT).ok()?;
+  let mut cursor = Cursor::new(sql, &tokens, &DIALECT);
+  cursor.skip_until(|cursor| cursor.is_punct("("));
+
+  let cols = parse_index_cols(&mut cursor).ok()?;
+  let cols = cols
+    .into_iter()
+    .map(|col| {
+      match col {
+        IndexesColumnType::String(col) => IndexesColumnType::String(ident(&col.to_string, &SpanRange::default())),
+        IndexesColumnType::Expr(expr) => IndexesColumnType::Expr(literal(expr.value, &SpanRange::default())),
+      }
+    })
+    .collect();
+
+  Some(cols)
+}
+
+fn import_foreign_keys(conn: &Connection, table_name: &str) -> rusqlite::Result<Vec<Ref>> {
+  // the most recently declared foreign key is listed first
+  let mut stmt = conn.prepare(
+    "SELECT id, \"table\", \"from\", \"to\", on_update, on_delete FROM pragma_foreign_key_list(?1) ORDER BY id DESC, seq",
+  )?;
+  let mut rows = stmt.query([table_name])?;
+
+  let span_range = SpanRange::default();
+  let mut refs: Vec<(i64, Ref)> = vec![];
+  while let Some(row) = rows.next()? {
+    let id: i64 = row.get(0)?;
+    let from = ident(&row.get::<_, String>(2)?, &span_range);
+    // the primary key of the referenced table is used if the columns are omitted
+    let to = row.get::<_, Option<String>>(3)?.map(|to| ident(&to, &span_range));
+
+    match refs.last_mut() {
+      Some((last_id, r#ref)) if *last_id == id => {
+        r#ref.from.compositions.push(from);
+        r#ref.to.compositions.extend(to);
+      }
+      _ => {
+        let r#ref = Ref {
+          from: ref_ident(None, ident(table_name, &span_range), vec![from], &span_range),
+          to: ref_ident(
+            None,
+            ident(&row.get::<_, String>(1)?, &span_range),
+            to.into_iter().collect(),
+            &span_range,
+          ),
+          on_update: referential_action(&row.get::<_, String>(4)?),
+          on_delete: referential_action(&row.get::<_, String>(5)?),
+          ..Default::default()
+        };
+
+        refs.push((id, r#ref));
+      }
+    }
+  }
+
+  Ok(refs.into_iter().map(|(_, r#ref)| r#ref).collect())
+}
+
+/// Converts a declared type, which may be omitted in SQLite.
+fn col_type(declared: &str) -> ColumnType {
+  // a column without a declared type has the `BLOB` affinity
+  if declared.trim().is_empty() {
+    return ColumnType {
+      raw: "blob".into(),
+      type_name: ColumnTypeName::Raw("blob".into()),
+      ..Default::default()
+    };
+  }
+
+  let parsed = tokenize(declared, &DIALECT).ok().and_then(|tokens| {
+    let mut cursor = Cursor::new(declared, &tokens, &DIALECT);
+    let col_type = parse_type(&mut cursor, &[]).ok()?;
+
+    cursor.is_done().then_some(col_type)
+  });
+
+  match parsed {
+    Some(col_type) => {
+      ColumnType {
+        span_range: SpanRange::default(),
+        ..col_type
+      }
+    }
+    None => {
+      ColumnType {
+        raw: declared.to_lowercase(),
+        type_name: ColumnTypeName::Raw(declared.to_lowercase()),
+        ..Default::default()
+      }
+    }
+  }
+}
+
+/// Converts the text of a default value, keeping anything but a literal as an expression.
+fn col_default(text: &str) -> Value {
+  tokenize(text, &DIALECT)
+    .ok()
+    .and_then(|tokens| parse_default(&mut Cursor::new(text, &tokens, &DIALECT), &[]).ok())
+    .unwrap_or_else(|| Value::Expr(text.into()))
+}
+
+fn referential_action(action: &str) -> Option<ReferentialAction> {
+  match action {
+    "CASCADE" => Some(ReferentialAction::Cascade),
+    "RESTRICT" => Some(ReferentialAction::Restrict),
+    "SET NULL" => Some(ReferentialAction::SetNull),
+    "SET DEFAULT" => Some(ReferentialAction::SetDefault),
+    // the default action is left out
+    _ => None,
+  }
+}
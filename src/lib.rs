@@ -1,5 +1,5 @@
 #![forbid(unsafe_code)]
-#![forbid(clippy::all)]
+#![deny(clippy::all)]
 #![no_std]
 
 use ast::SchemaBlock;
@@ -13,19 +13,29 @@ extern crate pest_derive;
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "sqlite")]
+extern crate std;
+
 pub(crate) mod analyzer;
 pub mod ast;
+pub mod diagnostic;
+pub mod diff;
+pub mod formatter;
+pub mod generator;
+pub mod importer;
 pub(crate) mod parser;
-#[cfg(not(feature = "utils"))]
-pub(crate) mod utils;
+pub mod source_map;
 #[cfg(feature = "utils")]
 pub mod utils;
 
 pub use analyzer::*;
+pub use diagnostic::Diagnostic;
 pub use parser::{
   parse as parse_dbml_unchecked,
+  parse_recoverable as parse_dbml_recoverable,
   Rule,
 };
+pub use source_map::SourceMap;
 
 /// Default database schema if not specified in a DBML file.
 pub const DEFAULT_SCHEMA: &str = "public";
@@ -59,7 +69,7 @@ pub const DEFAULT_SCHEMA: &str = "public";
 ///     }
 /// }
 /// ```
-pub fn parse_dbml(input: &str) -> Result<SchemaBlock, ParseError<Rule>> {
+pub fn parse_dbml(input: &str) -> Result<SchemaBlock<'_>, ParseError<Rule>> {
   let ast = parse_dbml_unchecked(input)?;
 
   analyze(&ast).map(|_| ast)
@@ -1,5 +1,6 @@
 mod err;
 mod helper;
+mod recovery;
 
 use alloc::string::{
   String,
@@ -13,6 +14,7 @@ use pest::iterators::Pair;
 use pest::Parser;
 
 use self::helper::*;
+pub use self::recovery::parse_recoverable;
 use crate::ast::*;
 
 #[derive(Parser)]
@@ -54,7 +56,7 @@ struct DBMLParser;
 /// let ast = result.unwrap();
 /// // Now `ast` contains the unsanitized abstract syntax tree (AST) of the parsed DBML text.
 /// ```
-pub fn parse(input: &str) -> ParserResult<SchemaBlock> {
+pub fn parse(input: &str) -> ParserResult<SchemaBlock<'_>> {
   let pair = DBMLParser::parse(Rule::schema, input)?
     .next()
     .ok_or_else(|| unreachable!("unhandled parsing error"))?;
@@ -65,6 +67,42 @@ pub fn parse(input: &str) -> ParserResult<SchemaBlock> {
   }
 }
 
+/// Collects the span ranges of all single-line and multi-line comments in the DBML text.
+///
+/// Comments are silently skipped by the main grammar, so they are gathered in a separate pass.
+pub(crate) fn parse_comments(input: &str) -> Vec<SpanRange> {
+  parse_trivia(input, |rule| {
+    matches!(rule, Rule::single_line_comment | Rule::multi_line_comment)
+  })
+}
+
+/// Collects the span ranges of all comments and string literals in the DBML text, in order.
+pub(crate) fn parse_comments_and_literals(input: &str) -> Vec<SpanRange> {
+  parse_trivia(input, |rule| {
+    matches!(
+      rule,
+      Rule::single_line_comment
+        | Rule::multi_line_comment
+        | Rule::triple_quoted_string
+        | Rule::single_quoted_string
+        | Rule::double_quoted_string
+        | Rule::backquoted_quoted_string
+    )
+  })
+}
+
+fn parse_trivia(input: &str, is_collected: impl Fn(Rule) -> bool) -> Vec<SpanRange> {
+  DBMLParser::parse(Rule::trivia, input)
+    .map(|pairs| {
+      pairs
+        .flatten()
+        .filter(|p| is_collected(p.as_rule()))
+        .map(|p| s2r(p.as_span()))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn parse_schema<'a>(pair: Pair<Rule>, input: &'a str) -> ParserResult<SchemaBlock<'a>> {
   let init = SchemaBlock {
     span_range: s2r(pair.as_span()),
@@ -113,16 +151,13 @@ fn parse_project_decl(pair: Pair<Rule>) -> ParserResult<ProjectBlock> {
             Rule::property => {
               let prop = parse_property(p2.clone())?;
 
-              match prop.key.to_string.as_str() {
-                "database_type" => {
-                  if let Value::String(db_name) = prop.value.value.clone() {
-                    acc.database_type = match DatabaseType::from_str(&db_name) {
-                      Ok(val) => val,
-                      Err(msg) => throw_msg(msg, p2)?,
-                    }
+              if prop.key.to_string.as_str() == "database_type" {
+                if let Value::String(db_name) = prop.value.value.clone() {
+                  acc.database_type = match DatabaseType::from_str(&db_name) {
+                    Ok(val) => val,
+                    Err(msg) => throw_msg(msg, p2)?,
                   }
                 }
-                _ => (),
               }
 
               acc.properties.push(prop)
@@ -244,7 +279,7 @@ fn parse_col_type(pair: Pair<Rule>) -> ParserResult<ColumnType> {
       Rule::col_type_quoted | Rule::col_type_unquoted => {
         for p2 in p1.into_inner() {
           match p2.as_rule() {
-            Rule::var | Rule::spaced_var => out.type_name = ColumnTypeName::Raw(p2.as_str().to_string()),
+            Rule::col_type_name | Rule::spaced_var => out.type_name = ColumnTypeName::Raw(p2.as_str().to_string()),
             Rule::col_type_arg => out.args = parse_col_type_arg(p2)?,
             Rule::col_type_array => {
               let val = p2.into_inner().try_fold(None, |_, p3| {
@@ -265,7 +300,12 @@ fn parse_col_type(pair: Pair<Rule>) -> ParserResult<ColumnType> {
             }
             _ => {
               throw_rules(
-                &[Rule::var, Rule::spaced_var, Rule::col_type_arg, Rule::col_type_array],
+                &[
+                  Rule::col_type_name,
+                  Rule::spaced_var,
+                  Rule::col_type_arg,
+                  Rule::col_type_array,
+                ],
                 p2,
               )?
             }
@@ -385,9 +425,8 @@ fn parse_enum_value(pair: Pair<Rule>) -> ParserResult<EnumValue> {
             Rule::attribute => {
               let attr = parse_attribute(p2)?;
 
-              match attr.key.to_string.as_str() {
-                "note" => settings.note = attr.value.clone().map(|v| v.value.to_string()),
-                _ => (),
+              if attr.key.to_string.as_str() == "note" {
+                settings.note = attr.value.clone().map(|v| v.value.to_string())
               }
 
               settings.attributes.push(attr);
@@ -0,0 +1,200 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::*;
+use crate::diagnostic::Diagnostic;
+
+/// Keywords that open a top-level block, used as synchronization points.
+const TOP_LEVEL_KEYWORDS: [&str; 5] = ["TableGroup", "Table", "Ref", "Enum", "Project"];
+
+/// Parses the entire DBML text, recovering from syntax errors.
+///
+/// Whenever a top-level block fails to parse, it is skipped up to the next line starting with a
+/// top-level keyword (`Table`, `Ref`, `Enum`, `TableGroup` or `Project`) outside comments and
+/// string literals, and a syntax diagnostic is reported.
+/// Skipped text is replaced with whitespace of the same byte length, so all span ranges in the
+/// resulting AST still point into the original input.
+///
+/// # Returns
+///
+/// A tuple of the unsanitized AST containing every successfully parsed block and the list of
+/// syntax diagnostics. The diagnostics are empty if and only if `parse` succeeds.
+///
+/// # Examples
+///
+/// ```rs
+/// use dbml_rs::parse_dbml_recoverable;
+///
+/// let dbml_text = r#"
+///     Table users {
+///         id int
+///     }
+///
+///     Table posts {
+///         id int [
+///     }
+/// "#;
+///
+/// let (ast, diags) = parse_dbml_recoverable(dbml_text);
+/// assert_eq!(ast.tables().len(), 1);
+/// assert_eq!(diags.len(), 1);
+/// ```
+pub fn parse_recoverable(input: &str) -> (SchemaBlock<'_>, Vec<Diagnostic>) {
+  if let Ok(schema_block) = parse(input) {
+    return (schema_block, vec![]);
+  }
+
+  let chunks = split_chunks(input);
+  let mut masked = String::from(input);
+  let mut diags = vec![];
+  // the chunks before this one are known to form complete blocks
+  let mut parsed = 0;
+  // the number of chunks parsed at once, which doubles while they parse so that the cost of
+  // recovery grows linearly with the input
+  let mut window = 1;
+
+  while parsed < chunks.len() && diags.len() <= chunks.len() {
+    let end = (parsed + window).min(chunks.len());
+    let range = chunks[parsed].start..chunks[end - 1].end;
+
+    let diag = match check(&masked, &range) {
+      None => {
+        parsed = end;
+        window *= 2;
+        continue;
+      }
+      // the window may end within a block, so widen it before blaming a chunk
+      Some(diag) if diag.primary_span.start == range.end && end < chunks.len() => {
+        window *= 2;
+        continue;
+      }
+      Some(diag) => diag,
+    };
+    window = 1;
+
+    let pos = diag.primary_span.start;
+    let failing = chunks[..end]
+      .iter()
+      .rposition(|chunk| chunk.start <= pos)
+      .unwrap_or(parsed);
+
+    // an unclosed block may only fail at a later chunk, so look backward for the culprit
+    let culprit = (parsed..=failing)
+      .rev()
+      .find_map(|idx| check(&masked, &chunks[idx]).map(|diag| (idx, diag)));
+
+    match culprit {
+      Some((idx, diag)) => {
+        let chunk = &chunks[idx];
+        let line_start = masked[..diag.primary_span.start]
+          .rfind('\n')
+          .map(|idx| idx + 1)
+          .unwrap_or_default();
+        let keep = chunk.start..line_start.clamp(chunk.start, chunk.end);
+
+        if !keep.is_empty() && check(&masked, &keep).is_none() {
+          mask(&mut masked, &(keep.end..chunk.end));
+        } else {
+          mask(&mut masked, chunk);
+        }
+
+        diags.push(diag);
+      }
+      None => {
+        mask(&mut masked, &(chunks[failing].start..input.len()));
+
+        diags.push(diag);
+      }
+    }
+  }
+
+  match parse(&masked) {
+    Ok(schema_block) => {
+      let schema_block = SchemaBlock {
+        span_range: schema_block.span_range,
+        input,
+        blocks: schema_block.blocks,
+      };
+
+      (schema_block, diags)
+    }
+    Err(_) => {
+      let schema_block = SchemaBlock {
+        span_range: 0..input.len(),
+        input,
+        ..Default::default()
+      };
+
+      (schema_block, diags)
+    }
+  }
+}
+
+/// Splits the input into chunks, each starting at a top-level keyword (except the first one).
+///
+/// Keywords within comments and string literals, such as a multi-line note, are ignored.
+fn split_chunks(input: &str) -> Vec<Range<usize>> {
+  let skipped = parse_comments_and_literals(input);
+  let mut skipped = skipped.iter().peekable();
+
+  let mut starts = vec![0];
+  let mut line_start = 0;
+  for line in input.split_inclusive('\n') {
+    let indent = line.len() - line.trim_start().len();
+    let pos = line_start + indent;
+
+    while skipped.next_if(|range| range.end <= pos).is_some() {}
+
+    if pos != 0 && is_top_level_keyword(line.trim_start()) && !skipped.peek().is_some_and(|range| range.contains(&pos))
+    {
+      starts.push(pos);
+    }
+
+    line_start += line.len();
+  }
+
+  starts
+    .iter()
+    .enumerate()
+    .map(|(idx, start)| *start..starts.get(idx + 1).copied().unwrap_or(input.len()))
+    .collect()
+}
+
+/// Checks if the text starts with a top-level keyword followed by a non-identifier character.
+fn is_top_level_keyword(text: &str) -> bool {
+  TOP_LEVEL_KEYWORDS.iter().any(|keyword| {
+    text
+      .get(..keyword.len())
+      .is_some_and(|prefix| prefix.eq_ignore_ascii_case(keyword))
+      && !text[keyword.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+  })
+}
+
+/// Parses the text within the range on its own, returning the syntax diagnostic if it fails.
+///
+/// Only the range is handed to the parser, so that the cost does not depend on its position. The
+/// span of the diagnostic is shifted to point into the whole input.
+fn check(input: &str, range: &Range<usize>) -> Option<Diagnostic> {
+  parse(&input[range.clone()]).err().map(|err| {
+    let mut diag = Diagnostic::syntax(&err);
+    diag.primary_span = diag.primary_span.start + range.start..diag.primary_span.end + range.start;
+
+    diag
+  })
+}
+
+/// Replaces the text within the range with whitespace while preserving line breaks and byte
+/// offsets.
+fn mask(input: &mut String, range: &Range<usize>) {
+  let mut masked = String::with_capacity(range.len());
+  for c in input[range.clone()].chars() {
+    match c {
+      '\n' | '\r' => masked.push(c),
+      // a space for each byte of the character
+      _ => masked.push_str(&"    "[..c.len_utf8()]),
+    }
+  }
+
+  input.replace_range(range.clone(), &masked);
+}
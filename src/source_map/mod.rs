@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+
+use crate::ast::{
+  SchemaBlock,
+  SpanRange,
+};
+
+/// Represents a zero-based position of a character within the source text.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, PartialOrd, Ord)]
+pub struct LineCol {
+  /// The zero-based line index.
+  pub line: usize,
+  /// The zero-based column counted in UTF-8 code units (bytes).
+  pub col: usize,
+  /// The zero-based column counted in UTF-16 code units, as used by the Language Server Protocol.
+  pub col_utf16: usize,
+}
+
+/// Converts byte offsets in the source text into lines and columns, and vice versa.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SourceMap<'a> {
+  /// The source text.
+  input: &'a str,
+  /// The byte offsets of the start of each line.
+  line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+  /// Creates a source map of the given source text.
+  pub fn new(input: &'a str) -> Self {
+    let line_starts = core::iter::once(0)
+      .chain(input.match_indices('\n').map(|(idx, _)| idx + 1))
+      .collect();
+
+    Self { input, line_starts }
+  }
+
+  /// Returns the source text.
+  pub fn input(&self) -> &'a str {
+    self.input
+  }
+
+  /// Returns the number of lines in the source text.
+  pub fn line_count(&self) -> usize {
+    self.line_starts.len()
+  }
+
+  /// Returns the text of the line at the zero-based index, without the line break.
+  pub fn line(&self, line: usize) -> Option<&'a str> {
+    let start = *self.line_starts.get(line)?;
+
+    Some(&self.input[start..self.line_end(line)])
+  }
+
+  /// Converts the byte offset into a line and column.
+  ///
+  /// Returns `None` if the offset is out of bounds or not on a character boundary.
+  pub fn line_col(&self, offset: usize) -> Option<LineCol> {
+    if !self.input.is_char_boundary(offset) {
+      return None;
+    }
+
+    let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+    let start = self.line_starts[line];
+
+    Some(LineCol {
+      line,
+      col: offset - start,
+      col_utf16: self.input[start..offset].encode_utf16().count(),
+    })
+  }
+
+  /// Converts the zero-based line and column (in UTF-8 code units) into a byte offset.
+  ///
+  /// Returns `None` if the position is beyond the end of the line or not on a character boundary.
+  pub fn offset(&self, line: usize, col: usize) -> Option<usize> {
+    let offset = self.line_starts.get(line)? + col;
+
+    (offset <= self.line_end(line) && self.input.is_char_boundary(offset)).then_some(offset)
+  }
+
+  /// Converts the zero-based line and column (in UTF-16 code units) into a byte offset.
+  ///
+  /// Returns `None` if the position is beyond the end of the line or inside a surrogate pair.
+  pub fn offset_utf16(&self, line: usize, col_utf16: usize) -> Option<usize> {
+    let start = *self.line_starts.get(line)?;
+    let mut units = 0;
+
+    for (idx, c) in self.line(line)?.char_indices() {
+      if units >= col_utf16 {
+        return (units == col_utf16).then_some(start + idx);
+      }
+
+      units += c.len_utf16();
+    }
+
+    (units == col_utf16).then_some(self.line_end(line))
+  }
+
+  /// Returns the full lines of the source text covered by the span, without the trailing line
+  /// break.
+  ///
+  /// Returns `None` if the span is out of bounds.
+  pub fn snippet(&self, span_range: &SpanRange) -> Option<&'a str> {
+    if span_range.start > span_range.end || span_range.end > self.input.len() {
+      return None;
+    }
+
+    let first = self.line_col(span_range.start)?.line;
+    let last = self.line_col(span_range.end)?.line;
+
+    Some(&self.input[self.line_starts[first]..self.line_end(last)])
+  }
+
+  /// Returns the byte offset of the end of the line, excluding the line break.
+  fn line_end(&self, line: usize) -> usize {
+    let end = match self.line_starts.get(line + 1) {
+      Some(next) => next - 1,
+      None => self.input.len(),
+    };
+
+    match self.input[..end].ends_with('\r') {
+      true => end - 1,
+      false => end,
+    }
+  }
+}
+
+impl<'a> From<&SchemaBlock<'a>> for SourceMap<'a> {
+  fn from(schema_block: &SchemaBlock<'a>) -> Self {
+    Self::new(schema_block.input)
+  }
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{
+  Command,
+  Output,
+  Stdio,
+};
+
+const SCHEMA: &str = r#"Project demo {
+  database_type: 'PostgreSQL'
+}
+
+Enum status {
+  active
+  inactive
+}
+
+Table users {
+  id int [pk]
+  status status
+}
+
+Table posts {
+  id int [pk]
+  user_id int [ref: > users.id]
+}
+
+Ref: posts.id - users.id
+"#;
+
+/// Runs the `dbml` binary with the arguments, feeding the text to stdin.
+fn dbml(args: &[&str], stdin: &str) -> Output {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_dbml"))
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+
+  child.wait_with_output().unwrap()
+}
+
+#[test]
+fn cli_check() {
+  let output = dbml(&["check"], SCHEMA);
+  assert_eq!(output.status.code(), Some(0));
+
+  let output = dbml(&["check", "-"], &SCHEMA.replace("Table posts", "Table users"));
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert_eq!(output.status.code(), Some(1));
+  assert!(
+    stderr.contains("error[E0010]: Duplicate table name\n  --> <stdin>:15:7\n"),
+    "{}",
+    stderr
+  );
+
+  let output = dbml(&["check", "missing.dbml"], "");
+  assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_fmt() {
+  let output = dbml(&["fmt"], "table users {id int [not null,pk]}");
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(
+    String::from_utf8(output.stdout).unwrap(),
+    "Table users {\n  id int [pk, not null]\n}\n"
+  );
+
+  let output = dbml(&["fmt", "--check"], "table users {id int [not null,pk]}");
+  assert_eq!(output.status.code(), Some(1));
+
+  let output = dbml(&["fmt", "--check"], "Table users {\n  id int [pk, not null]\n}\n");
+  assert_eq!(output.status.code(), Some(0));
+
+  let output = dbml(&["fmt"], "Table users {");
+  assert_eq!(output.status.code(), Some(1));
+  assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn cli_convert_and_stats() {
+  let output = dbml(&["convert", "--to", "json"], SCHEMA);
+  assert_eq!(output.status.code(), Some(0));
+
+  let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+  assert_eq!(json["databaseType"], "PostgreSQL");
+  assert_eq!(json["schemas"][0]["tables"][0]["name"], "users");
+  assert_eq!(json["schemas"][0]["enums"][0]["name"], "status");
+  assert_eq!(json["schemas"][0]["refs"][0]["endpoints"][0]["tableName"], "posts");
+  assert_eq!(json["schemas"][0]["refs"][0]["endpoints"][1]["relation"], "1");
+
+  let output = dbml(&["convert", "--to", "sql"], SCHEMA);
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  assert_eq!(output.status.code(), Some(0));
+  assert!(
+    stdout.starts_with("CREATE TYPE \"status\" AS ENUM ('active', 'inactive');\n"),
+    "{}",
+    stdout
+  );
+  assert!(
+    stdout.contains("ALTER TABLE \"users\" ADD FOREIGN KEY (\"id\") REFERENCES \"posts\" (\"id\");\n"),
+    "{}",
+    stdout
+  );
+
+  let output = dbml(&["convert", "--to", "mermaid"], SCHEMA);
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  assert_eq!(output.status.code(), Some(0));
+  assert!(stdout.starts_with("erDiagram\n  users {\n"), "{}", stdout);
+  assert!(stdout.contains("  posts }o--o| users : \"user_id\"\n"), "{}", stdout);
+
+  let output = dbml(&["convert", "--to", "mermaid", "--table-group", "missing"], SCHEMA);
+  assert_eq!(output.status.code(), Some(1));
+  assert!(output.stdout.is_empty());
+
+  let output = dbml(&["convert", "--to", "dot"], SCHEMA);
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  assert_eq!(output.status.code(), Some(0));
+  assert!(stdout.starts_with("digraph dbml {\n"), "{}", stdout);
+  assert!(
+    stdout.contains("  \"posts\":\"user_id\" -> \"users\":\"id\" [dir=both, arrowtail=crowodot, arrowhead=teeodot];\n"),
+    "{}",
+    stdout
+  );
+
+  let output = dbml(&["stats"], SCHEMA);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(
+    String::from_utf8(output.stdout).unwrap(),
+    "tables:       2\ncolumns:      4\nrefs:         2\nenums:        1\ntable groups: 0\n"
+  );
+}
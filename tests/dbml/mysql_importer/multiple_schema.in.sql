@@ -0,0 +1,52 @@
+CREATE DATABASE IF NOT EXISTS `shop`;
+USE `shop`;
+
+CREATE TABLE `users` (
+  `id` int PRIMARY KEY,
+  `name` varchar(255),
+  `pjs` ENUM ('created2', 'running2', 'done2', 'failure2'),
+  `pjs2` ENUM ('created2', 'running2', 'done2', 'failure2'),
+  `pg` ENUM ('male', 'female'),
+  `pg2` ENUM ('male2', 'female2')
+);
+
+CREATE TABLE `products` (
+  `id` int PRIMARY KEY,
+  `name` varchar(255) COMMENT 'Product name'
+);
+
+CREATE DATABASE IF NOT EXISTS `ecommerce`;
+
+CREATE TABLE `ecommerce`.`users` (
+  `id` int PRIMARY KEY,
+  `name` varchar(255),
+  `ejs` ENUM ('created2', 'running2', 'done2', 'failure2'),
+  `ejs2` ENUM ('created2', 'running2', 'done2', 'failure2'),
+  `eg` ENUM ('male', 'female'),
+  `eg2` ENUM ('male2', 'female2')
+);
+
+CREATE DATABASE IF NOT EXISTS `schemaA`;
+USE `schemaA`;
+
+CREATE TABLE `locations` (
+  `id` int PRIMARY KEY,
+  `name` varchar(255)
+);
+
+CREATE TABLE `products` (
+  `id` int PRIMARY KEY,
+  `name` varchar(255) COMMENT 'Sample field comment on multiples schema',
+  `lid` int,
+  CONSTRAINT `FK_1` FOREIGN KEY (`lid`) REFERENCES `locations` (`id`) ON DELETE CASCADE
+);
+
+USE `shop`;
+
+ALTER TABLE `ecommerce`.`users` ADD FOREIGN KEY (`id`) REFERENCES `users` (`id`);
+
+ALTER TABLE `ecommerce`.`users` ADD FOREIGN KEY (`id`) REFERENCES `users` (`name`);
+
+ALTER TABLE `schemaA`.`products` ADD FOREIGN KEY (`name`) REFERENCES `ecommerce`.`users` (`id`);
+
+ALTER TABLE `schemaA`.`locations` ADD FOREIGN KEY (`name`) REFERENCES `shop`.`users` (`id`);
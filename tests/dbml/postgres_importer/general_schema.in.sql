@@ -0,0 +1,143 @@
+--
+-- PostgreSQL database dump
+--
+
+SET statement_timeout = 0;
+SET client_encoding = 'UTF8';
+SELECT pg_catalog.set_config('search_path', '', false);
+
+CREATE TYPE public.orders_status AS ENUM (
+    'created',
+    'running',
+    'done',
+    'failure'
+);
+
+CREATE TYPE "product status" AS ENUM (
+    'Out of Stock',
+    'In Stock'
+);
+
+CREATE FUNCTION public.touch() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+  NEW.modified_at := now();
+  RETURN NEW;
+END;
+$$;
+
+CREATE TABLE public.orders (
+    id int NOT NULL,
+    user_id int UNIQUE NOT NULL,
+    status orders_status,
+    created_at varchar,
+    modified_at timestamp(2)
+);
+
+CREATE SEQUENCE public.orders_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+ALTER SEQUENCE public.orders_id_seq OWNED BY public.orders.id;
+
+ALTER TABLE ONLY public.orders ALTER COLUMN id SET DEFAULT nextval('public.orders_id_seq'::regclass);
+
+ALTER TABLE ONLY public.orders
+    ADD CONSTRAINT orders_pkey PRIMARY KEY (id);
+
+CREATE TABLE order_items (
+    order_id int,
+    product_id int,
+    quantity int DEFAULT 1,
+    created_at timestamp,
+    modified_at time
+);
+
+CREATE TABLE products (
+    id int,
+    name varchar,
+    merchant_id int NOT NULL,
+    price int,
+    status "product status",
+    created_at datetime DEFAULT (now()),
+    modified_at timestamp,
+    CONSTRAINT products_pkey PRIMARY KEY (id, name)
+);
+
+CREATE INDEX product_status ON products (merchant_id, status);
+
+CREATE UNIQUE INDEX ON products USING hash (id);
+
+COMMENT ON TABLE products IS 'Products table comment';
+
+CREATE TABLE users (
+    id int PRIMARY KEY,
+    full_name varchar,
+    email varchar,
+    gender varchar,
+    date_of_birth varchar,
+    created_at varchar,
+    modified_at time(2),
+    country_code int
+);
+
+ALTER TABLE ONLY users
+    ADD CONSTRAINT users_email_key UNIQUE (email);
+
+COMMENT ON TABLE public.users IS 'Store user data';
+
+CREATE TABLE merchants (
+    id int,
+    merchant_name varchar,
+    country_code int,
+    created_at varchar,
+    modified_at time,
+    admin_id int REFERENCES users,
+    PRIMARY KEY (id)
+);
+
+/* countries are referenced
+   by users and merchants */
+CREATE TABLE countries (
+    code int PRIMARY KEY,
+    name varchar,
+    continent_name varchar
+);
+
+CREATE TABLE foo (
+    bar text[],
+    bar2 int[1],
+    bar3 int[2][3],
+    bar4 int ARRAY,
+    bar5 int ARRAY[2],
+    bar6 text[8],
+    bar7 text[100],
+    bar8 time(2)[],
+    bar9 time(1)[1],
+    bar10 time(1)[],
+    bar11 time[5],
+    bar12 timestamp(2)[10][2][5],
+    bar13 character varying[],
+    bar14 character varying(25)[][2][],
+    bar15 character varying[76]
+);
+
+ALTER TABLE ONLY order_items
+    ADD FOREIGN KEY (order_id) REFERENCES orders(id);
+
+ALTER TABLE ONLY order_items
+    ADD FOREIGN KEY (product_id) REFERENCES products (id);
+
+ALTER TABLE ONLY users
+    ADD FOREIGN KEY (country_code) REFERENCES countries (code);
+
+ALTER TABLE ONLY merchants
+    ADD FOREIGN KEY (country_code) REFERENCES countries (code);
+
+ALTER TABLE ONLY products
+    ADD FOREIGN KEY (merchant_id) REFERENCES merchants (id);
@@ -0,0 +1,54 @@
+CREATE SCHEMA ecommerce;
+CREATE SCHEMA "schemaA";
+CREATE SCHEMA "schemaB";
+
+CREATE TYPE job_status AS ENUM ('created2', 'running2', 'done2', 'failure2');
+CREATE TYPE gender AS ENUM ('male2', 'female2');
+CREATE TYPE "schemaB".gender AS ENUM ('male', 'female');
+
+CREATE TABLE users (
+  id int PRIMARY KEY,
+  name varchar,
+  pjs job_status,
+  pjs2 public.job_status,
+  pg "schemaB".gender,
+  pg2 gender
+);
+
+CREATE TABLE products (
+  id int PRIMARY KEY,
+  name varchar
+);
+
+CREATE UNIQUE INDEX ON products USING HASH (id);
+
+COMMENT ON COLUMN products.name IS 'Product name of public schema';
+
+CREATE TABLE ecommerce.users (
+  id int PRIMARY KEY REFERENCES users (id) REFERENCES users (name),
+  name varchar,
+  ejs job_status,
+  ejs2 public.job_status,
+  eg "schemaB".gender,
+  eg2 gender
+);
+
+CREATE TABLE "schemaA".products (
+  id int PRIMARY KEY,
+  name varchar REFERENCES ecommerce.users (id),
+  lid int
+);
+
+CREATE INDEX product_status ON "schemaA".products (id, name);
+
+COMMENT ON COLUMN "schemaA".products.name IS 'Product name of schemaA';
+
+CREATE TABLE "schemaA".locations (
+  id int PRIMARY KEY,
+  name varchar
+);
+
+COMMENT ON TABLE "schemaA".locations IS 'This is a note in table "locations"';
+
+ALTER TABLE "schemaA".products ADD FOREIGN KEY (lid) REFERENCES "schemaA".locations (id);
+ALTER TABLE "schemaA".locations ADD FOREIGN KEY (name) REFERENCES users (id);
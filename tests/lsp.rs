@@ -0,0 +1,290 @@
+#![cfg(feature = "lsp")]
+
+use std::io::{
+  BufRead,
+  BufReader,
+  Read,
+  Write,
+};
+use std::process::{
+  Child,
+  ChildStdin,
+  ChildStdout,
+  Command,
+  Stdio,
+};
+
+use serde_json::{
+  json,
+  Value,
+};
+
+const URI: &str = "file:///schema.dbml";
+
+/// A minimal LSP client talking to the server over stdio.
+struct Client {
+  child: Child,
+  stdin: ChildStdin,
+  stdout: BufReader<ChildStdout>,
+  next_id: u64,
+}
+
+impl Client {
+  fn spawn() -> Self {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dbml-lsp"))
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .unwrap();
+    let stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    let mut client = Self {
+      child,
+      stdin,
+      stdout,
+      next_id: 0,
+    };
+    client.request("initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+
+    client
+  }
+
+  fn send(&mut self, msg: Value) {
+    let body = msg.to_string();
+
+    write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    self.stdin.flush().unwrap();
+  }
+
+  fn recv(&mut self) -> Value {
+    let mut len = 0;
+    loop {
+      let mut header = String::new();
+      assert_ne!(
+        self.stdout.read_line(&mut header).unwrap(),
+        0,
+        "server closed the connection"
+      );
+
+      match header.trim_end() {
+        "" => break,
+        header => {
+          if let Some(value) = header.strip_prefix("Content-Length: ") {
+            len = value.parse().unwrap();
+          }
+        }
+      }
+    }
+
+    let mut body = vec![0; len];
+    self.stdout.read_exact(&mut body).unwrap();
+
+    serde_json::from_slice(&body).unwrap()
+  }
+
+  fn request(&mut self, method: &str, params: Value) -> Value {
+    self.next_id += 1;
+    let id = self.next_id;
+    self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+    loop {
+      let msg = self.recv();
+      if msg["id"] == id {
+        return msg["result"].clone();
+      }
+    }
+  }
+
+  fn notify(&mut self, method: &str, params: Value) {
+    self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+  }
+
+  fn wait_diagnostics(&mut self) -> Value {
+    loop {
+      let msg = self.recv();
+      if msg["method"] == "textDocument/publishDiagnostics" {
+        return msg["params"]["diagnostics"].clone();
+      }
+    }
+  }
+
+  fn open(&mut self, text: &str) -> Value {
+    self.notify(
+      "textDocument/didOpen",
+      json!({ "textDocument": { "uri": URI, "languageId": "dbml", "version": 1, "text": text } }),
+    );
+
+    self.wait_diagnostics()
+  }
+
+  fn at(&mut self, method: &str, line: u32, character: u32) -> Value {
+    let mut params = json!({
+      "textDocument": { "uri": URI },
+      "position": { "line": line, "character": character },
+    });
+    if method == "textDocument/references" {
+      params["context"] = json!({ "includeDeclaration": true });
+    }
+
+    self.request(method, params)
+  }
+
+  fn shutdown(mut self) {
+    self.request("shutdown", Value::Null);
+    self.notify("exit", Value::Null);
+
+    assert!(self.child.wait().unwrap().success());
+  }
+}
+
+impl Drop for Client {
+  fn drop(&mut self) {
+    // make sure a failing test does not leave the server running
+    let _ = self.child.kill();
+  }
+}
+
+const SCHEMA: &str = r#"Project demo {
+  database_type: 'PostgreSQL'
+}
+
+Enum status {
+  active
+  inactive
+}
+
+Table users as U {
+  id int [pk]
+  status status [note: 'account status']
+}
+
+Table posts {
+  id int [pk]
+  user_id int [ref: > U.id]
+}
+
+TableGroup blog {
+  users
+  posts
+}
+"#;
+
+#[test]
+fn lsp_navigation() {
+  let mut client = Client::spawn();
+
+  client.open(SCHEMA);
+
+  // hover on the `status` column
+  let hover = client.at("textDocument/hover", 11, 3);
+  let value = hover["contents"]["value"].as_str().unwrap();
+  assert!(value.contains("status status"), "{}", value);
+  assert!(value.contains("account status"), "{}", value);
+
+  // `U.id` in the inline ref jumps to `users.id`
+  let definition = client.at("textDocument/definition", 16, 25);
+  assert_eq!(definition["range"]["start"], json!({ "line": 10, "character": 2 }));
+
+  // the enum-typed column jumps to the enum
+  let definition = client.at("textDocument/definition", 11, 10);
+  assert_eq!(definition["range"]["start"], json!({ "line": 4, "character": 5 }));
+
+  // the table group item jumps to the table
+  let definition = client.at("textDocument/definition", 20, 3);
+  assert_eq!(definition["range"]["start"], json!({ "line": 9, "character": 6 }));
+
+  // references of `users` include the declaration, the inline ref and the group item
+  let references = client.at("textDocument/references", 9, 7);
+  let lines: Vec<_> = references
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|location| location["range"]["start"]["line"].clone())
+    .collect();
+  assert_eq!(lines, vec![json!(9), json!(16), json!(20)]);
+
+  let symbols = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": URI } }));
+  let names: Vec<_> = symbols
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|symbol| symbol["name"].as_str().unwrap().to_string())
+    .collect();
+  assert_eq!(names, vec!["users", "posts", "status", "blog"]);
+  assert_eq!(symbols[0]["children"][1]["name"], "status");
+
+  client.shutdown();
+}
+
+#[test]
+fn lsp_diagnostics_and_completion() {
+  let mut client = Client::spawn();
+
+  let diagnostics = client.open(&SCHEMA.replace("Table posts", "Table users"));
+  assert_eq!(diagnostics[0]["code"], "E0010");
+  assert_eq!(
+    diagnostics[0]["relatedInformation"][0]["location"]["range"]["start"],
+    json!({ "line": 9, "character": 6 })
+  );
+
+  let text = SCHEMA.replace("user_id int [ref: > U.id]", "user_id int [ref: > users.");
+  client.notify(
+    "textDocument/didChange",
+    json!({
+      "textDocument": { "uri": URI, "version": 2 },
+      "contentChanges": [{ "text": text }],
+    }),
+  );
+  let diagnostics = client.wait_diagnostics();
+  assert_eq!(diagnostics[0]["code"], "E0000");
+
+  let completion = client.at("textDocument/completion", 16, 28);
+  let labels: Vec<_> = completion
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|item| item["label"].as_str().unwrap().to_string())
+    .collect();
+  assert_eq!(labels, vec!["id", "status"]);
+
+  let completion = client.at("textDocument/completion", 16, 22);
+  let labels: Vec<_> = completion
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|item| item["label"].as_str().unwrap().to_string())
+    .collect();
+  assert_eq!(labels, vec!["users", "U"]);
+
+  client.shutdown();
+}
+
+#[test]
+fn lsp_malformed_notifications() {
+  let mut client = Client::spawn();
+
+  for method in [
+    "textDocument/didOpen",
+    "textDocument/didChange",
+    "textDocument/didClose",
+  ] {
+    client.notify(method, json!({ "textDocument": 42 }));
+
+    let msg = client.recv();
+    assert_eq!(msg["method"], "window/logMessage");
+    assert_eq!(msg["params"]["type"], 1);
+    let message = msg["params"]["message"].as_str().unwrap();
+    assert!(
+      message.starts_with(&format!("invalid params of {}: ", method)),
+      "{}",
+      message
+    );
+  }
+
+  // the server keeps serving after the malformed notifications
+  assert!(client.open(SCHEMA).is_array());
+
+  client.shutdown();
+}
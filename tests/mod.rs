@@ -1,3 +1,6 @@
+// the baseline tests predate the clippy gate and are kept as written
+#![allow(clippy::redundant_pattern_matching)]
+
 use std::fs;
 use std::io::Result;
 use std::path::{
@@ -78,3 +81,2186 @@ fn parse_dbml_checked_tmp() -> Result<()> {
 
   Ok(())
 }
+
+/// Removes source-dependent fields from the debug output of an AST to compare its semantics.
+fn strip_source_info(debug_output: &str) -> String {
+  debug_output
+    .lines()
+    .filter(|line| {
+      let line = line.trim_start();
+
+      !line.starts_with("span_range:") && !line.starts_with("raw:") && !line.starts_with("input:")
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+#[test]
+fn emit_dbml_round_trip() -> Result<()> {
+  let testing_dbml_paths = read_dbml_dir("tests/dbml")?;
+
+  for path in testing_dbml_paths {
+    let content = fs::read_to_string(&path)?;
+    let parsed = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+
+    let emitted = parsed.to_dbml();
+    let reparsed = dbml_rs::parse_dbml_unchecked(&emitted)
+      .unwrap_or_else(|err| panic!("{:?}: emitted output is unparsable\n{}\n{}", path, err, emitted));
+
+    assert_eq!(
+      strip_source_info(&format!("{:#?}", parsed)),
+      strip_source_info(&format!("{:#?}", reparsed)),
+      "{:?}: round-trip mismatch",
+      path
+    );
+  }
+
+  Ok(())
+}
+
+#[test]
+fn format_dbml_idempotent() -> Result<()> {
+  use dbml_rs::formatter::*;
+
+  let testing_dbml_paths = read_dbml_dir("tests/dbml")?;
+  let options_list = [
+    FormatOptions::default(),
+    FormatOptions {
+      indent_width: 4,
+      quote_style: QuoteStyle::Always,
+      keyword_case: KeywordCase::Lower,
+    },
+  ];
+
+  for path in testing_dbml_paths {
+    let content = fs::read_to_string(&path)?;
+    let parsed = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+
+    for options in &options_list {
+      let formatted = format(&parsed, options);
+      let reparsed = dbml_rs::parse_dbml_unchecked(&formatted)
+        .unwrap_or_else(|err| panic!("{:?}: formatted output is unparsable\n{}\n{}", path, err, formatted));
+
+      assert_eq!(
+        formatted,
+        format(&reparsed, options),
+        "{:?}: formatting is not idempotent",
+        path
+      );
+      assert_eq!(
+        parsed.blocks.len(),
+        reparsed.blocks.len(),
+        "{:?}: formatting changed the schema",
+        path
+      );
+    }
+  }
+
+  Ok(())
+}
+
+#[test]
+fn format_dbml_comments() -> Result<()> {
+  use dbml_rs::formatter::*;
+
+  let content = fs::read_to_string("tests/dbml/formatter/comments.in.dbml")?;
+  let expected = fs::read_to_string("tests/dbml/formatter/comments.out.dbml")?;
+  let parsed = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+
+  assert_eq!(format(&parsed, &FormatOptions::default()), expected);
+
+  let options = FormatOptions {
+    indent_width: 4,
+    quote_style: QuoteStyle::Always,
+    keyword_case: KeywordCase::Upper,
+  };
+  let content = "Table users {\n  // the key\n  id int [not null, pk] // trailing\n}\n";
+  let parsed = dbml_rs::parse_dbml_unchecked(content).unwrap();
+
+  assert_eq!(
+    format(&parsed, &options),
+    "TABLE \"users\" {\n    // the key\n    \"id\" int [pk, not null] // trailing\n}\n"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn analyze_all_collects_errors() {
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int [pk]
+  id int
+}
+
+Table users {
+  name varchar
+}
+
+Enum status {
+  active
+  active
+}
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&ast);
+  let messages: Vec<_> = diags.iter().map(|diag| diag.message.as_str()).collect();
+
+  assert_eq!(
+    messages,
+    vec!["Duplicate column name", "Duplicate table name", "Duplicate enum value"]
+  );
+  assert_eq!(
+    dbml_rs::analyze(&ast).unwrap_err().variant.message(),
+    "Duplicate column name"
+  );
+}
+
+#[test]
+fn analyze_all_reports_conflicts_once() {
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int [pk]
+}
+
+Table posts {
+  id int [pk]
+  user_id int
+  editor_id int
+}
+
+Ref: posts.user_id > users.id
+Ref: posts.user_id > users.id
+Ref: posts.editor_id > users.id
+Ref: posts.user_id > users.id
+Ref: users.id < posts.editor_id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&ast);
+  let reports: Vec<_> = diags
+    .iter()
+    .map(|diag| {
+      let lines: Vec<_> = core::iter::once(&diag.primary_span)
+        .chain(diag.secondary_spans.iter().map(|label| &label.span_range))
+        .map(|span_range| content[..span_range.start].lines().count())
+        .collect();
+
+      (diag.code, lines)
+    })
+    .collect();
+
+  assert_eq!(reports, vec![("E0012", vec![16, 17, 19]), ("E0012", vec![18, 20])]);
+}
+
+#[test]
+fn diagnostic_codes() {
+  use dbml_rs::diagnostic::Severity;
+  use dbml_rs::err::{
+    Err,
+    InvalidForeignKeyErr,
+  };
+
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int [pk]
+  code int
+}
+
+Table users {
+  id int [pk]
+}
+
+Table posts {
+  id int [pk]
+  user_id int
+}
+
+Ref: users.code <> posts.user_id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&ast);
+
+  assert_eq!(diags.len(), 2);
+
+  let dup = &diags[0];
+  assert_eq!(dup.err(), Some(&Err::DuplicateTableName));
+  assert_eq!(dup.code, "E0010");
+  assert_eq!(dup.severity, Severity::Error);
+  assert!(dup.help.is_some());
+  assert_eq!(content[dup.primary_span.clone()].trim(), "users");
+  assert_eq!(dup.to_string(), "error[E0010]: Duplicate table name");
+
+  assert_eq!(
+    diags[1].err(),
+    Some(&Err::InvalidForeignKey {
+      err: InvalidForeignKeyErr::Many2Many
+    })
+  );
+  assert_eq!(diags[1].code, "E0039");
+
+  let err = diags[1].to_pest_error(content);
+  assert_eq!(err.variant.message(), diags[1].message);
+
+  // spans out of the input, as in imported schemas, are clamped
+  let err = diags[1].to_pest_error("Ref");
+  assert_eq!(err.variant.message(), diags[1].message);
+  assert_eq!(err.location, pest::error::InputLocation::Span((3, 3)));
+}
+
+#[test]
+fn diagnostic_secondary_spans() {
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users as U {
+  id int [pk]
+  id int
+}
+
+Table posts as U {
+  id int [pk]
+  user_id int
+}
+
+Table users {
+  id int
+}
+
+Ref: posts.user_id > users.id
+Ref: posts.user_id > users.id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&ast);
+  let reports: Vec<_> = diags
+    .iter()
+    .map(|diag| {
+      let labels: Vec<_> = diag
+        .secondary_spans
+        .iter()
+        .map(|label| (content[label.span_range.clone()].trim(), label.message.as_str()))
+        .collect();
+
+      (diag.code, labels)
+    })
+    .collect();
+
+  assert_eq!(
+    reports,
+    vec![
+      ("E0011", vec![("id int [pk]", "previously defined here")]),
+      ("E0017", vec![("U", "previously defined here")]),
+      ("E0010", vec![("users", "previously defined here")]),
+      ("E0012", vec![("posts.user_id > users.id", "conflicting relation here")]),
+    ]
+  );
+}
+
+#[test]
+fn parse_dbml_recoverable() {
+  let content = r#"
+Table users {
+  id int [pk]
+}
+
+Table posts {
+  id int [pk
+  user_id int
+}
+
+Enum status {
+  active
+}
+
+Tab
+"#;
+
+  let (ast, diags) = dbml_rs::parse_dbml_recoverable(content);
+  let names: Vec<_> = ast
+    .tables()
+    .iter()
+    .map(|table| table.ident.name.to_string.clone())
+    .collect();
+
+  assert_eq!(names, vec!["users"]);
+  assert_eq!(ast.enums().len(), 1);
+  assert_eq!(ast.input, content);
+  assert_eq!(diags.len(), 2);
+  assert!(diags
+    .iter()
+    .all(|diag| diag.code == dbml_rs::Diagnostic::SYNTAX_ERROR_CODE && diag.err().is_none()));
+  assert!(content[diags[0].primary_span.start..].starts_with("pk\n  user_id"));
+  assert!(content[diags[1].primary_span.start..].starts_with("Tab"));
+
+  let unclosed = "Table users {\n  id int\n\nTable posts {\n  id int\n}\n";
+  let (ast, diags) = dbml_rs::parse_dbml_recoverable(unclosed);
+
+  assert_eq!(ast.tables().len(), 1);
+  assert_eq!(ast.tables()[0].ident.name.to_string, "posts");
+  assert_eq!(diags.len(), 1);
+
+  let (_, diags) = dbml_rs::parse_dbml_recoverable(content.split("Tab\n").next().unwrap());
+  assert_eq!(diags.len(), 1);
+
+  let note = "Table users {\n  id int [pk\n  Note: '''\nTable in a note\n'''\n}\n\nTable posts {\n  id int\n}\n";
+  let (ast, diags) = dbml_rs::parse_dbml_recoverable(note);
+
+  assert_eq!(ast.tables().len(), 1);
+  assert_eq!(ast.tables()[0].ident.name.to_string, "posts");
+  assert_eq!(diags.len(), 1);
+
+  let broken: String = (0..1000)
+    .map(|i| format!("Table t{} {{\n  id int [\n}}\n\nTable u{} {{\n  id int\n}}\n", i, i))
+    .collect();
+  let (ast, diags) = dbml_rs::parse_dbml_recoverable(&broken);
+
+  assert_eq!(ast.tables().len(), 1000);
+  assert_eq!(diags.len(), 1000);
+  assert!(diags
+    .iter()
+    .all(|diag| broken[..diag.primary_span.start].ends_with("  id int [\n")));
+}
+
+#[test]
+fn parse_schema_qualified_col_type() {
+  use dbml_rs::ast::ColumnTypeName;
+
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Enum v2.status {
+  active
+}
+
+Table users {
+  status v2.status [not null]
+}
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let col = &ast.tables()[0].cols[0];
+  assert_eq!(col.r#type.type_name, ColumnTypeName::Raw("v2.status".to_string()));
+  assert!(dbml_rs::parse_dbml(content).is_ok());
+}
+
+#[test]
+fn source_map_line_col() {
+  use dbml_rs::source_map::LineCol;
+
+  let content = "Table users {\r\n  name varchar [note: '名前 😀']\n}\n";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let source_map = dbml_rs::SourceMap::from(&ast);
+
+  assert_eq!(source_map.line_count(), 4);
+  assert_eq!(source_map.line(0), Some("Table users {"));
+
+  let emoji = content.find('😀').unwrap();
+  assert_eq!(
+    source_map.line_col(emoji),
+    Some(LineCol {
+      line: 1,
+      col: 30,
+      col_utf16: 26
+    })
+  );
+  assert_eq!(source_map.line_col(emoji + 1), None);
+  assert_eq!(source_map.offset(1, 30), Some(emoji));
+  assert_eq!(source_map.offset_utf16(1, 26), Some(emoji));
+  assert_eq!(source_map.offset_utf16(1, 27), None);
+  assert_eq!(source_map.offset(0, 13), Some(13));
+  assert_eq!(source_map.offset(0, 14), None);
+
+  let col = &ast.tables()[0].cols[0];
+  assert_eq!(
+    source_map.snippet(&col.span_range),
+    Some("  name varchar [note: '名前 😀']")
+  );
+  assert_eq!(
+    source_map.line_col(ast.tables()[0].span_range.end),
+    Some(LineCol {
+      line: 2,
+      col: 1,
+      col_utf16: 1
+    })
+  );
+}
+
+#[test]
+fn diagnostic_render() {
+  let content =
+    "Project demo {\n  database_type: 'PostgreSQL'\n}\n\nTable users {\n  id int\n}\n\nTable users {\n  id int\n}\n";
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&ast);
+  let report = diags[0].render(&dbml_rs::SourceMap::from(&ast), "schema.dbml");
+
+  assert_eq!(
+    report,
+    r#"error[E0010]: Duplicate table name
+ --> schema.dbml:9:7
+  |
+9 | Table users {
+  |       ^^^^^
+  |
+5 | Table users {
+  |       ----- previously defined here
+  |
+  = help: rename one of the tables or move it into another schema
+"#
+  );
+}
+
+#[test]
+fn generate_postgres() {
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Enum status {
+  active
+  archived
+}
+
+Table users {
+  id int [pk, increment]
+  email varchar(255) [unique, not null, note: '''user's email''']
+  created_at timestamptz [default: `now()`]
+  Note: 'All users'
+}
+
+Table blog.posts {
+  id bigint [pk]
+  user_id int [ref: > users.id]
+  status status [default: active]
+  title varchar
+
+  indexes {
+    (user_id, title) [unique, name: 'posts_user_title']
+    title [type: gin]
+  }
+}
+
+Table tags {
+  id bigint [pk]
+}
+
+Table profiles {
+  user_id int [unique]
+}
+
+Ref post_tags: blog.posts.id <> tags.id [delete: cascade]
+Ref: users.id - profiles.user_id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = dbml_rs::generator::postgres::generate(&ast).unwrap();
+
+  assert_eq!(
+    sql,
+    r#"CREATE SCHEMA IF NOT EXISTS "blog";
+
+CREATE TYPE "status" AS ENUM ('active', 'archived');
+
+CREATE TABLE "users" (
+  "id" integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  "email" varchar(255) UNIQUE NOT NULL,
+  "created_at" timestamptz DEFAULT now()
+);
+
+CREATE TABLE "blog"."posts" (
+  "id" bigint PRIMARY KEY,
+  "user_id" integer,
+  "status" "status" DEFAULT 'active',
+  "title" varchar
+);
+
+CREATE TABLE "tags" (
+  "id" bigint PRIMARY KEY
+);
+
+CREATE TABLE "profiles" (
+  "user_id" integer UNIQUE
+);
+
+CREATE TABLE "blog"."posts_tags" (
+  "posts_id" bigint,
+  "tags_id" bigint,
+  PRIMARY KEY ("posts_id", "tags_id")
+);
+
+ALTER TABLE "blog"."posts_tags" ADD FOREIGN KEY ("posts_id") REFERENCES "blog"."posts" ("id") ON DELETE CASCADE;
+ALTER TABLE "blog"."posts_tags" ADD FOREIGN KEY ("tags_id") REFERENCES "tags" ("id") ON DELETE CASCADE;
+ALTER TABLE "profiles" ADD FOREIGN KEY ("user_id") REFERENCES "users" ("id");
+ALTER TABLE "blog"."posts" ADD FOREIGN KEY ("user_id") REFERENCES "users" ("id");
+
+CREATE UNIQUE INDEX "posts_user_title" ON "blog"."posts" ("user_id", "title");
+CREATE INDEX ON "blog"."posts" USING gin ("title");
+
+COMMENT ON TABLE "users" IS 'All users';
+COMMENT ON COLUMN "users"."email" IS 'user''s email';
+"#
+  );
+
+  let content =
+    "Project p {\n  database_type: 'PostgreSQL'\n}\n\nTable users {\n  id int\n\n  indexes {\n    id [pk]\n  }\n}";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = dbml_rs::generator::postgres::generate(&ast).unwrap();
+  assert_eq!(
+    sql,
+    "CREATE TABLE \"users\" (\n  \"id\" integer,\n  PRIMARY KEY (\"id\")\n);\n"
+  );
+
+  let content = r#"
+Project p {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id serial [pk]
+}
+
+Ref friends: users.id <> users.id
+"#;
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = dbml_rs::generator::postgres::generate(&ast).unwrap();
+  assert_eq!(
+    sql,
+    r#"CREATE TABLE "users" (
+  "id" serial PRIMARY KEY
+);
+
+CREATE TABLE "users_users" (
+  "users_id" integer,
+  "users_id_2" integer,
+  PRIMARY KEY ("users_id", "users_id_2")
+);
+
+ALTER TABLE "users_users" ADD FOREIGN KEY ("users_id") REFERENCES "users" ("id");
+ALTER TABLE "users_users" ADD FOREIGN KEY ("users_id_2") REFERENCES "users" ("id");
+"#
+  );
+
+  let ast = dbml_rs::parse_dbml_unchecked("Table users {\n  id int\n}").unwrap();
+  let diags = dbml_rs::generator::postgres::generate(&ast).unwrap_err();
+  assert_eq!(diags[0].code, "E0019");
+}
+
+#[test]
+fn generate_mysql() {
+  use dbml_rs::generator::mysql::{
+    generate,
+    MySqlOptions,
+  };
+
+  let content = r#"
+Project project_name {
+  database_type: 'MySQL'
+}
+
+Enum status {
+  active
+  archived
+}
+
+Table users {
+  id int [pk, increment]
+  email varchar [unique, not null, note: 'login e-mail']
+  status status [default: active]
+  token char(36) [default: `uuid()`]
+  created_at timestamp [default: `current_timestamp`]
+  Note: 'All users'
+}
+
+Table orders {
+  user_id int
+  seq int
+
+  indexes {
+    (user_id, seq) [pk]
+    seq [type: hash]
+  }
+}
+
+Table order_items {
+  id serial [pk]
+  user_id int
+  seq int
+}
+
+Ref: order_items.(user_id, seq) > orders.(user_id, seq) [delete: cascade]
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &MySqlOptions::default()).unwrap();
+
+  assert_eq!(
+    sql,
+    r#"CREATE TABLE `users` (
+  `id` int AUTO_INCREMENT PRIMARY KEY,
+  `email` varchar(255) NOT NULL UNIQUE COMMENT 'login e-mail',
+  `status` enum('active', 'archived') DEFAULT 'active',
+  `token` char(36) DEFAULT (uuid()),
+  `created_at` datetime DEFAULT current_timestamp
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='All users';
+
+CREATE TABLE `orders` (
+  `user_id` int,
+  `seq` int,
+  PRIMARY KEY (`user_id`, `seq`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE `order_items` (
+  `id` int AUTO_INCREMENT PRIMARY KEY,
+  `user_id` int,
+  `seq` int
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+ALTER TABLE `order_items` ADD FOREIGN KEY (`user_id`, `seq`) REFERENCES `orders` (`user_id`, `seq`) ON DELETE CASCADE;
+
+CREATE INDEX `orders_index_0` ON `orders` (`seq`) USING HASH;
+"#
+  );
+
+  let content = content.replace("[type: hash]", "[type: gin]");
+  let ast = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+  let diags = generate(&ast, &MySqlOptions::default()).unwrap_err();
+  assert_eq!(diags[0].code, "G0001");
+  assert!(diags[0].err().is_none());
+  assert_eq!(
+    diags[0].generator_err(),
+    Some(&dbml_rs::generator::err::Err::UnsupportedIndexType {
+      r#type: "gin".to_string(),
+      dialect: "MySQL",
+    })
+  );
+  assert_eq!(
+    diags[0].message,
+    "Unsupported index type: 'gin' indexes are not supported by MySQL"
+  );
+
+  let content =
+    "Project p {\n  database_type: 'MySQL'\n}\n\nTable users {\n  id int\n\n  indexes {\n    id [pk]\n  }\n}";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &MySqlOptions::default()).unwrap();
+  assert_eq!(
+    sql,
+    "CREATE TABLE `users` (\n  `id` int,\n  PRIMARY KEY (`id`)\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;\n"
+  );
+}
+
+#[test]
+fn generate_mssql() {
+  use dbml_rs::generator::mssql::generate;
+
+  let content = r#"
+Project project_name {
+  database_type: 'MSSQL'
+}
+
+Enum status {
+  active
+  archived
+}
+
+Table sales.orders {
+  id int [pk, increment]
+  code varchar(32) [unique, not null, note: '''customer's code''']
+  status status [not null, default: active]
+  paid bool [default: false]
+  Note: 'All orders'
+
+  indexes {
+    (code, status) [name: 'orders_code_status']
+  }
+}
+
+Table items {
+  order_id int
+  seq int
+
+  indexes {
+    (order_id, seq) [pk]
+  }
+}
+
+Ref: items.order_id > sales.orders.id [delete: restrict]
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast).unwrap();
+
+  assert_eq!(
+    sql,
+    r#"CREATE SCHEMA [sales];
+GO
+
+CREATE TABLE [sales].[orders] (
+  [id] int IDENTITY(1, 1) PRIMARY KEY,
+  [code] nvarchar(32) UNIQUE NOT NULL,
+  [status] nvarchar(255) NOT NULL DEFAULT N'active' CHECK ([status] IN (N'active', N'archived')),
+  [paid] bit DEFAULT 0
+);
+
+CREATE TABLE [items] (
+  [order_id] int,
+  [seq] int,
+  PRIMARY KEY ([order_id], [seq])
+);
+
+ALTER TABLE [items] ADD FOREIGN KEY ([order_id]) REFERENCES [sales].[orders] ([id]) ON DELETE NO ACTION;
+
+CREATE INDEX [orders_code_status] ON [sales].[orders] ([code], [status]);
+
+EXEC sp_addextendedproperty @name = N'MS_Description', @value = N'All orders', @level0type = N'SCHEMA', @level0name = N'sales', @level1type = N'TABLE', @level1name = N'orders';
+EXEC sp_addextendedproperty @name = N'MS_Description', @value = N'customer''s code', @level0type = N'SCHEMA', @level0name = N'sales', @level1type = N'TABLE', @level1name = N'orders', @level2type = N'COLUMN', @level2name = N'code';
+"#
+  );
+
+  let content = content.replace("(code, status) [name: 'orders_code_status']", "`lower(code)`");
+  let ast = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+  let diags = generate(&ast).unwrap_err();
+  assert_eq!(diags[0].code, "G0002");
+  assert_eq!(
+    diags[0].message,
+    "Unsupported expression index: expression indexes are not supported by SQL Server"
+  );
+
+  let content =
+    "Project p {\n  database_type: 'MSSQL'\n}\n\nTable users {\n  id int\n\n  indexes {\n    id [pk]\n  }\n}";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast).unwrap();
+  assert_eq!(sql, "CREATE TABLE [users] (\n  [id] int,\n  PRIMARY KEY ([id])\n);\n");
+}
+
+#[test]
+fn generate_sqlite() {
+  use dbml_rs::generator::sqlite::{
+    generate,
+    SchemaMode,
+    SqliteOptions,
+  };
+
+  let content = r#"
+Project project_name {
+  database_type: 'SQLite'
+}
+
+Enum status {
+  active
+  archived
+}
+
+Table sales.orders {
+  id int [pk, increment]
+  code varchar(32) [unique, not null]
+  status status [not null, default: active]
+  created_at timestamp [default: `CURRENT_TIMESTAMP`]
+
+  indexes {
+    `lower(code)`
+  }
+}
+
+Table items {
+  order_id int
+  seq int
+
+  indexes {
+    (order_id, seq) [pk]
+  }
+}
+
+Table receipts {
+  order_id int [unique]
+}
+
+Ref: items.order_id > sales.orders.id [delete: cascade]
+Ref: sales.orders.id - receipts.order_id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &SqliteOptions::default()).unwrap();
+
+  assert_eq!(
+    sql,
+    r#"CREATE TABLE "sales_orders" (
+  "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+  "code" TEXT UNIQUE NOT NULL,
+  "status" TEXT NOT NULL DEFAULT 'active' CHECK ("status" IN ('active', 'archived')),
+  "created_at" TEXT DEFAULT (CURRENT_TIMESTAMP)
+);
+
+CREATE TABLE "items" (
+  "order_id" INTEGER,
+  "seq" INTEGER,
+  PRIMARY KEY ("order_id", "seq"),
+  FOREIGN KEY ("order_id") REFERENCES "sales_orders" ("id") ON DELETE CASCADE
+);
+
+CREATE TABLE "receipts" (
+  "order_id" INTEGER UNIQUE,
+  FOREIGN KEY ("order_id") REFERENCES "sales_orders" ("id")
+);
+
+CREATE INDEX "sales_orders_index_0" ON "sales_orders" ((lower(code)));
+"#
+  );
+
+  let options = SqliteOptions {
+    schema_mode: SchemaMode::Reject,
+  };
+  let diags = generate(&ast, &options).unwrap_err();
+  assert_eq!(diags.len(), 1);
+  assert_eq!(diags[0].code, "G0003");
+  assert_eq!(
+    diags[0].message,
+    "Unsupported schema: schemas are not supported by SQLite"
+  );
+
+  let content =
+    "Project p {\n  database_type: 'SQLite'\n}\n\nTable users {\n  id int\n\n  indexes {\n    id [pk]\n  }\n}";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &SqliteOptions::default()).unwrap();
+  assert_eq!(
+    sql,
+    "CREATE TABLE \"users\" (\n  \"id\" INTEGER,\n  PRIMARY KEY (\"id\")\n);\n"
+  );
+}
+
+#[test]
+fn generate_oracle() {
+  use dbml_rs::generator::oracle::{
+    generate,
+    OracleOptions,
+  };
+
+  let content = r#"
+Project project_name {
+  database_type: 'Oracle'
+}
+
+Table orders {
+  id int [pk, increment]
+  ticket bigserial
+  paid bool [not null, default: false]
+  Note: 'All orders'
+}
+
+Table items {
+  order_id int
+  seq int
+
+  indexes {
+    (order_id, seq) [pk]
+    seq [name: 'items_seq']
+  }
+}
+
+Ref: items.order_id > orders.id [delete: cascade, update: cascade]
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &OracleOptions::default()).unwrap();
+
+  assert_eq!(
+    sql,
+    r#"CREATE SEQUENCE "orders_ticket_seq";
+
+CREATE TABLE "orders" (
+  "id" NUMBER(10) GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  "ticket" NUMBER(19) DEFAULT "orders_ticket_seq".NEXTVAL,
+  "paid" NUMBER(1) DEFAULT 0 NOT NULL
+);
+
+CREATE TABLE "items" (
+  "order_id" NUMBER(10),
+  "seq" NUMBER(10),
+  PRIMARY KEY ("order_id", "seq")
+);
+
+ALTER TABLE "items" ADD FOREIGN KEY ("order_id") REFERENCES "orders" ("id") ON DELETE CASCADE;
+
+CREATE INDEX "items_seq" ON "items" ("seq");
+
+COMMENT ON TABLE "orders" IS 'All orders';
+"#
+  );
+
+  let options = OracleOptions { max_ident_len: 16 };
+  let diags = generate(&ast, &options).unwrap_err();
+  assert_eq!(diags.len(), 1);
+  assert_eq!(diags[0].code, "G0004");
+  assert_eq!(
+    diags[0].message,
+    "Identifier too long: 'orders_ticket_seq' exceeds the maximum length of 16 bytes in Oracle"
+  );
+
+  let content = r#"
+Project project_name {
+  database_type: 'Oracle'
+}
+
+Table customer_account_records {
+  id int [pk]
+}
+
+Table campaigns {
+  id int [pk]
+  owner_id int
+}
+
+Ref: customer_account_records.id <> campaigns.id
+Ref campaign_owner_account_fk: campaigns.owner_id > customer_account_records.id
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let options = OracleOptions { max_ident_len: 24 };
+  let diags = generate(&ast, &options).unwrap_err();
+  let messages: Vec<_> = diags.iter().map(|diag| diag.message.as_str()).collect();
+  assert_eq!(
+    messages,
+    [
+      "Identifier too long: 'customer_account_records_campaigns' exceeds the maximum length of 24 bytes in Oracle",
+      "Identifier too long: 'customer_account_records_id' exceeds the maximum length of 24 bytes in Oracle",
+      "Identifier too long: 'campaign_owner_account_fk' exceeds the maximum length of 24 bytes in Oracle",
+    ]
+  );
+  assert!(diags.iter().all(|diag| diag.code == "G0004"));
+  assert_eq!(
+    content[diags[0].primary_span.clone()].trim_end(),
+    "customer_account_records.id <> campaigns.id"
+  );
+
+  let content =
+    "Project p {\n  database_type: 'Oracle'\n}\n\nTable users {\n  id int\n\n  indexes {\n    id [pk]\n  }\n}";
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let sql = generate(&ast, &OracleOptions::default()).unwrap();
+  assert_eq!(
+    sql,
+    "CREATE TABLE \"users\" (\n  \"id\" NUMBER(10),\n  PRIMARY KEY (\"id\")\n);\n"
+  );
+}
+
+#[test]
+fn generate_mermaid() {
+  use dbml_rs::generator::mermaid::{
+    generate,
+    MermaidOptions,
+  };
+
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users as U {
+  id int [pk]
+  email varchar(255) [not null, note: 'Login "name"']
+  score "double precision"
+}
+
+Table profiles {
+  user_id int [pk]
+  bio text
+}
+
+Table posts {
+  id int [pk]
+  author_id int [not null]
+  editor_id int
+  slug varchar(100)
+  price decimal(10, 2)
+
+  indexes {
+    slug [unique]
+  }
+}
+
+Table tags {
+  id int [pk]
+}
+
+Table billing.invoices {
+  id int [pk]
+}
+
+Ref: posts.author_id > users.id
+Ref editor: users.id < posts.editor_id
+Ref: posts.id <> tags.id
+Ref: users.id - profiles.user_id
+
+TableGroup content {
+  posts
+  tags
+}
+
+TableGroup accounts {
+  U
+  profiles
+}
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+
+  assert_eq!(
+    generate(&ast, &MermaidOptions::default()).unwrap(),
+    r#"erDiagram
+  users {
+    int id PK
+    varchar(255) email "Login 'name'"
+    double_precision score
+  }
+  profiles {
+    int user_id PK, FK
+    text bio
+  }
+  posts {
+    int id PK
+    int author_id FK
+    int editor_id FK
+    varchar(100) slug UK
+    decimal(10_2) price
+  }
+  tags {
+    int id PK
+  }
+  "billing.invoices" {
+    int id PK
+  }
+  posts }o--|| users : "author_id"
+  users |o--o{ posts : "editor"
+  posts }o--o{ tags : "id"
+  users ||--o| profiles : "user_id"
+"#
+  );
+
+  let options = MermaidOptions {
+    table_group: Some("content".to_string()),
+  };
+  assert_eq!(
+    generate(&ast, &options).unwrap(),
+    r#"erDiagram
+  posts {
+    int id PK
+    int author_id
+    int editor_id
+    varchar(100) slug UK
+    decimal(10_2) price
+  }
+  tags {
+    int id PK
+  }
+  posts }o--o{ tags : "id"
+"#
+  );
+
+  let options = MermaidOptions {
+    table_group: Some("accounts".to_string()),
+  };
+  assert_eq!(
+    generate(&ast, &options).unwrap(),
+    r#"erDiagram
+  users {
+    int id PK
+    varchar(255) email "Login 'name'"
+    double_precision score
+  }
+  profiles {
+    int user_id PK, FK
+    text bio
+  }
+  users ||--o| profiles : "user_id"
+"#
+  );
+
+  let options = MermaidOptions {
+    table_group: Some("billing".to_string()),
+  };
+  let diags = generate(&ast, &options).unwrap_err();
+  assert_eq!(diags.len(), 1);
+  assert_eq!(diags[0].code, "E0028");
+  assert_eq!(&content[diags[0].primary_span.clone()], "project_name");
+}
+
+#[test]
+fn generate_dot() {
+  let content = r#"
+Project project_name {
+  database_type: 'PostgreSQL'
+}
+
+Table users as U [headercolor: #24292F] {
+  id int [pk]
+  email varchar(255) [not null]
+
+  indexes {
+    (id, email) [unique]
+  }
+}
+
+Table posts [headercolor: #FFD700] {
+  id int [pk]
+  author_id int [not null]
+  editor_id int
+}
+
+Table tags {
+  id int [pk]
+  "a<b>" text
+}
+
+Table billing.invoices {
+  id int [pk]
+  user_id int
+  user_email varchar(255)
+}
+
+Ref: posts.author_id > users.id
+Ref: users.id < posts.editor_id
+Ref: posts.id <> tags.id
+Ref: users.(id, email) - billing.invoices.(user_id, user_email)
+
+TableGroup accounts {
+  U
+  billing.invoices
+}
+"#;
+
+  let ast = dbml_rs::parse_dbml_unchecked(content).unwrap();
+
+  assert_eq!(
+    ast.to_dot().unwrap(),
+    r##"digraph dbml {
+  graph [rankdir=LR];
+  node [shape=plain, fontname="Helvetica"];
+  edge [fontname="Helvetica"];
+
+  subgraph "cluster_accounts" {
+    label="accounts";
+    "users" [label=<
+      <table border="0" cellborder="1" cellspacing="0" cellpadding="4">
+        <tr><td bgcolor="#24292F"><font color="#FFFFFF"><b>users</b></font></td></tr>
+        <tr><td port="id" align="left"><u>id</u> <i>int</i></td></tr>
+        <tr><td port="email" align="left">email <i>varchar(255)</i></td></tr>
+      </table>
+    >];
+    "billing.invoices" [label=<
+      <table border="0" cellborder="1" cellspacing="0" cellpadding="4">
+        <tr><td bgcolor="#E0E0E0"><font color="#000000"><b>billing.invoices</b></font></td></tr>
+        <tr><td port="id" align="left"><u>id</u> <i>int</i></td></tr>
+        <tr><td port="user_id" align="left">user_id <i>int</i></td></tr>
+        <tr><td port="user_email" align="left">user_email <i>varchar(255)</i></td></tr>
+      </table>
+    >];
+  }
+
+  "posts" [label=<
+    <table border="0" cellborder="1" cellspacing="0" cellpadding="4">
+      <tr><td bgcolor="#FFD700"><font color="#000000"><b>posts</b></font></td></tr>
+      <tr><td port="id" align="left"><u>id</u> <i>int</i></td></tr>
+      <tr><td port="author_id" align="left">author_id <i>int</i></td></tr>
+      <tr><td port="editor_id" align="left">editor_id <i>int</i></td></tr>
+    </table>
+  >];
+
+  "tags" [label=<
+    <table border="0" cellborder="1" cellspacing="0" cellpadding="4">
+      <tr><td bgcolor="#E0E0E0"><font color="#000000"><b>tags</b></font></td></tr>
+      <tr><td port="id" align="left"><u>id</u> <i>int</i></td></tr>
+      <tr><td port="a&lt;b&gt;" align="left">a&lt;b&gt; <i>text</i></td></tr>
+    </table>
+  >];
+
+  "posts":"author_id" -> "users":"id" [dir=both, arrowtail=crowodot, arrowhead=teetee];
+  "users":"id" -> "posts":"editor_id" [dir=both, arrowtail=teeodot, arrowhead=crowodot];
+  "posts":"id" -> "tags":"id" [dir=both, arrowtail=crowodot, arrowhead=crowodot];
+  "users":"id" -> "billing.invoices":"user_id" [dir=both, arrowtail=teeodot, arrowhead=teeodot];
+  "users":"email" -> "billing.invoices":"user_email" [dir=both, arrowtail=teeodot, arrowhead=teeodot];
+}
+"##
+  );
+
+  // header colors not written by the parser fall back to the default one
+  let mut ast = dbml_rs::parse_dbml_unchecked(
+    "Project p {\n  database_type: 'PostgreSQL'\n}\nTable users [headercolor: #FFF] {\n  id int\n}",
+  )
+  .unwrap();
+  for block in &mut ast.blocks {
+    if let dbml_rs::ast::TopLevelBlock::Table(table) = block {
+      let attr = &mut table.settings.as_mut().unwrap().attributes[0];
+      attr.value.as_mut().unwrap().value = dbml_rs::ast::Value::HexColor("#ABCD".to_string());
+    }
+  }
+  let dot = dbml_rs::generator::dot::generate(&ast).unwrap();
+  assert!(
+    dot.contains(r##"<tr><td bgcolor="#E0E0E0"><font color="#000000"><b>users</b></font></td></tr>"##),
+    "{}",
+    dot
+  );
+}
+
+/// Formats each top-level block separately, so that schemas can be compared regardless of the
+/// order of their blocks.
+fn format_blocks(schema_block: &dbml_rs::ast::SchemaBlock) -> Vec<String> {
+  use dbml_rs::ast::SchemaBlock;
+  use dbml_rs::formatter::*;
+
+  let mut blocks: Vec<_> = schema_block
+    .blocks
+    .iter()
+    .map(|block| {
+      let schema_block = SchemaBlock {
+        blocks: vec![block.clone()],
+        ..Default::default()
+      };
+
+      format(&schema_block, &FormatOptions::default())
+    })
+    .collect();
+  blocks.sort();
+
+  blocks
+}
+
+/// Reads the expected output of an importer, written in the double-quoted index names of dbml.js.
+fn read_importer_fixture(path: &str) -> Result<String> {
+  let content = fs::read_to_string(path)?;
+
+  Ok(
+    content
+      .lines()
+      .map(|line| {
+        match line.find("name: \"") {
+          Some(i) if line.ends_with("\"]") => {
+            format!("{}'{}']", &line[..i + 6], &line[i + 7..line.len() - 2])
+          }
+          _ => line.to_string(),
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("\n"),
+  )
+}
+
+#[test]
+fn import_postgres() -> Result<()> {
+  use dbml_rs::importer::postgres::import;
+
+  for name in ["general_schema", "multiple_schema"] {
+    let input = fs::read_to_string(format!("tests/dbml/postgres_importer/{}.in.sql", name))?;
+    let expected = read_importer_fixture(&format!("tests/dbml/postgres_importer/{}.out.dbml", name))?;
+
+    let imported = import(&input).unwrap_or_else(|diag| panic!("{}: {}", name, diag));
+    let expected = dbml_rs::parse_dbml_unchecked(&expected).unwrap();
+
+    assert_eq!(
+      format_blocks(&imported),
+      format_blocks(&expected),
+      "{}: import mismatch",
+      name
+    );
+  }
+
+  let input = r#"
+/* accounts of the blog */
+CREATE TABLE users (id bigserial PRIMARY KEY, name text NOT NULL DEFAULT 'anonymous'::text);
+CREATE TABLE posts (
+  id bigint GENERATED ALWAYS AS IDENTITY,
+  user_id bigint,
+  CONSTRAINT posts_user_fk FOREIGN KEY (user_id) REFERENCES users ON DELETE CASCADE
+);
+CREATE INDEX ON posts (lower(title), user_id DESC);
+"#;
+  let imported = import(input).unwrap();
+
+  assert_eq!(
+    dbml_rs::formatter::format(&imported, &Default::default()),
+    r#"Table users {
+  id   bigint [pk, increment]
+  name text   [not null, default: 'anonymous']
+}
+
+Table posts {
+  id      bigint [increment]
+  user_id bigint
+
+  Indexes {
+    (`lower(title)`, user_id)
+  }
+}
+
+Ref posts_user_fk: users.id < posts.user_id [delete: cascade]
+"#
+  );
+
+  let diag = import("CREATE TABLE users (id int,, name text);").unwrap_err();
+  assert_eq!(diag.code, "E0000");
+  assert_eq!(diag.message, "expected an identifier");
+  assert_eq!(diag.primary_span, 27..28);
+
+  // semantic errors of imported schemas are reported within the empty input
+  let imported = import("CREATE TABLE users (id int PRIMARY KEY, id text);").unwrap();
+  let (_, diags) = dbml_rs::analyze_all(&imported);
+  assert!(diags.iter().any(|diag| diag.code == "E0011"));
+  assert!(diags.iter().all(|diag| diag.primary_span == (0..0)));
+  let err = dbml_rs::analyze(&imported).unwrap_err();
+  assert_eq!(err.variant.message(), diags[0].message);
+
+  Ok(())
+}
+
+#[test]
+fn import_mysql() -> Result<()> {
+  use dbml_rs::importer::mysql::import;
+
+  for name in ["general_schema", "multiple_schema"] {
+    let input = fs::read_to_string(format!("tests/dbml/mysql_importer/{}.in.sql", name))?;
+    let expected = read_importer_fixture(&format!("tests/dbml/mysql_importer/{}.out.dbml", name))?;
+
+    let imported = import(&input).unwrap_or_else(|diag| panic!("{}: {}", name, diag));
+    let expected = dbml_rs::parse_dbml_unchecked(&expected).unwrap();
+
+    assert_eq!(
+      format_blocks(&imported),
+      format_blocks(&expected),
+      "{}: import mismatch",
+      name
+    );
+  }
+
+  Ok(())
+}
+
+#[test]
+fn import_mssql() -> Result<()> {
+  use dbml_rs::ast::*;
+  use dbml_rs::importer::mssql::import;
+
+  for name in ["general_schema", "multiple_schema"] {
+    let input = fs::read_to_string(format!("tests/dbml/mssql_importer/{}.in.sql", name))?;
+    let expected = read_importer_fixture(&format!("tests/dbml/mssql_importer/{}.out.dbml", name))?;
+
+    let imported = import(&input).unwrap_or_else(|diag| panic!("{}: {}", name, diag));
+    let expected = dbml_rs::parse_dbml_unchecked(&expected).unwrap();
+
+    assert_eq!(
+      format_blocks(&imported),
+      format_blocks(&expected),
+      "{}: import mismatch",
+      name
+    );
+  }
+
+  let input = r#"
+CREATE TABLE [dbo].[customers] (
+  [id] int IDENTITY(1,1) NOT NULL,
+  [tier] nvarchar(16) NOT NULL DEFAULT ('basic'),
+  CONSTRAINT [PK_customers] PRIMARY KEY CLUSTERED ([id] ASC),
+  CONSTRAINT [CK_customers_tier] CHECK ([tier] IN (N'basic', N'premium'))
+)
+GO
+CREATE TABLE [sales].[invoices] (
+  [id] bigint IDENTITY(1,1) NOT NULL PRIMARY KEY,
+  [customer_id] int NOT NULL,
+  [total] decimal(10, 2) NOT NULL
+)
+GO
+ALTER TABLE [sales].[invoices] ADD CONSTRAINT [DF_invoices_total] DEFAULT ((0)) FOR [total]
+GO
+ALTER TABLE [sales].[invoices] WITH CHECK ADD CONSTRAINT [FK_invoices_customers]
+  FOREIGN KEY ([customer_id]) REFERENCES [dbo].[customers] ([id]) ON DELETE CASCADE
+GO
+CREATE INDEX [IX_invoices_customer_id] ON [sales].[invoices] ([customer_id]) INCLUDE ([total])
+GO
+"#;
+  let mut imported = import(input).unwrap();
+
+  assert_eq!(
+    imported.to_dbml(),
+    dbml_rs::parse_dbml_unchecked(
+      r#"Enum customers_tier_enum {
+  basic
+  premium
+}
+
+Table customers {
+  id int [pk, increment]
+  tier customers_tier_enum [not null, default: 'basic']
+}
+
+Table sales.invoices {
+  id bigint [pk, increment]
+  customer_id int [not null]
+  total decimal(10, 2) [not null, default: 0]
+
+  Indexes {
+    customer_id [name: 'IX_invoices_customer_id']
+  }
+}
+
+Ref FK_invoices_customers: customers.id < sales.invoices.customer_id [delete: cascade]
+"#
+    )
+    .unwrap()
+    .to_dbml()
+  );
+
+  // the imported schema is semantically valid once its database type is known
+  imported.blocks.insert(
+    0,
+    TopLevelBlock::Project(ProjectBlock {
+      database_type: DatabaseType::MSSQL,
+      ..Default::default()
+    }),
+  );
+  dbml_rs::analyze(&imported).unwrap();
+
+  let mut imported = import("CREATE TABLE [users] ([id] int PRIMARY KEY, [id] nvarchar(10));").unwrap();
+  imported.blocks.insert(
+    0,
+    TopLevelBlock::Project(ProjectBlock {
+      database_type: DatabaseType::MSSQL,
+      ..Default::default()
+    }),
+  );
+  let err = dbml_rs::analyze(&imported).unwrap_err();
+  assert_eq!(err.variant.message(), "Duplicate column name");
+
+  Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn import_sqlite() -> Result<()> {
+  use dbml_rs::ast::*;
+  use dbml_rs::importer::sqlite::import_file;
+
+  let path = std::env::temp_dir().join(format!("dbml-rs-import-sqlite-{}.db", std::process::id()));
+  let _ = fs::remove_file(&path);
+
+  let conn = rusqlite::Connection::open(&path).unwrap();
+  conn
+    .execute_batch(
+      r#"
+CREATE TABLE users (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  email VARCHAR(255) NOT NULL UNIQUE,
+  name TEXT,
+  is_active BOOLEAN NOT NULL DEFAULT 1,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE posts (
+  id INTEGER PRIMARY KEY,
+  user_id INTEGER NOT NULL REFERENCES users ON DELETE CASCADE,
+  title VARCHAR(100) DEFAULT 'untitled',
+  score DECIMAL(5, 2) DEFAULT 2.5
+);
+CREATE TABLE post_tags (
+  post_id INTEGER REFERENCES posts(id),
+  tag TEXT,
+  PRIMARY KEY (post_id, tag)
+);
+CREATE INDEX posts_user_id ON posts (user_id);
+CREATE UNIQUE INDEX posts_title ON posts (user_id, lower(title));
+CREATE VIEW active_users AS SELECT * FROM users WHERE is_active;
+"#,
+    )
+    .unwrap();
+  drop(conn);
+
+  let mut imported = import_file(&path).unwrap();
+  fs::remove_file(&path)?;
+
+  assert_eq!(
+    imported.to_dbml(),
+    dbml_rs::parse_dbml_unchecked(
+      r#"Table users {
+  id integer [pk, increment]
+  email varchar(255) [not null, unique]
+  name text
+  is_active boolean [not null, default: 1]
+  created_at timestamp [default: `CURRENT_TIMESTAMP`]
+}
+
+Table posts {
+  id integer [pk]
+  user_id integer [not null]
+  title varchar(100) [default: 'untitled']
+  score decimal(5, 2) [default: 2.5]
+
+  Indexes {
+    user_id [name: 'posts_user_id']
+    (user_id, `lower(title)`) [unique, name: 'posts_title']
+  }
+}
+
+Table post_tags {
+  post_id integer
+  tag text
+
+  Indexes {
+    (post_id, tag) [pk]
+  }
+}
+
+Ref: users.id < posts.user_id [delete: cascade]
+
+Ref: posts.id < post_tags.post_id
+"#
+    )
+    .unwrap()
+    .to_dbml()
+  );
+
+  imported.blocks.insert(
+    0,
+    TopLevelBlock::Project(ProjectBlock {
+      database_type: DatabaseType::SQLite,
+      ..Default::default()
+    }),
+  );
+  dbml_rs::analyze(&imported).unwrap();
+
+  Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_model() -> Result<()> {
+  use dbml_rs::generator::json::generate_value;
+  use dbml_rs::importer::json::import;
+
+  let input = r#"Project shop {
+  database_type: 'PostgreSQL'
+  Note: 'An online shop'
+}
+
+Enum sales.status {
+  pending [note: 'Not paid yet']
+  paid
+}
+
+Table users as U [headercolor: #3498DB] {
+  id int [pk, increment]
+  email varchar(255) [not null, unique, note: 'Login']
+  tags "text[]"
+  created_at timestamp [default: `now()`]
+}
+
+Table sales.orders {
+  id int [pk]
+  user_id int [ref: > users.id]
+  status sales.status [default: 'pending']
+  total decimal(10, 2) [default: 0.5]
+
+  Indexes {
+    (user_id, `lower(status)`) [unique, name: 'orders_user_status']
+    total [type: btree, note: 'For reports']
+  }
+
+  Note: 'Placed orders'
+}
+
+TableGroup store {
+  U
+  sales.orders
+}
+
+Ref fk_self: users.id - users.id [delete: cascade]
+"#;
+  let ast = dbml_rs::parse_dbml_unchecked(input).unwrap();
+  let json = generate_value(&ast).unwrap();
+
+  assert_eq!(json["databaseType"], "PostgreSQL");
+  assert_eq!(json["schemas"][0]["name"], "public");
+  assert_eq!(json["schemas"][1]["name"], "sales");
+  assert_eq!(
+    json["schemas"][0]["tables"][0]["fields"][1],
+    serde_json::json!({
+      "name": "email",
+      "type": { "schemaName": null, "type_name": "varchar(255)", "args": "255" },
+      "pk": false,
+      "unique": true,
+      "increment": false,
+      "not_null": true,
+      "note": "Login",
+      "dbdefault": null,
+    })
+  );
+  assert_eq!(
+    json["schemas"][1]["tables"][0]["fields"][2]["type"],
+    serde_json::json!({ "schemaName": "sales", "type_name": "status", "args": null })
+  );
+  assert_eq!(
+    json["schemas"][0]["tableGroups"][0]["tables"][0],
+    serde_json::json!({ "tableName": "users", "schemaName": null })
+  );
+  assert_eq!(
+    json["schemas"][0]["refs"][1]["endpoints"],
+    serde_json::json!([
+      { "schemaName": "sales", "tableName": "orders", "fieldNames": ["user_id"], "relation": "*" },
+      { "schemaName": null, "tableName": "users", "fieldNames": ["id"], "relation": "1" },
+    ])
+  );
+
+  let json = json.to_string();
+  let imported = import(&json).unwrap();
+  dbml_rs::analyze(&imported).unwrap();
+
+  assert_eq!(
+    imported.to_dbml(),
+    dbml_rs::parse_dbml_unchecked(
+      r#"Project shop {
+  database_type: 'PostgreSQL'
+  Note: 'An online shop'
+}
+
+Enum sales.status {
+  pending [note: 'Not paid yet']
+  paid
+}
+
+Table users as U [headercolor: #3498DB] {
+  id int [pk, increment]
+  email varchar(255) [not null, unique, note: 'Login']
+  tags "text[]"
+  created_at timestamp [default: `now()`]
+}
+
+Table sales.orders {
+  id int [pk]
+  user_id int
+  status sales.status [default: 'pending']
+  total decimal(10, 2) [default: 0.5]
+
+  Indexes {
+    (user_id, `lower(status)`) [unique, name: 'orders_user_status']
+    total [type: btree, note: 'For reports']
+  }
+
+  Note: 'Placed orders'
+}
+
+TableGroup store {
+  users
+  sales.orders
+}
+
+Ref fk_self: users.id - users.id [delete: cascade]
+
+Ref: sales.orders.user_id > users.id
+"#
+    )
+    .unwrap()
+    .to_dbml()
+  );
+
+  let err = import(r#"{ "schemas": [{ "name": "public", "tables": [{ "fields": [] }] }] }"#).unwrap_err();
+  assert_eq!(err.message, "expected a string at '/schemas/0/tables/0/name'");
+
+  let err = import(
+    r##"{ "schemas": [{ "name": "public", "tables": [{ "name": "users", "headerColor": "#ABCD", "fields": [] }] }] }"##,
+  )
+  .unwrap_err();
+  assert_eq!(
+    err.message,
+    "expected a '#RGB' or '#RRGGBB' color at '/schemas/0/tables/0/headerColor'"
+  );
+
+  let err = import("{\n  \"schemas\": [,]\n}").unwrap_err();
+  assert_eq!(err.primary_span, 16..16);
+  assert_eq!(err.message, "expected value");
+
+  Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() -> Result<()> {
+  use dbml_rs::ast::SchemaBlock;
+
+  let parsed = dbml_rs::parse_dbml_unchecked("Ref: posts.user_id > users.id [delete: set null]").unwrap();
+  let value = serde_json::to_value(&parsed)?;
+  let r#ref = &value["blocks"][0]["Ref"];
+  assert_eq!(r#ref["rel"], "Many2One");
+  assert_eq!(r#ref["lhs"]["compositions"][0]["to_string"], "user_id");
+  assert_eq!(r#ref["settings"]["on_delete"], "SetNull");
+
+  for path in read_dbml_dir("tests/dbml")? {
+    let content = fs::read_to_string(&path)?;
+    let parsed = dbml_rs::parse_dbml_unchecked(&content).unwrap();
+
+    let serialized = serde_json::to_string(&parsed)?;
+    let deserialized: SchemaBlock = serde_json::from_str(&serialized)?;
+
+    assert_eq!(deserialized.input, "", "{:?}: input serialized", path);
+    assert_eq!(
+      format!("{:?}", deserialized.blocks),
+      format!("{:?}", parsed.blocks),
+      "{:?}: round trip mismatch",
+      path
+    );
+  }
+
+  // the spans of a deserialized schema are out of its empty input
+  let content = "Project p {\n  database_type: 'PostgreSQL'\n}\n\nTable users {\n  id int\n  id text\n}\n";
+  let parsed = dbml_rs::parse_dbml_unchecked(content).unwrap();
+  let deserialized: SchemaBlock = serde_json::from_str(&serde_json::to_string(&parsed)?)?;
+  let err = dbml_rs::analyze(&deserialized).unwrap_err();
+  assert_eq!(err.variant.message(), "Duplicate column name");
+
+  Ok(())
+}
+
+#[test]
+fn diff_schemas() {
+  use dbml_rs::diff::*;
+
+  let old = dbml_rs::parse_dbml_unchecked(
+    r#"Project shop {
+  database_type: 'PostgreSQL'
+}
+
+Enum status {
+  draft
+  published
+}
+
+Table users {
+  id int [pk]
+  email varchar(255) [not null]
+  bio text
+}
+
+Table posts {
+  id int [pk]
+  user_id int [ref: > users.id]
+  title varchar(255) [not null]
+  status status [default: 'draft']
+  body text
+
+  Indexes {
+    title
+  }
+}
+
+Table logs {
+  id int [pk]
+  message text
+}
+
+Ref: posts.user_id > users.id [delete: cascade]
+"#,
+  )
+  .unwrap();
+  let new = dbml_rs::parse_dbml_unchecked(
+    r#"Project shop {
+  database_type: 'PostgreSQL'
+}
+
+Enum status {
+  published
+  archived
+}
+
+Table users {
+  id int [pk]
+  email varchar(255) [not null, unique]
+  bio text
+}
+
+Table posts {
+  id int [pk]
+  user_id int
+  title varchar(100)
+  status status [default: 'published']
+  views "int[]"
+
+  Indexes {
+    (user_id, title) [unique]
+  }
+}
+
+Table audit_logs {
+  id int [pk]
+  message text
+}
+
+Table tags {
+  name varchar(50) [pk]
+}
+
+Ref: users.id < posts.user_id [delete: restrict]
+"#,
+  )
+  .unwrap();
+
+  let diff = diff(&old, &new);
+
+  assert!(matches!(diff.tables[0], TableDiff::Changed { .. }));
+  assert!(diff.tables[2].is_renamed());
+  match &diff.tables[1] {
+    TableDiff::Changed { changes, .. } => {
+      match &changes[0] {
+        TableChange::ColumnChanged { new, changes, .. } => {
+          assert_eq!(new.name.to_string, "title");
+          assert_eq!(changes, &[ColumnChange::Type, ColumnChange::Nullable]);
+        }
+        change => panic!("unexpected change: {:?}", change),
+      }
+    }
+    table_diff => panic!("unexpected table diff: {:?}", table_diff),
+  }
+  match &diff.refs[..] {
+    [RefDiff::Changed { changes, .. }] => assert_eq!(changes, &[RefChange::OnDelete]),
+    refs => panic!("unexpected ref diffs: {:?}", refs),
+  }
+  assert!(!diff.is_empty());
+
+  assert_eq!(
+    diff.to_string(),
+    r#"~ Table users
+  ~ Column email: unique added
+~ Table posts
+  ~ Column title: type varchar(255) -> varchar(100)
+  ~ Column title: not null -> null
+  ~ Column status: default 'draft' -> 'published'
+  + Column views "int[]"
+  - Column body
+  + Index (user_id, title) [unique]
+  - Index title
+~ Table logs renamed to audit_logs
++ Table tags
+~ Enum status
+  + Value archived
+  - Value draft
+~ Ref: users.id < posts.user_id
+  ~ delete: cascade -> restrict
+"#
+  );
+
+  assert!(dbml_rs::diff::diff(&new, &new).is_empty());
+}
+
+#[test]
+fn migrate_postgres() {
+  use dbml_rs::generator::postgres;
+
+  let old = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Enum kind {
+  article
+  video
+}
+
+Table users {
+  id int [pk]
+  email varchar(255) [not null]
+}
+
+Table posts {
+  id int [pk]
+  user_id int [ref: > users.id]
+  title varchar(255) [not null]
+  kind kind [default: 'article']
+  body text
+
+  Indexes {
+    title
+  }
+}
+
+Table comments {
+  id int [pk]
+  post_id int [ref: > posts.id]
+}
+
+Table votes {
+  id int [pk]
+  comment_id int [ref: > comments.id]
+}
+"#,
+  )
+  .unwrap();
+  let new = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Enum kind {
+  article
+  podcast
+  video
+}
+
+Table users {
+  id int [pk]
+  email varchar(255) [not null, unique]
+  bio text [note: 'About the author']
+}
+
+Table posts {
+  id int [pk]
+  user_id int
+  title varchar(100) [not null]
+  kind kind [default: 'article']
+
+  Indexes {
+    (user_id, title) [unique]
+  }
+}
+
+Table tags {
+  name varchar(50) [pk]
+  post_id int [ref: > posts.id]
+}
+
+Ref: users.id < posts.user_id [delete: cascade]
+"#,
+  )
+  .unwrap();
+
+  let up = postgres::migrate(&old, &new).unwrap();
+
+  assert!(up.is_destructive());
+  assert_eq!(
+    up.to_string(),
+    r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_user_id_fkey";
+DROP INDEX "posts_title_idx";
+ALTER TYPE "kind" ADD VALUE 'podcast' BEFORE 'video';
+CREATE TABLE "tags" (
+  "name" varchar(50) PRIMARY KEY,
+  "post_id" integer
+);
+-- destructive: may lose data
+ALTER TABLE "users" ADD UNIQUE ("email");
+ALTER TABLE "users" ADD COLUMN "bio" text;
+-- destructive: may lose data
+ALTER TABLE "posts" ALTER COLUMN "title" TYPE varchar(100) USING "title"::varchar(100);
+-- destructive: may lose data
+ALTER TABLE "posts" DROP COLUMN "body";
+-- destructive: may lose data
+DROP TABLE "votes";
+-- destructive: may lose data
+DROP TABLE "comments";
+CREATE UNIQUE INDEX ON "posts" ("user_id", "title");
+ALTER TABLE "posts" ADD FOREIGN KEY ("user_id") REFERENCES "users" ("id") ON DELETE CASCADE;
+ALTER TABLE "tags" ADD FOREIGN KEY ("post_id") REFERENCES "posts" ("id");
+COMMENT ON COLUMN "users"."bio" IS 'About the author';
+"#
+  );
+
+  let down = postgres::migrate(&new, &old).unwrap();
+
+  assert_eq!(
+    down.to_string(),
+    r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_user_id_fkey";
+ALTER TABLE "users" DROP CONSTRAINT "users_email_key";
+DROP INDEX "posts_user_id_title_idx";
+ALTER TYPE "kind" RENAME TO "kind_old";
+CREATE TYPE "kind" AS ENUM ('article', 'video');
+ALTER TABLE "posts" ALTER COLUMN "kind" DROP DEFAULT;
+-- destructive: may lose data
+ALTER TABLE "posts" ALTER COLUMN "kind" TYPE "kind" USING "kind"::text::"kind";
+ALTER TABLE "posts" ALTER COLUMN "kind" SET DEFAULT 'article';
+CREATE TABLE "comments" (
+  "id" integer PRIMARY KEY,
+  "post_id" integer
+);
+CREATE TABLE "votes" (
+  "id" integer PRIMARY KEY,
+  "comment_id" integer
+);
+-- destructive: may lose data
+ALTER TABLE "users" DROP COLUMN "bio";
+-- destructive: may lose data
+ALTER TABLE "posts" ALTER COLUMN "title" TYPE varchar(255) USING "title"::varchar(255);
+ALTER TABLE "posts" ADD COLUMN "body" text;
+-- destructive: may lose data
+DROP TABLE "tags";
+DROP TYPE "kind_old";
+CREATE INDEX ON "posts" ("title");
+ALTER TABLE "posts" ADD FOREIGN KEY ("user_id") REFERENCES "users" ("id");
+ALTER TABLE "comments" ADD FOREIGN KEY ("post_id") REFERENCES "posts" ("id");
+ALTER TABLE "votes" ADD FOREIGN KEY ("comment_id") REFERENCES "comments" ("id");
+"#
+  );
+
+  assert!(postgres::migrate(&new, &new).unwrap().is_empty());
+
+  let old = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Enum mood {
+  happy
+  sad
+}
+
+Table users {
+  id int [pk]
+}
+"#,
+  )
+  .unwrap();
+  let new = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int [pk]
+  handle varchar(50) [not null]
+  joined_at timestamp [not null, default: `now()`]
+}
+"#,
+  )
+  .unwrap();
+
+  assert_eq!(
+    postgres::migrate(&old, &new).unwrap().to_string(),
+    r#"-- destructive: may lose data
+ALTER TABLE "users" ADD COLUMN "handle" varchar(50) NOT NULL;
+ALTER TABLE "users" ADD COLUMN "joined_at" timestamp NOT NULL DEFAULT now();
+-- destructive: may lose data
+DROP TYPE "mood";
+"#
+  );
+
+  let old = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int
+  name text
+}
+"#,
+  )
+  .unwrap();
+  let new = dbml_rs::parse_dbml_unchecked(
+    r#"Project blog {
+  database_type: 'PostgreSQL'
+}
+
+Table users {
+  id int [pk]
+  name text [not null]
+}
+"#,
+  )
+  .unwrap();
+
+  assert_eq!(
+    postgres::migrate(&old, &new).unwrap().to_string(),
+    r#"-- destructive: may lose data
+ALTER TABLE "users" ALTER COLUMN "name" SET NOT NULL;
+-- destructive: may lose data
+ALTER TABLE "users" ADD PRIMARY KEY ("id");
+"#
+  );
+}
+
+#[test]
+fn classify_schema_changes() {
+  use dbml_rs::diff::*;
+
+  let old = dbml_rs::parse_dbml_unchecked(
+    r#"Enum status {
+  draft
+  published
+}
+
+Table users {
+  id int [pk]
+  email varchar(255)
+  name varchar(100)
+  score real
+  bio text
+}
+
+Table posts {
+  id int [pk]
+  user_id int
+  status status [default: 'draft']
+  price decimal(10, 2)
+}
+"#,
+  )
+  .unwrap();
+  let input = r#"Enum status {
+  published
+  archived
+}
+
+Table users {
+  id bigint [pk]
+  email varchar(50) [unique]
+  name varchar(100) [not null]
+  score "double precision"
+  age int [not null]
+}
+
+Table posts {
+  id int [pk]
+  user_id int [ref: > users.id]
+  status status [default: 'draft']
+  price decimal(12, 2)
+}
+"#;
+  let new = dbml_rs::parse_dbml_unchecked(input).unwrap();
+  let source_map = dbml_rs::SourceMap::new(input);
+
+  let findings = diff(&old, &new).classify();
+  let report: Vec<_> = findings
+    .iter()
+    .map(|finding| finding.render(&source_map, "schema.dbml"))
+    .collect();
+
+  assert_eq!(
+    report,
+    [
+      "schema.dbml:7:3: safe: column users.id is widened from int to bigint",
+      "schema.dbml:8:3: breaking: column users.email is narrowed from varchar(255) to varchar(50)",
+      "schema.dbml:8:3: risky: column users.email becomes unique, which existing duplicates violate",
+      "schema.dbml:9:3: breaking: column users.name becomes not null without a default",
+      "schema.dbml:10:3: safe: column users.score is widened from real to \"double precision\"",
+      "schema.dbml:11:3: breaking: not null column users.age is added without a default",
+      "schema.dbml:6:7: breaking: column users.bio is dropped",
+      "schema.dbml:18:3: safe: column posts.price is widened from decimal(10, 2) to decimal(12, 2)",
+      "schema.dbml:3:3: safe: value archived is added to enum status",
+      "schema.dbml:17:3: breaking: value draft is removed from enum status but column posts.status defaults to it",
+      "schema.dbml:16:16: risky: ref posts.user_id > users.id is added, which existing rows may violate",
+    ]
+  );
+  assert_eq!(
+    findings.iter().map(|finding| finding.compatibility).max(),
+    Some(Compatibility::Breaking)
+  );
+  assert!(diff(&new, &new).classify().is_empty());
+}